@@ -3,16 +3,18 @@
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
 use std::fmt::{Display, Formatter};
-use std::io::Error;
+use std::io::{Error, Write};
 use std::process::{Child, Command, Output, Stdio};
+use std::sync::Mutex;
 
 use CmdErrorCause::UnsuccessfulStatus;
 
 use crate::cmd::CmdErrorCause::Io;
-use crate::cmd::IoErrorCause::{StartFail, WaitFail};
+use crate::cmd::IoErrorCause::{StartFail, WaitFail, WriteFail};
 
 #[derive(Debug)]
-pub enum IoErrorCause { StartFail, WaitFail }
+#[allow(clippy::enum_variant_names)]
+pub enum IoErrorCause { StartFail, WriteFail, WaitFail }
 
 #[derive(Debug)]
 pub enum CmdErrorCause {
@@ -39,6 +41,7 @@ impl Display for CmdError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let reason = match &self.cause {
             Io(StartFail, err) => format!("Fail to start command {}. \nCause: {}", self.cmd, err),
+            Io(WriteFail, err) => format!("Fail to write the input of command {}. \nCause: {}", self.cmd, err),
             Io(WaitFail, err) => format!("Fail to wait for command {} exit. \nCause: {}", self.cmd, err),
             UnsuccessfulStatus(code, stdout, stderr) => format!(
                 "Unsuccessful command {} execution. \nCause: Status code {:?}.\n stdout: {}.\n stderr: {}",
@@ -55,11 +58,57 @@ impl Display for CmdError {
 
 pub type Result<T> = std::result::Result<T, CmdError>;
 
+/// Package managers like `apt` hold a system-wide lock.
+static PKG_MANAGER_LOCK: Mutex<()> = Mutex::new(());
+
+const PKG_MANAGERS: [&str; 10] = ["apt-get", "apt", "dpkg", "add-apt-repository", "dnf", "rpm", "pacman", "brew", "winget", "flatpak"];
+
+fn runs_pkg_manager(cmd: &str, args: &[&str]) -> bool {
+    let is_pkg_manager = |program: &str| PKG_MANAGERS.contains(&program);
+
+    match cmd {
+        "sudo" => args
+            .iter()
+            .find(|arg| !arg.starts_with('-'))
+            .is_some_and(|program| is_pkg_manager(program)),
+
+        "bash" | "sh" | "powershell" => args
+            .iter()
+            .any(|script| script
+                .split_whitespace()
+                .any(is_pkg_manager)
+            ),
+
+        _ => is_pkg_manager(cmd),
+    }
+}
+
+const SECRET_FLAGS: [&str; 3] = ["--token", "--password", "--registration-token"];
+
+fn redacted(args: &[&str]) -> String {
+    args.iter()
+        .enumerate()
+        .map(|(i, arg)| match arg.split_once('=') {
+            _ if i > 0 && SECRET_FLAGS.contains(&args[i - 1]) => "***".to_string(),
+            Some((flag, _)) if SECRET_FLAGS.contains(&flag) => format!("{flag}=***"),
+            _ => arg.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn exec_cmd_async(cmd: &str, args: &[&str]) -> Result<Child> {
+    spawn(cmd, args, &[])
+}
+
+fn spawn(cmd: &str, args: &[&str], envs: &[(&str, &str)]) -> Result<Child> {
     let io_err = move |cause: IoErrorCause| move |err: Error| CmdError::from(cmd, Io(cause, err));
 
+    debug!("$ {cmd} {}", redacted(args));
+
     Command::new(cmd)
         .args(args)
+        .envs(envs.iter().copied())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -68,6 +117,20 @@ pub fn exec_cmd_async(cmd: &str, args: &[&str]) -> Result<Child> {
 }
 
 pub fn exec_cmd(cmd: &str, args: &[&str]) -> Result<Output> {
+    exec(cmd, args, &[], None)
+}
+
+/// Keeps secrets like passwords out of the process arguments and the log.
+pub fn exec_cmd_input(cmd: &str, args: &[&str], input: &str) -> Result<Output> {
+    exec(cmd, args, &[], Some(input))
+}
+
+/// Keeps secrets like tokens out of the process arguments and the log.
+pub fn exec_cmd_env(cmd: &str, args: &[&str], envs: &[(&str, &str)]) -> Result<Output> {
+    exec(cmd, args, envs, None)
+}
+
+fn exec(cmd: &str, args: &[&str], envs: &[(&str, &str)], input: Option<&str>) -> Result<Output> {
     let io_err = move |cause: IoErrorCause| move |err: Error| CmdError::from(cmd, Io(cause, err));
 
     let err = |cause: CmdErrorCause| CmdError::from(cmd, cause);
@@ -84,6 +147,16 @@ pub fn exec_cmd(cmd: &str, args: &[&str]) -> Result<Output> {
         }
     };
 
+    let write_input = |mut child: Child| {
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            stdin
+                .write_all(input.as_bytes())
+                .map_err(io_err(WriteFail))?;
+        }
+
+        Ok(child)
+    };
+
     let wait_child = |child: Child| {
         child
             .wait_with_output()
@@ -91,8 +164,23 @@ pub fn exec_cmd(cmd: &str, args: &[&str]) -> Result<Output> {
             .and_then(check_success)
     };
 
-    exec_cmd_async(cmd, args)
+    let _pkg_manager_guard = runs_pkg_manager(cmd, args).then(|| PKG_MANAGER_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+    );
+
+    spawn(cmd, args, envs)
+        .and_then(write_input)
         .and_then(wait_child)
+        .inspect(|output| debug!(
+            "stdout: {}\nstderr: {}",
+            String::from_utf8_lossy(&output.stdout).trim(),
+            String::from_utf8_lossy(&output.stderr).trim(),
+        ))
+}
+
+pub fn exec_powershell(script: &str) -> Result<Output> {
+    exec_cmd("powershell", &["-NoProfile", "-NonInteractive", "-Command", script])
 }
 
 pub fn print_output(output: Output) {
@@ -100,11 +188,11 @@ pub fn print_output(output: Output) {
     let stderr = String::from_utf8_lossy(&output.stderr);
 
     if !stdout.trim().is_empty() {
-        println!("stdout: {stdout}");
+        verbose!("stdout: {stdout}");
     }
 
     if !stderr.trim().is_empty() {
-        println!("stderr: {stderr}");
+        verbose!("stderr: {stderr}");
     }
 }
 
@@ -153,6 +241,42 @@ mod tests {
         println!();
     }
 
+    #[test]
+    fn detects_pkg_manager_commands() {
+        assert!(runs_pkg_manager("sudo", &["apt-get", "--yes", "install", "git"]));
+        assert!(runs_pkg_manager("sudo", &["-E", "dpkg", "-i", "zoom.deb"]));
+        assert!(runs_pkg_manager("bash", &["-c", "sudo apt-get update"]));
+        assert!(runs_pkg_manager("powershell", &["-NoProfile", "-Command", "winget install --id Zoom.Zoom"]));
+        assert!(!runs_pkg_manager("sudo", &["tee", "/etc/apt/sources.list.d/gh.list"]));
+        assert!(!runs_pkg_manager("tar", &["-xf", "go.tar.gz"]));
+    }
+
+    #[test]
+    fn redacts_secret_args() {
+        assert_eq!(
+            "gitlab-runner register --url https://gitlab.com --token *** --executor shell",
+            redacted(&["gitlab-runner", "register", "--url", "https://gitlab.com", "--token", "glrt-abc", "--executor", "shell"]),
+        );
+        assert_eq!("login --password=*** --user root", redacted(&["login", "--password=secret", "--user", "root"]));
+        assert_eq!("-c echo --token", redacted(&["-c", "echo --token"]));
+    }
+
+    #[test]
+    fn writes_input_to_stdin() -> Result<()> {
+        let output = exec_cmd_input("cat", &[], "SELECT 1;")?;
+
+        assert_eq!("SELECT 1;", String::from_utf8_lossy(&output.stdout));
+        Ok(())
+    }
+
+    #[test]
+    fn sets_env_vars() -> Result<()> {
+        let output = exec_cmd_env("sh", &["-c", "printf %s \"$CI_SERVER_TOKEN\""], &[("CI_SERVER_TOKEN", "glrt-abc")])?;
+
+        assert_eq!("glrt-abc", String::from_utf8_lossy(&output.stdout));
+        Ok(())
+    }
+
     #[test]
     fn execute_ls() {
         assert_exec_success("ls", &["."]);
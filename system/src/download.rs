@@ -2,48 +2,432 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
+use std::collections::HashMap;
+use std::env;
 use std::fmt::{Display, Formatter};
-use std::fs::File;
+use std::fs;
 use std::io;
-use std::io::{ErrorKind};
+use std::io::{ErrorKind, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-use reqwest::{blocking, Url};
+use reqwest::header::{ACCEPT_RANGES, CONTENT_LENGTH, RANGE};
+use reqwest::tls::TlsInfo;
+use reqwest::{redirect, Client, ClientBuilder, Method, NoProxy, Proxy, Response, StatusCode, Url};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::runtime;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
+use crate::download::auth::Auth;
 use crate::download::gpg::GpgKey;
-use crate::download::hashing::Hash;
+use crate::download::hashing::HashAlgorithm::Sha256;
+use crate::download::hashing::{calculate_sha256, parse_releases_index, parse_sha256sum, Hash, HashAlgorithm};
+use crate::download::minisign::MinisignKey;
+use crate::download::provenance::Provenance;
+use crate::download::tls::PublicKeyPin;
 use crate::tmp::TmpWorkingDir;
-use DownloadRequestError::{InsecureProtocol, InvalidUrl};
+use DownloadRequestError::{InsecureProtocol, InvalidUrl, ResolveFailed, UnexpectedVersion};
 
 pub mod hashing;
 pub mod gpg;
+pub mod cache;
+pub mod auth;
+pub mod tls;
+pub mod minisign;
+pub mod provenance;
+
+pub fn parse_proxy(proxy_raw: &str) -> Result<Url, String> {
+    Url::parse(proxy_raw)
+        .map_err(|error| format!("Invalid proxy URL {}. Cause: {}", proxy_raw, error))
+}
+
+pub const CONNECT_TIMEOUT_SECS: u64 = 30;
+
+pub const READ_TIMEOUT_SECS: u64 = 60;
+
+/// Reads a rate like curl's `--limit-rate`, e.g., `5M`.
+pub fn parse_rate(rate_raw: &str) -> Result<u64, String> {
+    let rate = rate_raw.trim();
+    let (digits, unit) = match rate.char_indices().last() {
+        Some((i, suffix)) if suffix.is_ascii_alphabetic() => (&rate[..i], suffix.to_ascii_uppercase()),
+        _ => (rate, 'B'),
+    };
+
+    let multiplier: u64 = match unit {
+        'B' => 1,
+        'K' => 1024,
+        'M' => 1024 * 1024,
+        'G' => 1024 * 1024 * 1024,
+        _ => return Err(format!("Invalid rate unit in {rate_raw}, use K, M, or G")),
+    };
+
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|amount| amount.checked_mul(multiplier))
+        .filter(|bytes_per_sec| *bytes_per_sec > 0)
+        .ok_or_else(|| format!("Invalid rate {rate_raw}, e.g., 500K or 5M"))
+}
+
+/// Gives each chunk of the concurrent downloads the next slot of time at the rate.
+#[derive(Debug)]
+struct RateLimit {
+    bytes_per_sec: u64,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimit {
+    fn new(bytes_per_sec: u64) -> Self {
+        RateLimit { bytes_per_sec, next_slot: Mutex::new(Instant::now()) }
+    }
+
+    async fn throttle(&self, bytes: usize) {
+        let duration = Duration::from_secs_f64(bytes as f64 / self.bytes_per_sec as f64);
+        let slot_end = match self.next_slot.lock() {
+            Ok(mut next_slot) => {
+                let slot_end = (*next_slot).max(Instant::now()) + duration;
+
+                *next_slot = slot_end;
+                slot_end
+            }
+            Err(_) => return,
+        };
+
+        tokio::time::sleep_until(slot_end.into()).await;
+    }
+}
+
+static REQUIRE_INTEGRITY: OnceLock<bool> = OnceLock::new();
+
+pub fn set_require_integrity(required: bool) {
+    let _ = REQUIRE_INTEGRITY.set(required);
+}
+
+pub fn integrity_required() -> bool {
+    REQUIRE_INTEGRITY.get().copied().unwrap_or(false)
+}
+
+/// Below this size, the extra connections don't pay off.
+const CHUNKED_MIN_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Inclusive byte ranges, like the `Range` header takes them.
+fn chunk_ranges(size: u64, connections: usize) -> Vec<(u64, u64)> {
+    let chunk_size = size.div_ceil(connections.max(1) as u64).max(1);
+
+    (0..size)
+        .step_by(chunk_size as usize)
+        .map(|start| (start, (start + chunk_size).min(size) - 1))
+        .collect()
+}
+
+fn offset_range(offset: u64) -> Option<String> {
+    (offset > 0).then(|| format!("bytes={offset}-"))
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Timeouts {
+    connect: Duration,
+    read: Duration,
+}
+
+impl Timeouts {
+    pub fn new(connect: Duration, read: Duration) -> Self {
+        Timeouts { connect, read }
+    }
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(CONNECT_TIMEOUT_SECS), Duration::from_secs(READ_TIMEOUT_SECS))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DownloadOptions {
+    /// Pre-fetched artifacts that replace the downloads when running offline.
+    artifacts_dir: Option<PathBuf>,
+    proxy: Option<Url>,
+
+    timeouts: Timeouts,
+    rate_limit: Option<Arc<RateLimit>>,
+    connections: usize,
+    pins: HashMap<String, Vec<PublicKeyPin>>,
+
+    /// SHA-256 of the completed downloads by URL for the lockfile.
+    digests: Option<Arc<Mutex<HashMap<Url, String>>>>,
+    locked_digests: Arc<HashMap<Url, String>>,
+}
+
+impl DownloadOptions {
+    /// Resolves every file from the directory, still checking its integrity.
+    pub fn offline(self, artifacts_dir: PathBuf) -> Self {
+        DownloadOptions { artifacts_dir: Some(artifacts_dir), ..self }
+    }
+
+    pub fn with_proxy(self, proxy: Url) -> Self {
+        DownloadOptions { proxy: Some(proxy), ..self }
+    }
+
+    pub fn with_timeouts(self, timeouts: Timeouts) -> Self {
+        DownloadOptions { timeouts, ..self }
+    }
+
+    pub fn with_rate_limit(self, bytes_per_sec: u64) -> Self {
+        DownloadOptions { rate_limit: Some(Arc::new(RateLimit::new(bytes_per_sec))), ..self }
+    }
+
+    pub fn with_connections(self, connections: usize) -> Self {
+        DownloadOptions { connections: connections.max(1), ..self }
+    }
+
+    pub fn with_pins(mut self, host: &str, pins: Vec<PublicKeyPin>) -> Self {
+        self.pins.entry(host.to_string()).or_default().extend(pins);
+        self
+    }
+
+    pub fn recording_digests(self) -> Self {
+        DownloadOptions { digests: Some(Default::default()), ..self }
+    }
+
+    /// Fails the downloads whose SHA-256 isn't the locked one.
+    pub fn with_locked_digests(self, locked_digests: HashMap<Url, String>) -> Self {
+        DownloadOptions { locked_digests: Arc::new(locked_digests), ..self }
+    }
+
+    pub fn artifacts_dir(&self) -> Option<&Path> {
+        self.artifacts_dir.as_deref()
+    }
+
+    pub fn digest_of(&self, url: &Url) -> Option<String> {
+        self.digests
+            .as_ref()
+            .and_then(|digests| digests.lock().ok()?.get(url).cloned())
+    }
+
+    /// Exports the proxy to the image commands, also in lowercase for curl.
+    pub fn export_proxy_env(&self) {
+        if let Some(proxy) = &self.proxy {
+            for var in ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"] {
+                env::set_var(var, proxy.as_str());
+            }
+        }
+
+        for var in ["HTTP_PROXY", "HTTPS_PROXY", "NO_PROXY"] {
+            let lowercase_var = var.to_lowercase();
+
+            if let (Ok(value), Err(_)) = (env::var(var), env::var(&lowercase_var)) {
+                env::set_var(lowercase_var, value);
+            }
+        }
+    }
+
+    fn client(&self) -> io::Result<Client> {
+        self.build_client(Client::builder())
+    }
+
+    /// Stops at the first response to tell the certificate of the requested host.
+    fn unredirected_client(&self) -> io::Result<Client> {
+        self.build_client(Client::builder().redirect(redirect::Policy::none()))
+    }
+
+    fn build_client(&self, builder: ClientBuilder) -> io::Result<Client> {
+        let builder = builder
+            .connect_timeout(self.timeouts.connect)
+            .read_timeout(self.timeouts.read)
+            .tls_info(true);
+
+        let builder = match &self.proxy {
+            Some(proxy) => Proxy::all(proxy.clone())
+                .map(|proxy| builder.proxy(proxy.no_proxy(NoProxy::from_env())))
+                .map_err(|error| io_err(format!("Invalid proxy {}: {}", proxy, error)))?,
+            None => builder,
+        };
+
+        builder
+            .build()
+            .map_err(|error| io_err(format!("Failed to build the HTTP client: {}", error)))
+    }
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        DownloadOptions {
+            artifacts_dir: None,
+            proxy: None,
+            timeouts: Timeouts::default(),
+            rate_limit: None,
+            connections: 1,
+            pins: HashMap::new(),
+            digests: None,
+            locked_digests: Arc::new(HashMap::new()),
+        }
+    }
+}
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum Integrity {
     Hash(Hash),
     Gpg(GpgKey),
+
+    /// Checksum file, like `<artifact>.sha256` or `SHASUMS256.txt`.
+    RemoteSha256 { url: Url },
+
+    /// Hash alone in a file, like Composer's `installer.sig`.
+    RemoteHash { algorithm: HashAlgorithm, url: Url },
+
+    /// JSON releases index, like Flutter's.
+    ReleasesIndex { url: Url },
+
+    /// The signature holds the file content once resolved.
+    Minisign { public_key: MinisignKey, signature_url: Url, signature: Option<String> },
     None,
 }
 
 impl Integrity {
-    pub fn check(&self, file_path: &Path) -> Result<bool, String> {
+    /// Fetches the remote checksum first, so it runs outside an async context.
+    pub fn check(&self, file_path: &Path, options: &DownloadOptions) -> Result<bool, String> {
         match self {
             Integrity::Hash(hash) => hash
                 .matches(file_path)
                 .map_err(|error| error.to_string()),
             Integrity::Gpg(key) => {
-                key.install()?;
+                key.install(options.artifacts_dir())?;
                 key.verify(file_path)
             }
+            Integrity::RemoteSha256 { .. } | Integrity::RemoteHash { .. } | Integrity::ReleasesIndex { .. } => {
+                let filename = file_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|error| error.to_string())?
+                    .block_on(self.resolve(&filename, options))?
+                    .check(file_path, options)
+            }
+            Integrity::Minisign { public_key, signature: Some(signature), .. } =>
+                public_key.verify(file_path, signature),
+            Integrity::Minisign { signature: None, .. } => {
+                let filename = file_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|error| error.to_string())?
+                    .block_on(self.resolve(&filename, options))?
+                    .check(file_path, options)
+            }
             Integrity::None => Ok(true),
         }
     }
+
+    pub fn minisign(public_key: MinisignKey, signature_url: Url) -> Self {
+        Integrity::Minisign { public_key, signature_url, signature: None }
+    }
+
+    pub async fn resolve(&self, filename: &str, options: &DownloadOptions) -> Result<Integrity, String> {
+        if let Integrity::Minisign { public_key, signature_url, signature: None } = self {
+            let signature = read_sidecar(signature_url, options)
+                .await
+                .map_err(|error| format!("Fail to fetch signature {}: {}", signature_url, error))?;
+
+            return Ok(Integrity::Minisign {
+                public_key: public_key.clone(),
+                signature_url: signature_url.clone(),
+                signature: Some(signature),
+            });
+        }
+
+        if let Integrity::RemoteHash { algorithm, url } = self {
+            let hash = read_sidecar(url, options)
+                .await
+                .map_err(|error| format!("Fail to fetch hash {}: {}", url, error))?;
+
+            return Ok(Integrity::Hash(Hash::new(algorithm.clone(), hash.trim().to_string())));
+        }
+
+        let (Integrity::RemoteSha256 { url } | Integrity::ReleasesIndex { url }) = self else {
+            return Ok(self.clone());
+        };
+
+        let checksums = read_sidecar(url, options)
+            .await
+            .map_err(|error| format!("Fail to fetch checksum {}: {}", url, error))?;
+
+        let hash = match self {
+            Integrity::ReleasesIndex { .. } => parse_releases_index(&checksums, filename),
+            _ => parse_sha256sum(&checksums, filename),
+        };
+
+        hash
+            .map(|hash| Integrity::Hash(Hash::new(Sha256, hash)))
+            .ok_or_else(|| format!("Checksum {} has no SHA-256 of {}", url, filename))
+    }
+}
+
+/// Fetches the checksum or signature file, or reads it from the artifacts offline.
+async fn read_sidecar(url: &Url, options: &DownloadOptions) -> Result<String, String> {
+    if url.scheme() != "https" {
+        return Err(format!("URL {} protocol is not HTTPS", url));
+    }
+
+    match options.artifacts_dir() {
+        Some(artifacts_dir) => {
+            let sidecar = url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .map(|name| artifacts_dir.join(name))
+                .ok_or_else(|| format!("URL {} has no filename to find its artifact", url))?;
+
+            fs::read_to_string(&sidecar)
+                .map_err(|error| format!("Fail to read {}: {}", sidecar.display(), error))
+        }
+        None => fetch_text(url, options).await.map_err(|error| error.to_string()),
+    }
+}
+
+async fn fetch_text(url: &Url, options: &DownloadOptions) -> io::Result<String> {
+    options
+        .client()?
+        .get(url.clone())
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|error| io_err(error.to_string()))?
+        .text()
+        .await
+        .map_err(|error| io_err(error.to_string()))
+}
+
+impl Display for Integrity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Integrity::Hash(hash) => format!("{} hash", hash.algorithm()),
+            Integrity::Gpg(_) => "GPG signature".to_string(),
+            Integrity::RemoteSha256 { url } => format!("SHA-256 hash from {}", url),
+            Integrity::RemoteHash { algorithm, url } => format!("{} hash from {}", algorithm, url),
+            Integrity::ReleasesIndex { url } => format!("SHA-256 hash from {}", url),
+            Integrity::Minisign { signature_url, .. } => format!("minisign signature from {}", signature_url),
+            Integrity::None => "none".to_string(),
+        };
+
+        write!(f, "{}", msg)
+    }
 }
 
 #[derive(Debug)]
 pub enum DownloadRequestError {
     InvalidUrl { url: String, error: String },
     InsecureProtocol { url: String },
+    ResolveFailed { url: String, error: String },
+    UnexpectedVersion { expected: String, url: String },
 }
 
 impl Display for DownloadRequestError {
@@ -51,16 +435,61 @@ impl Display for DownloadRequestError {
         let msg = match self {
             InvalidUrl { url, error } => format!("Invalid URL {}. Cause: {}", url, error),
             InsecureProtocol { url } => format!("URL {} protocol is not HTTPS", url),
+            ResolveFailed { url, error } => format!("Fail to resolve URL {}. Cause: {}", url, error),
+            UnexpectedVersion { expected, url } =>
+                format!("Unable to fetch required version {}, the download redirects to {}", expected, url),
         };
 
         write!(f, "{}", msg)
     }
 }
 
+/// What to do when the resolved URL doesn't match the expected version.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum VersionPolicy {
+    RequireExpected,
+
+    /// The hash belongs to the expected version, so it skips the hash check.
+    AllowLatest,
+}
+
+/// Retries server errors (5xx) and timeouts, doubling the backoff each time.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Retry {
+    attempts: u32,
+    backoff: Duration,
+}
+
+impl Retry {
+    pub fn new(attempts: u32, backoff: Duration) -> Self {
+        Retry { attempts: attempts.max(1), backoff }
+    }
+
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    fn delay(&self, retry: u32) -> Duration {
+        self.backoff.saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+    }
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self::new(3, Duration::from_secs(1))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DownloadRequest {
     url: Url,
     integrity: Integrity,
+    retry: Retry,
+    mirrors: Vec<Url>,
+    auth: Option<Auth>,
+    pins: Vec<PublicKeyPin>,
+    provenance: Option<Provenance>,
+    options: DownloadOptions,
 }
 
 impl DownloadRequest {
@@ -70,7 +499,16 @@ impl DownloadRequest {
             .map_err(|error| InvalidUrl { url: url_raw.to_string(), error: error.to_string() })
             .and_then(|url| {
                 if url.scheme() == "https" {
-                    Ok(DownloadRequest { url, integrity })
+                    Ok(DownloadRequest {
+                        url,
+                        integrity,
+                        retry: Retry::default(),
+                        mirrors: Vec::new(),
+                        auth: None,
+                        pins: Vec::new(),
+                        provenance: None,
+                        options: DownloadOptions::default(),
+                    })
                 } else {
                     Err(InsecureProtocol { url: url.to_string() })
                 }
@@ -85,6 +523,128 @@ impl DownloadRequest {
         self.integrity.clone()
     }
 
+    pub fn auth(&self) -> Option<Auth> {
+        self.auth.clone()
+    }
+
+    pub fn check(&self, file_path: &Path) -> Result<bool, String> {
+        self.integrity.check(file_path, &self.options)
+    }
+
+    pub fn with_retry(self, retry: Retry) -> Self {
+        DownloadRequest { retry, ..self }
+    }
+
+    pub fn with_mirrors(self, mirrors: Vec<Url>) -> Self {
+        DownloadRequest { mirrors, ..self }
+    }
+
+    pub fn with_mirror_dirs(self, mirror_dirs: &[Url]) -> Self {
+        let Some(filename) = self.filename() else {
+            return self;
+        };
+
+        let mirrors = mirror_dirs
+            .iter()
+            .filter_map(|dir| dir.join(&filename).ok())
+            .collect::<Vec<Url>>();
+
+        self.with_mirrors(mirrors)
+    }
+
+    /// Mirrors on other hosts don't get the credentials.
+    pub fn with_auth(self, auth: Auth) -> Self {
+        DownloadRequest { auth: Some(auth), ..self }
+    }
+
+    pub fn with_pins(self, pins: Vec<PublicKeyPin>) -> Self {
+        DownloadRequest { pins, ..self }
+    }
+
+    pub fn with_provenance(self, provenance: Provenance) -> Self {
+        DownloadRequest { provenance: Some(provenance), ..self }
+    }
+
+    fn pins_of(&self, host: &str) -> Vec<PublicKeyPin> {
+        let mut pins = self.options.pins.get(host).cloned().unwrap_or_default();
+
+        if self.url.host_str() == Some(host) {
+            pins.extend(self.pins.iter().cloned());
+        }
+
+        pins
+    }
+
+    pub fn curl_script(&self) -> String {
+        let pins = self.url.host_str().map(|host| self.pins_of(host)).unwrap_or_default();
+
+        tls::curl_script(&self.url, &pins)
+    }
+
+    pub fn with_options(self, options: DownloadOptions) -> Self {
+        DownloadRequest { options, ..self }
+    }
+
+    /// Follows the redirects of a generic URL, like `latest`, to the actual file.
+    /// The resolved URL must contain the expected version, like `/code_1.92.1`,
+    /// or else the policy decides.
+    pub fn resolve_final_url(
+        &self,
+        expected_pattern: &str,
+        policy: VersionPolicy,
+    ) -> Result<DownloadRequest, DownloadRequestError> {
+        let to_resolve_err = |error: String| ResolveFailed { url: self.url.to_string(), error };
+
+        let final_url = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|error| to_resolve_err(error.to_string()))?
+            .block_on(self.fetch_final_url())
+            .map_err(|error| to_resolve_err(error.to_string()))?;
+
+        self.with_final_url(final_url, expected_pattern, policy)
+    }
+
+    async fn fetch_final_url(&self) -> io::Result<Url> {
+        self.options
+            .client()?
+            .head(self.url.clone())
+            .send()
+            .await
+            .map(|res| res.url().clone())
+            .map_err(|error| io_err(error.to_string()))
+    }
+
+    fn with_final_url(
+        &self,
+        final_url: Url,
+        expected_pattern: &str,
+        policy: VersionPolicy,
+    ) -> Result<DownloadRequest, DownloadRequestError> {
+        if final_url.scheme() != "https" {
+            return Err(InsecureProtocol { url: final_url.to_string() });
+        }
+
+        let resolved = DownloadRequest { url: final_url.clone(), ..self.clone() };
+
+        if final_url.as_str().contains(expected_pattern) {
+            return Ok(resolved);
+        }
+
+        match policy {
+            VersionPolicy::RequireExpected => Err(UnexpectedVersion {
+                expected: expected_pattern.to_string(),
+                url: final_url.to_string(),
+            }),
+            VersionPolicy::AllowLatest => {
+                info!("Unable to fetch version {expected_pattern} from {final_url}.");
+                info!("Fetching the latest version without hash integrity check.");
+
+                Ok(DownloadRequest { integrity: Integrity::None, ..resolved })
+            }
+        }
+    }
+
     pub fn filename(&self) -> Option<String> {
         self.url
             .path_segments()
@@ -93,6 +653,7 @@ impl DownloadRequest {
     }
 }
 
+#[derive(Clone)]
 pub struct Downloader {
     pub req: DownloadRequest,
     pub path: PathBuf,
@@ -110,54 +671,486 @@ impl Downloader {
         Self::new(req, path)
     }
 
-    pub fn to_file(&self) -> io::Result<File> {
-        File::create_new(&self.path)
+    /// Copies the file from the artifacts when offline, or from the cache.
+    pub async fn download(&self) -> io::Result<()> {
+        let filename = self.req.filename().unwrap_or_default();
+        let integrity = self.req.integrity
+            .resolve(&filename, &self.req.options)
+            .await
+            .map_err(io_err)?;
+
+        let req = DownloadRequest { integrity, ..self.req.clone() };
+
+        Downloader { req, path: self.path.clone() }
+            .download_resolved()
+            .await
+    }
+
+    /// The remote checksum is fetched by now, so the cache keys it by the hash.
+    async fn download_resolved(&self) -> io::Result<()> {
+        if let Some(artifacts_dir) = self.req.options.artifacts_dir() {
+            self.copy_artifact(artifacts_dir)?;
+            self.check_integrity()?;
+            self.check_provenance().await?;
+
+            return self.check_digest();
+        }
+
+        if self.restore_cached() {
+            self.check_provenance().await?;
+
+            return self.check_digest();
+        }
+
+        self.fetch(&self.req.options.client()?).await?;
+        self.check_integrity()?;
+        self.check_provenance().await?;
+
+        if let Err(error) = cache::store(&self.req, &self.path) {
+            debug!("Fail to cache {}: {error}", self.path.display());
+        }
+
+        self.check_digest()
+    }
+
+    fn check_digest(&self) -> io::Result<()> {
+        let options = &self.req.options;
+        let locked_digest = options.locked_digests.get(&self.req.url);
+
+        if locked_digest.is_none() && options.digests.is_none() {
+            return Ok(());
+        }
+
+        let digest = calculate_sha256(&self.path)?;
+
+        if let Some(locked_digest) = locked_digest.filter(|locked_digest| **locked_digest != digest) {
+            let filename = self.req.filename().unwrap_or_default();
+
+            return Err(io_err(format!(
+                "Downloaded file {filename} has SHA-256 {digest}, but the lockfile has {locked_digest}"
+            )));
+        }
+
+        if let Some(Ok(mut registry)) = options.digests.as_deref().map(Mutex::lock) {
+            registry.insert(self.req.url.clone(), digest);
+        }
+
+        Ok(())
+    }
+
+    fn restore_cached(&self) -> bool {
+        if !cache::restore(&self.req, &self.path) {
+            return false;
+        }
+
+        let filename = self.req.filename().unwrap_or_default();
+
+        if self.check_integrity().is_ok() {
+            info!("Using cached {filename}.");
+            return true;
+        }
+
+        // Download it again rather than failing on a corrupted cache
+        cache::evict(&self.req);
+        let _ = fs::remove_file(&self.path);
+
+        false
     }
 
     pub fn download_blocking(&self) -> io::Result<()> {
-        let format_err_msg = |msg: String, target: String| { format!("{}: {}", msg, target) };
+        runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(self.download())
+    }
+
+    async fn fetch(&self, client: &Client) -> io::Result<()> {
+        let mut result = self.fetch_from(client, &self.req.url).await;
+
+        for mirror in &self.req.mirrors {
+            match result {
+                Ok(()) => return Ok(()),
+                Err(error) => info!("{error}. Falling back to the next mirror..."),
+            }
+
+            result = self.fetch_from(client, mirror).await;
+        }
 
-        let io_err = |msg: String| { io::Error::new(ErrorKind::Other, msg) };
+        result
+    }
 
-        let to_io_err = |msg: String| |err: reqwest::Error| io_err(format_err_msg(msg, err.to_string()));
+    async fn fetch_from(&self, client: &Client, url: &Url) -> io::Result<()> {
+        let retry = &self.req.retry;
+        let mut attempt = 1;
 
-        let filename = self.req.filename().unwrap_or_else(|| "".to_string());
+        loop {
+            match self.fetch_once(client, url).await {
+                Ok(()) => return Ok(()),
+                Err(FetchError::Transient(error)) if attempt < retry.attempts => {
+                    let delay = retry.delay(attempt);
 
-        let url = &self.req.url;
+                    // The next attempt resumes from the partial file
+                    info!("{error}. Retrying in {}s...", delay.as_secs_f32());
 
-        blocking::get(url.clone())
-            .map_err(to_io_err(format!("Failed to fetch {}", url)))
-            .and_then(|res| {
-                if res.status().is_success() {
-                    Ok(res)
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
                 }
-                else {
-                    Err(io_err(format!("Failed to download {}: {}", filename, res.status())))
+                Err(FetchError::Transient(error) | FetchError::Fatal(error)) => return Err(error),
+            }
+        }
+    }
+
+    /// Lets an interrupted download resume, like `go1.22.5.tar.gz.part`.
+    fn part_path(&self) -> PathBuf {
+        cache::part_path(&self.req).unwrap_or_else(|| {
+            let mut part_path = self.path.clone().into_os_string();
+
+            part_path.push(".part");
+            PathBuf::from(part_path)
+        })
+    }
+
+    /// Copies the file if the cache is on another file system.
+    async fn complete_part(&self, part_path: &Path) -> Result<(), FetchError> {
+        if tokio::fs::rename(part_path, &self.path).await.is_ok() {
+            return Ok(());
+        }
+
+        tokio::fs::copy(part_path, &self.path).await.map_err(FetchError::Fatal)?;
+        tokio::fs::remove_file(part_path).await.map_err(FetchError::Fatal)
+    }
+
+    async fn fetch_once(&self, client: &Client, url: &Url) -> Result<(), FetchError> {
+        let filename = self.req.filename().unwrap_or_default();
+        let part_path = self.part_path();
+
+        if let Some(parent) = part_path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(FetchError::Fatal)?;
+        }
+
+        let offset = tokio::fs::metadata(&part_path)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+
+        if offset == 0 && self.req.options.connections > 1 && self.fetch_chunked(client, url).await? {
+            return Ok(());
+        }
+
+        let mut res = self.request(client, Method::GET, url, offset_range(offset)).await?;
+
+        if offset > 0 && res.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            // The partial file doesn't belong to the current artifact anymore
+            tokio::fs::remove_file(&part_path).await.map_err(FetchError::Fatal)?;
+
+            res = self.request(client, Method::GET, url, None).await?;
+        }
+
+        let status = res.status();
+
+        if !status.is_success() {
+            let error = io_err(format!("Failed to download {}: {}", filename, status));
+
+            return Err(if status.is_server_error() {
+                FetchError::Transient(error)
+            } else {
+                FetchError::Fatal(error)
+            });
+        }
+
+        let file = if offset > 0 && status == StatusCode::PARTIAL_CONTENT {
+            info!("Resuming {filename} from {offset} bytes...");
+
+            tokio::fs::OpenOptions::new().append(true).open(&part_path).await
+        } else {
+            // The server sends the whole file if it doesn't support ranges
+            tokio::fs::File::create(&part_path).await
+        };
+
+        let mut file = file.map_err(FetchError::Fatal)?;
+
+        self.write_body(&mut res, &mut file).await?;
+        self.complete_part(&part_path).await
+    }
+
+    /// Returns `false` if the host has no ranges or the file is small.
+    async fn fetch_chunked(&self, client: &Client, url: &Url) -> Result<bool, FetchError> {
+        let filename = self.req.filename().unwrap_or_default();
+        let head = self.request(client, Method::HEAD, url, None).await?;
+        let accepts_ranges = head
+            .headers()
+            .get(ACCEPT_RANGES)
+            .is_some_and(|value| value == "bytes");
+        let size = head
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok()?.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        if !head.status().is_success() || !accepts_ranges || size < CHUNKED_MIN_SIZE {
+            return Ok(false);
+        }
+
+        let connections = self.req.options.connections;
+        let part_path = self.part_path();
+        let file = tokio::fs::File::create(&part_path).await.map_err(FetchError::Fatal)?;
+
+        file.set_len(size).await.map_err(FetchError::Fatal)?;
+
+        info!("Downloading {filename} over {connections} connections...");
+
+        let downloader = Arc::new(self.clone());
+        let mut tasks = JoinSet::new();
+
+        for (start, end) in chunk_ranges(size, connections) {
+            let (downloader, client, url) = (downloader.clone(), client.clone(), url.clone());
+
+            tasks.spawn(async move { downloader.fetch_chunk(&client, &url, start, end).await });
+        }
+
+        let mut result = Ok(());
+
+        while let Some(joined) = tasks.join_next().await {
+            let chunk_result = joined
+                .map_err(|error| FetchError::Fatal(io_err(error.to_string())))
+                .and_then(|chunk_result| chunk_result);
+
+            if let (Ok(()), Err(error)) = (&result, chunk_result) {
+                tasks.abort_all();
+                result = Err(error);
+            }
+        }
+
+        if let Err(error) = result {
+            // The chunks leave gaps in the part file, so it can't resume
+            let _ = tokio::fs::remove_file(&part_path).await;
+
+            return Err(error);
+        }
+
+        self.complete_part(&part_path).await?;
+
+        Ok(true)
+    }
+
+    async fn fetch_chunk(&self, client: &Client, url: &Url, start: u64, end: u64) -> Result<(), FetchError> {
+        let filename = self.req.filename().unwrap_or_default();
+        let mut res = self.request(client, Method::GET, url, Some(format!("bytes={start}-{end}"))).await?;
+        let status = res.status();
+
+        if status != StatusCode::PARTIAL_CONTENT {
+            let error = io_err(format!("Failed to download bytes {start}-{end} of {}: {}", filename, status));
+
+            return Err(if status.is_server_error() {
+                FetchError::Transient(error)
+            } else {
+                FetchError::Fatal(error)
+            });
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(self.part_path())
+            .await
+            .map_err(FetchError::Fatal)?;
+
+        file.seek(SeekFrom::Start(start)).await.map_err(FetchError::Fatal)?;
+
+        self.write_body(&mut res, &mut file).await
+    }
+
+    async fn write_body(&self, res: &mut Response, file: &mut tokio::fs::File) -> Result<(), FetchError> {
+        let filename = self.req.filename().unwrap_or_default();
+
+        while let Some(chunk) = res
+            .chunk()
+            .await
+            .map_err(|err| FetchError::from_reqwest(err, format!("Failed to copy file {}", filename)))? {
+            file.write_all(&chunk).await.map_err(FetchError::Fatal)?;
+
+            if let Some(rate_limit) = &self.req.options.rate_limit {
+                rate_limit.throttle(chunk.len()).await;
+            }
+        }
+
+        file.flush().await.map_err(FetchError::Fatal)
+    }
+
+    async fn request(
+        &self,
+        client: &Client,
+        method: Method,
+        url: &Url,
+        range: Option<String>,
+    ) -> Result<Response, FetchError> {
+        let mut req = client.request(method, url.clone());
+
+        if let Some(auth) = self.req.auth().filter(|_| url.host_str() == self.req.url.host_str()) {
+            let (name, value) = auth.header().map_err(|error| FetchError::Fatal(io_err(error)))?;
+
+            self.verify_before_auth(url).await?;
+
+            req = req.header(name, value);
+        }
+
+        if let Some(range) = range {
+            req = req.header(RANGE, range);
+        }
+
+        let res = req
+            .send()
+            .await
+            .map_err(|err| FetchError::from_reqwest(err, format!("Failed to fetch {}", url)))?;
+
+        // Pins apply to the host serving the file after redirects
+        if let Some(host) = res.url().host_str() {
+            let cert_der = res.extensions().get::<TlsInfo>().and_then(TlsInfo::peer_certificate);
+
+            tls::verify(host, cert_der, &self.req.pins_of(host)).map_err(|error| FetchError::Fatal(io_err(error)))?;
+        }
+
+        Ok(res)
+    }
+
+    /// The credentials go before the response, so it checks the pins first.
+    async fn verify_before_auth(&self, url: &Url) -> Result<(), FetchError> {
+        let Some(host) = url.host_str() else { return Ok(()) };
+        let pins = self.req.pins_of(host);
+
+        if pins.is_empty() {
+            return Ok(());
+        }
+
+        let res = self.req.options
+            .unredirected_client()
+            .map_err(FetchError::Fatal)?
+            .head(url.clone())
+            .send()
+            .await
+            .map_err(|err| FetchError::from_reqwest(err, format!("Failed to check the pins of {}", host)))?;
+
+        let cert_der = res.extensions().get::<TlsInfo>().and_then(TlsInfo::peer_certificate);
+
+        tls::verify(host, cert_der, &pins).map_err(|error| FetchError::Fatal(io_err(error)))
+    }
+
+    /// Artifacts are named after the last segment of their URL path.
+    fn copy_artifact(&self, artifacts_dir: &Path) -> io::Result<()> {
+        let filename = self.req
+            .filename()
+            .ok_or_else(|| io_err(format!("URL {} has no filename to find its artifact", self.req.url)))?;
+
+        let artifact = artifacts_dir.join(&filename);
+
+        if !artifact.is_file() {
+            return Err(io_err(format!("Artifact {} not found for {}", artifact.display(), self.req.url)));
+        }
+
+        fs::copy(&artifact, &self.path).map(|_| ())
+    }
+
+    fn check_integrity(&self) -> io::Result<()> {
+        let filename = self.req.filename().unwrap_or_default();
+
+        self.req
+            .check(self.path.as_path())
+            .map_err(io_err)
+            .and_then(|check| {
+                if check {
+                    Ok(())
+                } else {
+                    Err(io_err(format!("Downloaded file {} failed integrity check {:?}", filename, self.req.integrity)))
                 }
             })
-            .and_then(|mut res| {
-                let mut file = self.to_file()?;
+    }
 
-                res
-                    .copy_to(&mut file)
-                    .map_err(|err| io_err(format!("Failed to copy file {}: {}", filename, err)))
-            })
-            .and_then(|_| {
-                self.req
-                    .integrity
-                    .check(self.path.as_path())
-                    .map_err(io_err)
-                    .and_then(|check| {
-                        if check {
-                            Ok(())
-                        } else {
-                            Err(io_err(format!("Downloaded file {} failed integrity check {:?}", filename, self.req.integrity)))
-                        }
-                    })
-            })
+    async fn check_provenance(&self) -> io::Result<()> {
+        let Some(provenance) = &self.req.provenance else { return Ok(()) };
+        let filename = self.req.filename().unwrap_or_default();
+
+        if provenance.needs_network() && self.req.options.artifacts_dir().is_some() {
+            return Err(io_err(format!("Provenance of {filename} can't be verified offline")));
+        }
+
+        let provenance_raw = match provenance.provenance_url().map_err(io_err)? {
+            Some(url) => Some(
+                read_sidecar(&url, &self.req.options)
+                    .await
+                    .map_err(|error| io_err(format!("Fail to fetch provenance {}: {}", url, error)))?
+            ),
+            None => None,
+        };
+
+        info!("Verifying provenance of {filename}...");
+
+        provenance
+            .verify(&self.path, provenance_raw.as_deref())
+            .map_err(io_err)
     }
 }
 
+enum FetchError {
+    Transient(io::Error),
+    Fatal(io::Error),
+}
+
+impl FetchError {
+    fn from_reqwest(error: reqwest::Error, msg: String) -> Self {
+        let transient = error.is_timeout();
+        let error = if transient {
+            io_err(format!("{}: timed out, the host stopped responding", msg))
+        } else {
+            io_err(format!("{}: {}", msg, error))
+        };
+
+        if transient {
+            FetchError::Transient(error)
+        } else {
+            FetchError::Fatal(error)
+        }
+    }
+}
+
+/// Returns the results in the order of the downloaders.
+pub fn download_all(downloaders: Vec<Downloader>, concurrency: usize) -> Vec<io::Result<()>> {
+    let count = downloaders.len();
+    let rt = match runtime::Builder::new_multi_thread().enable_all().build() {
+        Ok(rt) => rt,
+        Err(error) => return (0..count).map(|_| Err(io_err(error.to_string()))).collect(),
+    };
+
+    rt.block_on(async {
+        let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+
+        for (index, downloader) in downloaders.into_iter().enumerate() {
+            let permits = permits.clone();
+
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+
+                (index, downloader.download().await)
+            });
+        }
+
+        let mut results = Vec::with_capacity(count);
+
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => results.push(result),
+                Err(error) => return (0..count).map(|_| Err(io_err(error.to_string()))).collect(),
+            }
+        }
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    })
+}
+
+fn io_err(msg: String) -> io::Error {
+    io::Error::new(ErrorKind::Other, msg)
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -194,6 +1187,27 @@ mod tests {
         assert!(error_matches);
     }
 
+    #[test]
+    fn rejects_invalid_proxy() {
+        assert!(parse_proxy("not a proxy").is_err());
+        assert!(parse_proxy("http://proxy.example.com:3128").is_ok());
+    }
+
+    #[test]
+    fn takes_pins_of_request_and_options() {
+        let pin = "sha256//YhKJKSzoTt2b5FP18fvpHo7fJYqQCjAa3HWY3tvRMwE=".parse::<PublicKeyPin>().unwrap();
+        let options = DownloadOptions::default().with_pins("cdn.example.com", vec![pin.clone()]);
+        let req = DownloadRequest::new("https://example.com/install.sh", Integrity::None)
+            .unwrap()
+            .with_pins(vec![pin.clone()])
+            .with_options(options);
+
+        assert_eq!(vec![pin.clone()], req.pins_of("example.com"));
+        assert_eq!(vec![pin], req.pins_of("cdn.example.com"));
+        assert!(req.pins_of("other.example.com").is_empty());
+        assert!(req.curl_script().contains("--pinnedpubkey"));
+    }
+
     #[test]
     fn reads_url_path_filename() -> Result<(), DownloadRequestError> {
         let url = "https://example.com/route/file.txt";
@@ -204,6 +1218,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn retries_with_exponential_backoff() -> Result<(), DownloadRequestError> {
+        let req = DownloadRequest::new("https://example.com/file.txt", Integrity::None)?;
+
+        assert_eq!(Retry::default(), req.retry);
+
+        let retry = Retry::new(4, Duration::from_millis(500));
+        let req = req.with_retry(retry.clone());
+
+        assert_eq!(retry, req.retry);
+        assert_eq!(Duration::from_millis(500), retry.delay(1));
+        assert_eq!(Duration::from_secs(1), retry.delay(2));
+        assert_eq!(Duration::from_secs(2), retry.delay(3));
+        assert_eq!(1, Retry::new(0, Duration::ZERO).attempts);
+
+        Ok(())
+    }
+
+    #[test]
+    fn checks_resolved_url_version() -> Result<(), DownloadRequestError> {
+        let checksum = "d0f161ec79145772445d5a14b15030592498aaafa59237a602d66f43653e5309".to_string();
+        let integrity = Integrity::Hash(Hash::new(HashAlgorithm::Sha256, checksum));
+        let req = DownloadRequest::new("https://example.com/download?build=stable", integrity.clone())?;
+        let expected_url = Url::parse("https://cdn.example.com/1.92.1/code_1.92.1_amd64.deb").unwrap();
+        let latest_url = Url::parse("https://cdn.example.com/1.93.0/code_1.93.0_amd64.deb").unwrap();
+
+        let resolved = req.with_final_url(expected_url.clone(), "/code_1.92.1", VersionPolicy::RequireExpected)?;
+
+        assert_eq!(expected_url, resolved.url());
+        assert_eq!(integrity, resolved.integrity());
+        assert_eq!(Some("code_1.92.1_amd64.deb".to_string()), resolved.filename());
+
+        let latest = req.with_final_url(latest_url.clone(), "/code_1.92.1", VersionPolicy::AllowLatest)?;
+
+        assert_eq!(latest_url, latest.url());
+        assert_eq!(Integrity::None, latest.integrity());
+
+        assert!(matches!(
+            req.with_final_url(latest_url, "/code_1.92.1", VersionPolicy::RequireExpected),
+            Err(UnexpectedVersion { .. })
+        ));
+
+        let insecure_url = Url::parse("http://cdn.example.com/1.92.1/code_1.92.1_amd64.deb").unwrap();
+
+        assert!(matches!(
+            req.with_final_url(insecure_url, "/code_1.92.1", VersionPolicy::AllowLatest),
+            Err(InsecureProtocol { .. })
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn creates_downloader_to_tmp_file() -> io::Result<()> {
         let url = "https://example.com/route/file.txt";
@@ -220,6 +1286,139 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn resumes_from_part_file() -> io::Result<()> {
+        use std::io::{Read, Write};
+
+        // The host sends the rest of the file from the range it's asked
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let port = listener.local_addr()?.port();
+        let server = std::thread::spawn(move || -> io::Result<String> {
+            let (mut stream, _) = listener.accept()?;
+            let mut request = [0; 1024];
+            let len = stream.read(&mut request)?;
+
+            stream.write_all(b"HTTP/1.1 206 Partial Content\r\nContent-Length: 6\r\nConnection: close\r\n\r\n world")?;
+
+            Ok(String::from_utf8_lossy(&request[..len]).to_lowercase())
+        });
+
+        let req = DownloadRequest::new("https://example.com/route/file.txt", Integrity::None)
+            .expect("Fail to create download request");
+
+        let tmp = TmpWorkingDir::new()?;
+        let downloader = Downloader::from(req, &tmp);
+        let url = Url::parse(&format!("http://127.0.0.1:{port}/route/file.txt")).unwrap();
+
+        fs::write(downloader.part_path(), "hello")?;
+
+        runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(async {
+                let client = downloader.req.options.client()?;
+
+                match downloader.fetch_once(&client, &url).await {
+                    Ok(()) => Ok(()),
+                    Err(FetchError::Transient(error) | FetchError::Fatal(error)) => Err(error),
+                }
+            })?;
+
+        let request = server.join().expect("Fail to join the server")?;
+
+        assert!(request.contains("range: bytes=5-"));
+        assert_eq!("hello world", fs::read_to_string(&downloader.path)?);
+        assert!(!downloader.part_path().exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn times_out_per_request() -> io::Result<()> {
+        // The host accepts the connection but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let port = listener.local_addr()?.port();
+
+        std::thread::spawn(move || {
+            let _connections = listener.incoming().collect::<Vec<_>>();
+        });
+
+        let url = format!("https://127.0.0.1:{port}/route/file.txt");
+        let req = DownloadRequest::new(&url, Integrity::None)
+            .expect("Fail to create download request")
+            .with_retry(Retry::new(1, Duration::ZERO));
+
+        assert_eq!(Timeouts::default(), req.options.timeouts);
+
+        let timeouts = Timeouts::new(Duration::from_secs(1), Duration::from_secs(1));
+        let req = req.with_options(DownloadOptions::default().with_timeouts(timeouts));
+
+        let tmp = TmpWorkingDir::new()?;
+        let downloader = Downloader::from(req, &tmp);
+
+        let start = Instant::now();
+        let error = downloader
+            .download_blocking()
+            .expect_err("Stalled host downloaded");
+
+        assert!(error.to_string().contains("timed out"), "{error}");
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_rate() {
+        assert_eq!(Ok(512), parse_rate("512"));
+        assert_eq!(Ok(500 * 1024), parse_rate("500K"));
+        assert_eq!(Ok(5 * 1024 * 1024), parse_rate("5m"));
+        assert_eq!(Ok(1024 * 1024 * 1024), parse_rate("1G"));
+        assert!(parse_rate("0").is_err());
+        assert!(parse_rate("5T").is_err());
+        assert!(parse_rate("fast").is_err());
+        assert!(parse_rate("").is_err());
+    }
+
+    #[test]
+    fn splits_chunk_ranges() {
+        assert_eq!(vec![(0, 24), (25, 49), (50, 74), (75, 99)], chunk_ranges(100, 4));
+        assert_eq!(vec![(0, 33), (34, 67), (68, 99)], chunk_ranges(100, 3));
+        assert_eq!(vec![(0, 99)], chunk_ranges(100, 1));
+        assert_eq!(vec![(0, 0), (1, 1)], chunk_ranges(2, 4));
+    }
+
+    #[test]
+    fn throttles_to_rate() -> io::Result<()> {
+        let rate_limit = RateLimit::new(1000);
+        let start = Instant::now();
+
+        runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(async {
+                rate_limit.throttle(100).await;
+                rate_limit.throttle(100).await;
+            });
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        Ok(())
+    }
+
+    #[test]
+    fn downloads_to_part_file() -> io::Result<()> {
+        let url = "https://example.com/route/file.tar.gz";
+        let req = DownloadRequest::new(url, Integrity::None)
+            .expect("Fail to create download request");
+
+        let tmp = TmpWorkingDir::new()?;
+        let downloader = Downloader::from(req, &tmp);
+
+        assert_eq!(tmp.join("file.tar.gz.part".as_ref()), downloader.part_path());
+
+        Ok(())
+    }
+
     #[test]
     fn downloads_file() -> io::Result<()> {
         let base_url = "https://raw.githubusercontent.com/mathswe-ops/mathswe-ops---mvp/main";
@@ -250,6 +1449,101 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn copies_artifact_offline() -> io::Result<()> {
+        let url = "https://example.com/route/test_file.txt";
+        let checksum = "0ecfebe350c45dbded8cfb32d3af0b910bde66fc2aafbafabdaaeef6cae48a59".to_string();
+        let integrity = Integrity::Hash(Hash::new(HashAlgorithm::Sha256, checksum));
+        let req = DownloadRequest::new(url, integrity)
+            .expect("Fail to build a correct download request");
+
+        let artifacts_dir = Path::new("resources").join("test").join("download");
+        let temp_dir = TmpWorkingDir::new()?;
+        let downloader = Downloader::from(req, &temp_dir);
+
+        downloader.copy_artifact(&artifacts_dir)?;
+        downloader.check_integrity()?;
+
+        let missing_req = DownloadRequest::new("https://example.com/missing.txt", Integrity::None)
+            .expect("Fail to build a correct download request");
+
+        assert!(Downloader::from(missing_req, &temp_dir).copy_artifact(&artifacts_dir).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn checks_remote_hash_offline() -> Result<(), String> {
+        let artifacts_dir = TmpWorkingDir::new().map_err(|error| error.to_string())?;
+        let checksum = "0ecfebe350c45dbded8cfb32d3af0b910bde66fc2aafbafabdaaeef6cae48a59";
+
+        fs::write(artifacts_dir.join("test_file.sig".as_ref()), format!("{checksum}\n"))
+            .map_err(|error| error.to_string())?;
+
+        let options = DownloadOptions::default().offline(artifacts_dir.path().to_path_buf());
+        let integrity = Integrity::RemoteHash {
+            algorithm: HashAlgorithm::Sha256,
+            url: Url::parse("https://example.com/test_file.sig").unwrap(),
+        };
+        let test_file_path = Path::new("resources").join("test").join("download").join("test_file.txt");
+
+        assert!(integrity.check(&test_file_path, &options)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn downloads_all_in_order() -> io::Result<()> {
+        let temp_dir = TmpWorkingDir::new()?;
+        let downloaders = ["a.txt", "b.txt", "c.txt"]
+            .iter()
+            .map(|filename| {
+                let url = format!("https://127.0.0.1:1/{filename}");
+                let req = DownloadRequest::new(&url, Integrity::None)
+                    .expect("Fail to build a correct download request");
+
+                Downloader::from(req, &temp_dir)
+            })
+            .collect::<Vec<Downloader>>();
+
+        let errors = download_all(downloaders, 2)
+            .into_iter()
+            .map(|result| result.expect_err("Unreachable host downloaded").to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(3, errors.len());
+        assert!(errors[0].contains("/a.txt"));
+        assert!(errors[1].contains("/b.txt"));
+        assert!(errors[2].contains("/c.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn falls_back_to_mirrors() -> io::Result<()> {
+        let url = "https://127.0.0.1:1/route/file.txt";
+        let mirror_dir = Url::parse("https://127.0.0.1:2/mirror/").unwrap();
+        let req = DownloadRequest::new(url, Integrity::None)
+            .expect("Fail to build a correct download request")
+            .with_mirror_dirs(&[mirror_dir]);
+
+        let temp_dir = TmpWorkingDir::new()?;
+        let downloader = Downloader::from(req, &temp_dir);
+
+        assert_eq!(
+            vec![Url::parse("https://127.0.0.1:2/mirror/file.txt").unwrap()],
+            downloader.req.mirrors,
+        );
+
+        let error = downloader
+            .download_blocking()
+            .expect_err("Unreachable mirror downloaded");
+
+        assert!(error.to_string().contains("127.0.0.1:2/mirror/file.txt"));
+
+        Ok(())
+    }
+
     #[test]
     fn fails_with_bad_url() -> io::Result<()> {
         let base_url = "https://raw.githubusercontent.com/mathswe-ops/mathswe-ops---mvp/main";
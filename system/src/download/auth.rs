@@ -0,0 +1,60 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::env;
+
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+
+/// Only names the variable with the secret, so it never goes to the image info.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Auth {
+    /// Like `{ "bearer": { "token_env": "GITHUB_TOKEN" } }`.
+    Bearer { token_env: String },
+
+    /// Like `X-JFrog-Art-Api`.
+    Header { name: String, value_env: String },
+}
+
+impl Auth {
+    pub fn header(&self) -> Result<(String, String), String> {
+        let read_var = |var: &str| env::var(var)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("Set {var} to authenticate the download"));
+
+        match self {
+            Auth::Bearer { token_env } => read_var(token_env)
+                .map(|token| (AUTHORIZATION.to_string(), format!("Bearer {token}"))),
+
+            Auth::Header { name, value_env } => read_var(value_env)
+                .map(|value| (name.clone(), value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::download::auth::Auth;
+
+    #[test]
+    fn reads_secret_from_env() {
+        let bearer: Auth = serde_json::from_str(r#"{ "bearer": { "token_env": "SYSTEM_TEST_AUTH_TOKEN" } }"#).unwrap();
+        let header = Auth::Header {
+            name: "X-JFrog-Art-Api".to_string(),
+            value_env: "SYSTEM_TEST_AUTH_KEY".to_string(),
+        };
+
+        assert!(bearer.header().is_err());
+
+        env::set_var("SYSTEM_TEST_AUTH_TOKEN", "token");
+        env::set_var("SYSTEM_TEST_AUTH_KEY", "key");
+
+        assert_eq!(Ok(("authorization".to_string(), "Bearer token".to_string())), bearer.header());
+        assert_eq!(Ok(("X-JFrog-Art-Api".to_string(), "key".to_string())), header.header());
+    }
+}
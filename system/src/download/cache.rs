@@ -0,0 +1,162 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::download::hashing::sha256_hex;
+use crate::download::{DownloadRequest, Integrity};
+use crate::tmp;
+
+fn downloads_dir() -> Option<PathBuf> {
+    tmp::cache_dir().map(|dir| dir.join("downloads"))
+}
+
+/// Only hash-checked requests are cached since their content is pinned.
+fn cache_key(req: &DownloadRequest) -> Option<String> {
+    let pin = match &req.integrity {
+        Integrity::Hash(hash) => hash.value(),
+        Integrity::RemoteSha256 { url }
+        | Integrity::RemoteHash { url, .. }
+        | Integrity::ReleasesIndex { url } => url.to_string(),
+        Integrity::Gpg(_) | Integrity::Minisign { .. } | Integrity::None => return None,
+    };
+
+    Some(sha256_hex(format!("{} {}", req.url, pin).as_bytes()))
+}
+
+fn cached_path_in(dir: &Path, req: &DownloadRequest) -> Option<PathBuf> {
+    let key = cache_key(req)?;
+    let filename = req.filename()?;
+
+    Some(dir.join(key).join(filename))
+}
+
+fn cached_path(req: &DownloadRequest) -> Option<PathBuf> {
+    downloads_dir().and_then(|dir| cached_path_in(&dir, req))
+}
+
+fn part_path_of(cached: &Path) -> PathBuf {
+    let mut part_path = cached.as_os_str().to_owned();
+
+    part_path.push(".part");
+    PathBuf::from(part_path)
+}
+
+/// Next to the cached file, so the next operation resumes the download.
+pub fn part_path(req: &DownloadRequest) -> Option<PathBuf> {
+    cached_path(req).map(|cached| part_path_of(&cached))
+}
+
+fn restore_from(cached: Option<PathBuf>, path: &Path) -> bool {
+    cached
+        .filter(|cached| cached.is_file())
+        .is_some_and(|cached| fs::copy(cached, path).is_ok())
+}
+
+fn store_to(cached: Option<PathBuf>, path: &Path) -> io::Result<()> {
+    let Some(cached) = cached else { return Ok(()) };
+    let Some(parent) = cached.parent() else { return Ok(()) };
+    let part = part_path_of(&cached);
+
+    fs::create_dir_all(parent)?;
+
+    // Concurrent operations never read a half-written file
+    fs::copy(path, &part)?;
+    fs::rename(&part, &cached)
+}
+
+pub fn keeps(req: &DownloadRequest) -> bool {
+    downloads_dir().is_some_and(|dir| cached_path_in(&dir, req).is_some())
+}
+
+pub fn restore(req: &DownloadRequest, path: &Path) -> bool {
+    restore_from(cached_path(req), path)
+}
+
+pub fn store(req: &DownloadRequest, path: &Path) -> io::Result<()> {
+    store_to(cached_path(req), path)
+}
+
+pub fn evict(req: &DownloadRequest) {
+    if let Some(cached) = cached_path(req) {
+        let _ = fs::remove_file(cached);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io;
+
+    use reqwest::Url;
+
+    use crate::download::cache::{cached_path_in, downloads_dir, keeps, part_path, part_path_of, restore_from, store_to};
+    use crate::download::hashing::Hash;
+    use crate::download::hashing::HashAlgorithm::Sha256;
+    use crate::download::{DownloadRequest, Integrity};
+    use crate::tmp::TmpWorkingDir;
+
+    #[test]
+    fn keys_by_url_and_hash() -> io::Result<()> {
+        let tmp = TmpWorkingDir::new()?;
+        let url = "https://example.com/route/file.tar.gz";
+        let req = |hash: &str| DownloadRequest::new(url, Integrity::Hash(Hash::new(Sha256, hash.to_string()))).unwrap();
+
+        let cached = cached_path_in(tmp.path(), &req("abc")).unwrap();
+        let other_hash = cached_path_in(tmp.path(), &req("def")).unwrap();
+
+        assert!(cached.starts_with(tmp.path()));
+        assert!(cached.ends_with("file.tar.gz"));
+        assert_ne!(cached, other_hash);
+        assert_eq!(cached, cached_path_in(tmp.path(), &req("abc")).unwrap());
+        assert!(part_path_of(&cached).ends_with("file.tar.gz.part"));
+
+        let unchecked = DownloadRequest::new(url, Integrity::None).unwrap();
+
+        assert_eq!(None, cached_path_in(tmp.path(), &unchecked));
+        assert!(!keeps(&unchecked));
+        assert_eq!(None, part_path(&unchecked));
+
+        Ok(())
+    }
+
+    #[test]
+    fn keys_remote_checksums() -> io::Result<()> {
+        let tmp = TmpWorkingDir::new()?;
+        let url = "https://example.com/route/file.tar.gz";
+        let checksum_url = |name: &str| Url::parse(&format!("https://example.com/route/{name}")).unwrap();
+        let req = |name: &str| DownloadRequest::new(url, Integrity::RemoteSha256 { url: checksum_url(name) }).unwrap();
+
+        let cached = cached_path_in(tmp.path(), &req("file.tar.gz.sha256")).unwrap();
+        let other_checksum = cached_path_in(tmp.path(), &req("SHASUMS256.txt")).unwrap();
+
+        assert!(cached.ends_with("file.tar.gz"));
+        assert_ne!(cached, other_checksum);
+        assert_eq!(downloads_dir().is_some(), keeps(&req("file.tar.gz.sha256")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn stores_and_restores() -> io::Result<()> {
+        let tmp = TmpWorkingDir::new()?;
+        let url = "https://example.com/route/file.txt";
+        let req = DownloadRequest::new(url, Integrity::Hash(Hash::new(Sha256, "abc".to_string()))).unwrap();
+        let cached = cached_path_in(&tmp.join("cache".as_ref()), &req);
+        let downloaded = tmp.join("file.txt".as_ref());
+        let restored = tmp.join("restored.txt".as_ref());
+
+        assert!(!restore_from(cached.clone(), &restored));
+
+        fs::write(&downloaded, "content")?;
+        store_to(cached.clone(), &downloaded)?;
+
+        assert!(restore_from(cached, &restored));
+        assert_eq!("content", fs::read_to_string(restored)?);
+
+        Ok(())
+    }
+}
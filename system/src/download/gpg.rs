@@ -2,11 +2,40 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
+use std::fmt::{Display, Formatter};
+use std::fs;
 use std::path::Path;
+use std::process::Output;
 
 use reqwest::Url;
 
+use crate::cmd;
 use crate::cmd::exec_cmd;
+use crate::tmp::TmpWorkingDir;
+
+/// Vendors publish their renewed keys there too.
+const KEYSERVER: &str = "hkps://keyserver.ubuntu.com";
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum KeyStatus {
+    Valid,
+    Expired,
+    Revoked,
+    Missing,
+}
+
+impl Display for KeyStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            KeyStatus::Valid => "valid",
+            KeyStatus::Expired => "expired",
+            KeyStatus::Revoked => "revoked",
+            KeyStatus::Missing => "missing",
+        };
+
+        write!(f, "{}", msg)
+    }
+}
 
 #[derive(PartialEq, Clone, Debug)]
 pub struct GpgKey {
@@ -20,6 +49,10 @@ impl GpgKey {
     }
 
     pub fn verify(&self, file_path: &Path) -> Result<bool, String> {
+        if file_path.extension().is_some_and(|ext| ext == "rpm") {
+            return self.verify_rpm(file_path);
+        }
+
         let cmd_output = exec_cmd("gpg", &["--verify", file_path.to_str().unwrap()])
             .map_err(|error| error.to_string())?;
 
@@ -41,6 +74,10 @@ impl GpgKey {
 
         let out_has_fingerprint = self.gpg_output_contains_fingerprint(&output);
 
+        if out_has_fingerprint && output.contains("This key has been revoked") {
+            return Err(format!("GPG key {} that signed {} is revoked", self.fingerprint, file_path.display()));
+        }
+
         let correct
             = out_contains_required_strings
             && out_has_no_signature_error
@@ -49,32 +86,169 @@ impl GpgKey {
         Ok(correct)
     }
 
-    pub fn install(&self) -> Result<(), String> {
-        let curl_cmd = format!("curl --proto '=https' --tlsv1.2 -sSf {} | gpg --import -", self.url);
-        let cmd_output = exec_cmd("bash", &["-c", &curl_cmd])
-            .map_err(|error| error.to_string())?;
+    pub fn install(&self, artifacts_dir: Option<&Path>) -> Result<(), String> {
+        let cmd_output = match artifacts_dir {
+            Some(artifacts_dir) => self.import_artifact(artifacts_dir),
+            None => {
+                let curl_cmd = format!("curl --proto '=https' --tlsv1.2 -sSf {} | gpg --import -", self.url);
+
+                exec_cmd("bash", &["-c", &curl_cmd])
+            }
+        }.map_err(|error| error.to_string())?;
 
         let stdout = String::from_utf8_lossy(&cmd_output.stdout);
 
-        println!("{}", stdout);
+        verbose!("{}", stdout);
+
+        match self.status()? {
+            KeyStatus::Valid => (),
+            KeyStatus::Missing =>
+                return Err(format!("Key {} does not have fingerprint {}", self.url, self.fingerprint)),
+            status => {
+                self.refresh(status, artifacts_dir)?;
 
-        self.check_key_fingerprint()?;
+                if self.status()? != KeyStatus::Valid {
+                    return Err(self.unusable_error(status));
+                }
+            }
+        }
 
-        println!("GPG key installed");
+        info!("GPG key installed");
 
         Ok(())
     }
 
-    fn check_key_fingerprint(&self) -> Result<(), String> {
-        let cmd_output = exec_cmd("gpg", &["--fingerprint"])
+    fn import_artifact(&self, artifacts_dir: &Path) -> cmd::Result<Output> {
+        let filename = self.url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or_default();
+
+        let key_path = artifacts_dir.join(filename);
+
+        exec_cmd("gpg", &["--import", key_path.to_str().unwrap()])
+    }
+
+    pub fn url(&self) -> Url {
+        self.url.clone()
+    }
+
+    /// It replaces an expired or revoked key with a valid one from the keyserver.
+    pub fn check_key_file(&self, key_path: &Path) -> Result<(), String> {
+        match self.key_file_status(key_path)? {
+            KeyStatus::Valid => Ok(()),
+            KeyStatus::Missing =>
+                Err(format!("Repository key {} does not match the expected fingerprint", self.url)),
+            status => {
+                self.refresh_key_file(key_path, status)?;
+
+                match self.key_file_status(key_path)? {
+                    KeyStatus::Valid => Ok(()),
+                    _ => Err(self.unusable_error(status)),
+                }
+            }
+        }
+    }
+
+    fn status(&self) -> Result<KeyStatus, String> {
+        let cmd_output = exec_cmd("gpg", &["--with-colons", "--fingerprint"])
             .map_err(|error| error.to_string())?;
 
         let stdout = String::from_utf8_lossy(&cmd_output.stdout);
 
-        match self.gpg_output_contains_fingerprint(&stdout) {
-            true => Ok(()),
-            false => Err("Key fingerprint does not exist in GPG".to_string())
+        Ok(key_status(&stdout, &self.fingerprint))
+    }
+
+    fn key_file_status(&self, key_path: &Path) -> Result<KeyStatus, String> {
+        let cmd_output = exec_cmd(
+            "gpg",
+            &["--show-keys", "--with-colons", "--fingerprint", key_path.to_str().unwrap()],
+        ).map_err(|error| error.to_string())?;
+
+        let stdout = String::from_utf8_lossy(&cmd_output.stdout);
+
+        Ok(key_status(&stdout, &self.fingerprint))
+    }
+
+    fn refresh(&self, status: KeyStatus, artifacts_dir: Option<&Path>) -> Result<(), String> {
+        if artifacts_dir.is_some() {
+            return Err(format!("{}, and it can't be refreshed offline", self.unusable_error(status)));
         }
+
+        info!("GPG key {} is {status}, refreshing it from {KEYSERVER}...", self.fingerprint);
+
+        let output = exec_cmd("gpg", &["--keyserver", KEYSERVER, "--recv-keys", &self.normalized_fingerprint()])
+            .map_err(|error| error.to_string())?;
+
+        verbose!("{}", String::from_utf8_lossy(&output.stderr));
+
+        Ok(())
+    }
+
+    /// Uses a throwaway keyring, so the user keyring stays untouched.
+    fn refresh_key_file(&self, key_path: &Path, status: KeyStatus) -> Result<(), String> {
+        info!("Repository key {} is {status}, refreshing it from {KEYSERVER}...", self.fingerprint);
+
+        let tmp = TmpWorkingDir::new()
+            .map_err(|error| error.to_string())?;
+
+        let home = tmp.join(Path::new("gnupg"));
+        let home = home.to_str().unwrap();
+        let fingerprint = self.normalized_fingerprint();
+
+        fs::create_dir(home).map_err(|error| error.to_string())?;
+
+        exec_cmd("gpg", &["--homedir", home, "--keyserver", KEYSERVER, "--recv-keys", &fingerprint])
+            .map_err(|error| error.to_string())?;
+
+        exec_cmd(
+            "gpg",
+            &["--homedir", home, "--yes", "--output", key_path.to_str().unwrap(), "--export", &fingerprint],
+        ).map_err(|error| error.to_string())?;
+
+        Ok(())
+    }
+
+    /// `rpmkeys` checks against a throwaway database holding only this key.
+    fn verify_rpm(&self, file_path: &Path) -> Result<bool, String> {
+        let tmp = TmpWorkingDir::new()
+            .map_err(|error| error.to_string())?;
+
+        let key_path = tmp.join(Path::new("key.asc"));
+        let key_path = key_path.to_str().unwrap();
+        let db_path = tmp.join(Path::new("rpmdb"));
+        let db_path = db_path.to_str().unwrap();
+
+        exec_cmd("gpg", &["--yes", "--armor", "--output", key_path, "--export", &self.normalized_fingerprint()])
+            .map_err(|error| error.to_string())?;
+
+        exec_cmd("rpmdb", &["--dbpath", db_path, "--initdb"])
+            .map_err(|error| error.to_string())?;
+
+        exec_cmd("rpmkeys", &["--dbpath", db_path, "--import", key_path])
+            .map_err(|error| error.to_string())?;
+
+        let checksig_cmd = format!(
+            "rpmkeys --dbpath '{db_path}' --checksig --verbose '{}' 2>&1 || true",
+            file_path.display(),
+        );
+
+        let output = exec_cmd("bash", &["-c", &checksig_cmd])
+            .map_err(|error| error.to_string())?;
+
+        Ok(rpm_signature_ok(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn unusable_error(&self, status: KeyStatus) -> String {
+        format!(
+            "GPG key {} from {} is {status}, also on {KEYSERVER}. The vendor may have rotated its key, so check the key fingerprint of the image",
+            self.fingerprint,
+            self.url,
+        )
+    }
+
+    fn normalized_fingerprint(&self) -> String {
+        normalize_fingerprint(&self.fingerprint)
     }
 
     fn gpg_output_contains_fingerprint(&self, output: &str) -> bool {
@@ -85,21 +259,99 @@ impl GpgKey {
             .filter(no_whitespace)
             .collect();
 
-        let normalized_fingerprint: String = self
-            .fingerprint
-            .chars()
-            .filter(no_whitespace)
-            .collect();
+        normalized_output.contains(&self.normalized_fingerprint())
+    }
+}
 
-        normalized_output.contains(&normalized_fingerprint)
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Failing signature lines end with `NOKEY` or `BAD` instead of `OK`.
+fn rpm_signature_ok(report: &str) -> bool {
+    let signatures = report
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.contains("Signature, key ID"))
+        .collect::<Vec<&str>>();
+
+    !signatures.is_empty() && signatures.iter().all(|line| line.ends_with(": OK"))
+}
+
+/// Each `pub` or `sub` record has the validity, and the next `fpr` its fingerprint.
+fn key_status(listing: &str, fingerprint: &str) -> KeyStatus {
+    let fingerprint = normalize_fingerprint(fingerprint);
+    let mut validity = None;
+
+    for line in listing.lines() {
+        let fields = line.split(':').collect::<Vec<&str>>();
+
+        match fields[0] {
+            "pub" | "sub" => validity = fields.get(1).copied(),
+            "fpr" => {
+                let Some(key_validity) = validity.take() else { continue };
+
+                if fields.get(9).map(|fpr| fpr.to_uppercase()) == Some(fingerprint.clone()) {
+                    return match key_validity {
+                        "e" => KeyStatus::Expired,
+                        "r" => KeyStatus::Revoked,
+                        _ => KeyStatus::Valid,
+                    };
+                }
+            }
+            _ => (),
+        }
     }
+
+    KeyStatus::Missing
 }
 
 #[cfg(test)]
 mod tests {
     use reqwest::Url;
 
-    use crate::download::gpg::GpgKey;
+    use crate::download::gpg::{key_status, rpm_signature_ok, GpgKey, KeyStatus};
+
+    const ZOOM_FINGERPRINT: &str = "59C8 6188 E22A BB19 BD55 4047 7B04 A1B8 DD79 B481";
+
+    fn listing(validity: &str) -> String {
+        format!(
+            "tru::1:1718000000:0:3:1:5\n\
+            pub:{validity}:4096:1:7B04A1B8DD79B481:1544136054:1725000000::-:::scESC::::::23::0:\n\
+            fpr:::::::::59C86188E22ABB19BD5540477B04A1B8DD79B481:\n\
+            uid:{validity}::::1544136054::C41A4F5D2F1B7B5B::Zoom Video Communications, Inc. Linux Package Signing Key::::::::::0:\n\
+            sub:{validity}:4096:1:1C8C4C39A5D2D2E1:1544136054::::::e::::::23:\n\
+            fpr:::::::::0A1B2C3D4E5F60718293A4B5C6D7E8F901234567:\n"
+        )
+    }
+
+    #[test]
+    fn reads_key_status() {
+        assert_eq!(KeyStatus::Valid, key_status(&listing("-"), ZOOM_FINGERPRINT));
+        assert_eq!(KeyStatus::Valid, key_status(&listing("u"), &ZOOM_FINGERPRINT.to_lowercase()));
+        assert_eq!(KeyStatus::Expired, key_status(&listing("e"), ZOOM_FINGERPRINT));
+        assert_eq!(KeyStatus::Revoked, key_status(&listing("r"), ZOOM_FINGERPRINT));
+        assert_eq!(KeyStatus::Missing, key_status(&listing("-"), "09C8 6188 E22A BB19 BD55 4047 7B04 A1B8 DD79 B481"));
+        assert_eq!(KeyStatus::Missing, key_status("", ZOOM_FINGERPRINT));
+    }
+
+    #[test]
+    fn reads_rpm_signature_report() {
+        let report = |status: &str| format!(
+            "zoom_x86_64.rpm:\n    Header V4 RSA/SHA512 Signature, key ID dd79b481: {status}\n    \
+            Header SHA256 digest: OK\n    Payload SHA256 digest: OK\n    \
+            V4 RSA/SHA512 Signature, key ID dd79b481: {status}\n    MD5 digest: OK\n"
+        );
+
+        assert!(rpm_signature_ok(&report("OK")));
+        assert!(!rpm_signature_ok(&report("NOKEY")));
+        assert!(!rpm_signature_ok(&report("BAD")));
+        assert!(!rpm_signature_ok("zoom_x86_64.rpm:\n    Header SHA256 digest: OK\n    MD5 digest: OK\n"));
+    }
 
     #[test]
     fn installs_zoom_gpg_key() {
@@ -107,7 +359,7 @@ mod tests {
         let fingerprint = "59C8 6188 E22A BB19 BD55 4047 7B04 A1B8 DD79 B481";
         let key = GpgKey::new(Url::parse(url).unwrap(), fingerprint.to_string());
 
-        key.install().expect("Fail to install Zoom GPG key");
+        key.install(None).expect("Fail to install Zoom GPG key");
     }
 
     #[test]
@@ -118,6 +370,6 @@ mod tests {
         let fingerprint = "09C8 6188 E22A BB19 BD55 4047 7B04 A1B8 DD79 B481";
         let key = GpgKey::new(Url::parse(url).unwrap(), fingerprint.to_string());
 
-        key.install().expect_err("Fail to reject wrong GPG fingerprint");
+        key.install(None).expect_err("Fail to reject wrong GPG fingerprint");
     }
 }
@@ -2,15 +2,29 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::io;
 use std::io::{BufReader, Read};
 use std::path::Path;
-use sha2::{Digest, Sha256};
+use serde_json::Value;
+use sha2::{Digest, Sha256, Sha384};
 
 #[derive(PartialEq, Clone, Debug)]
 pub(crate) enum HashAlgorithm {
-    Sha256
+    Sha256,
+    Sha384,
+}
+
+impl Display for HashAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Sha384 => "SHA-384",
+        };
+
+        write!(f, "{}", msg)
+    }
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -24,6 +38,14 @@ impl Hash {
         Hash { algorithm, hash }
     }
 
+    pub(crate) fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm.clone()
+    }
+
+    pub(crate) fn value(&self) -> String {
+        self.hash.clone()
+    }
+
     pub(crate) fn matches(&self, file_path: &Path) -> io::Result<bool> {
         self.calculate_hash(file_path)
             .map(|file_hash| self.hash == file_hash)
@@ -31,15 +53,70 @@ impl Hash {
 
     fn calculate_hash(&self, file_path: &Path) -> io::Result<String> {
         match self.algorithm {
-            HashAlgorithm::Sha256 => calculate_sha256(file_path)
+            HashAlgorithm::Sha256 => calculate_sha256(file_path),
+            HashAlgorithm::Sha384 => calculate_sha384(file_path),
         }
     }
 }
 
-fn calculate_sha256(file_path: &Path) -> io::Result<String> {
+/// A `<hash>  <file>` line per file, or its only hash if it lists one.
+pub(crate) fn parse_sha256sum(checksums: &str, filename: &str) -> Option<String> {
+    let is_sha256 = |hash: &str| hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit());
+
+    let entries = checksums
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let hash = fields.next().filter(|hash| is_sha256(hash))?;
+
+            // Binary mode marks the file like `*file`, maybe under a dir
+            let file = fields
+                .next()
+                .map(|file| file.trim_start_matches('*'))
+                .map(|file| file.rsplit('/').next().unwrap_or(file));
+
+            Some((hash.to_lowercase(), file))
+        })
+        .collect::<Vec<(String, Option<&str>)>>();
+
+    match entries.as_slice() {
+        [(hash, _)] => Some(hash.clone()),
+        _ => entries
+            .into_iter()
+            .find(|(_, file)| *file == Some(filename))
+            .map(|(hash, _)| hash),
+    }
+}
+
+/// The index lists the `archive` path and `sha256` of each release, like Flutter's.
+pub(crate) fn parse_releases_index(index: &str, filename: &str) -> Option<String> {
+    let index = serde_json::from_str::<Value>(index).ok()?;
+
+    index["releases"]
+        .as_array()?
+        .iter()
+        .find(|release| release["archive"]
+            .as_str()
+            .is_some_and(|archive| archive.rsplit('/').next() == Some(filename)))
+        .and_then(|release| release["sha256"].as_str())
+        .map(str::to_string)
+}
+
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    to_hex(&Sha256::digest(data))
+}
+
+pub(crate) fn calculate_sha256(file_path: &Path) -> io::Result<String> {
+    calculate_digest(file_path, Sha256::new())
+}
+
+fn calculate_sha384(file_path: &Path) -> io::Result<String> {
+    calculate_digest(file_path, Sha384::new())
+}
+
+fn calculate_digest<D: Digest>(file_path: &Path, mut hasher: D) -> io::Result<String> {
     let file = File::open(file_path)?;
     let mut reader = BufReader::new(file);
-    let mut hasher = Sha256::new();
     let mut buffer = [0; 1024];
 
     loop {
@@ -51,9 +128,11 @@ fn calculate_sha256(file_path: &Path) -> io::Result<String> {
         hasher.update(&buffer[..bytes_read]);
     }
 
-    let hash = hasher.finalize();
+    Ok(to_hex(&hasher.finalize()))
+}
 
-    Ok(format!("{:x}", hash))
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
 }
 
 #[cfg(test)]
@@ -61,7 +140,7 @@ mod tests {
     use std::io;
     use std::path::Path;
 
-    use crate::download::hashing::{calculate_sha256, Hash, HashAlgorithm};
+    use crate::download::hashing::{calculate_sha256, calculate_sha384, parse_releases_index, parse_sha256sum, Hash, HashAlgorithm};
 
     #[test]
     fn checks_sample_file_sha256() -> io::Result<()> {
@@ -91,4 +170,46 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn checks_sample_file_sha384_impl() -> io::Result<()> {
+        let checksum = "21aea43a16b408ee66527621b22d8017023b78fe061af7d708d0d588764b32c33b0e75bb2db26855eb67258ac1277a77";
+        let test_file_path = Path::new("resources")
+            .join("test")
+            .join("download")
+            .join("test_file.txt");
+        let computed_hash = calculate_sha384(&test_file_path)?;
+
+        assert_eq!(checksum, computed_hash);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_sha256sum_output() {
+        let hash = "0ecfebe350c45dbded8cfb32d3af0b910bde66fc2aafbafabdaaeef6cae48a59";
+        let other = "21aea43a16b408ee66527621b22d8017023b78fe061af7d708d0d588764b32c3";
+        let shasums = format!("{other}  app-1.0.0.darwin.tar.gz\n{hash} *./app-1.0.0.linux.tar.gz\n");
+
+        assert_eq!(Some(hash.to_string()), parse_sha256sum(&format!("{hash}  system-linux-x64\n"), "system-linux-x64"));
+        assert_eq!(Some(hash.to_string()), parse_sha256sum(hash, "system-linux-x64"));
+        assert_eq!(Some(hash.to_string()), parse_sha256sum(&shasums, "app-1.0.0.linux.tar.gz"));
+        assert_eq!(None, parse_sha256sum(&shasums, "app-1.0.0.windows.zip"));
+        assert_eq!(None, parse_sha256sum("not-a-hash system-linux-x64", "system-linux-x64"));
+        assert_eq!(None, parse_sha256sum("", "system-linux-x64"));
+    }
+
+    #[test]
+    fn reads_releases_index() {
+        let index = r#"{
+            "releases": [
+                { "archive": "stable/linux/flutter_linux_3.22.3-stable.tar.xz", "sha256": "abc" },
+                { "archive": "stable/linux/flutter_linux_3.24.0-stable.tar.xz", "sha256": "def" }
+            ]
+        }"#;
+
+        assert_eq!(Some("def".to_string()), parse_releases_index(index, "flutter_linux_3.24.0-stable.tar.xz"));
+        assert_eq!(None, parse_releases_index(index, "flutter_linux_3.24.1-stable.tar.xz"));
+        assert_eq!(None, parse_releases_index("not json", "flutter_linux_3.24.0-stable.tar.xz"));
+    }
 }
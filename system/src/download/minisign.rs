@@ -0,0 +1,144 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use minisign_verify::{Error, PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+
+/// In the base64 form vendors publish, like Zig's.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct MinisignKey(String);
+
+impl TryFrom<String> for MinisignKey {
+    type Error = String;
+
+    fn try_from(key_b64: String) -> Result<Self, Self::Error> {
+        MinisignKey::from_base64(&key_b64)
+    }
+}
+
+impl From<MinisignKey> for String {
+    fn from(key: MinisignKey) -> Self {
+        key.0
+    }
+}
+
+impl MinisignKey {
+    pub fn from_base64(key_b64: &str) -> Result<Self, String> {
+        let key_b64 = key_b64.trim();
+
+        PublicKey::from_base64(key_b64)
+            .map(|_| MinisignKey(key_b64.to_string()))
+            .map_err(|error| format!("Invalid minisign public key {}: {}", key_b64, error))
+    }
+
+    /// If the trusted comment names a file, it must name this one.
+    pub fn verify(&self, file_path: &Path, signature_raw: &str) -> Result<bool, String> {
+        let public_key = PublicKey::from_base64(&self.0).map_err(|error| error.to_string())?;
+        let signature = Signature::decode(signature_raw)
+            .map_err(|error| format!("Invalid minisign signature: {}", error))?;
+
+        let filename = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        if signed_filename(&signature).is_some_and(|signed| signed != filename) {
+            return Ok(false);
+        }
+
+        let result = match public_key.verify_stream(&signature) {
+            Ok(mut verifier) => {
+                let mut file = File::open(file_path).map_err(|error| error.to_string())?;
+                let mut buf = vec![0; 64 * 1024];
+
+                loop {
+                    let n = file.read(&mut buf).map_err(|error| error.to_string())?;
+
+                    if n == 0 {
+                        break;
+                    }
+
+                    verifier.update(&buf[..n]);
+                }
+
+                verifier.finalize()
+            }
+
+            // Legacy signatures sign the whole file rather than its hash
+            Err(Error::UnsupportedLegacyMode) => {
+                let bin = fs::read(file_path).map_err(|error| error.to_string())?;
+
+                public_key.verify(&bin, &signature, true)
+            }
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(()) => Ok(true),
+            Err(Error::InvalidSignature | Error::UnexpectedKeyId) => Ok(false),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+}
+
+/// Like `timestamp:1717962445\tfile:zig-linux-x86_64-0.13.0.tar.xz\thashed`.
+fn signed_filename(signature: &Signature) -> Option<&str> {
+    signature
+        .trusted_comment()
+        .split('\t')
+        .find_map(|field| field.strip_prefix("file:"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::download::minisign::MinisignKey;
+    use crate::tmp::TmpWorkingDir;
+
+    const PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RWQf6LRCGA9i59SLOFxz6NxvASXDJeRtuZykwQepbDEGt87ig1BNpWaVWuNrm73YiIiJbq71Wi+dP9eKL8OC351vwIasSSbXxwA=
+trusted comment: timestamp:1555779966\tfile:test
+QtKMXWyYcwdpZAlPF7tE2ENJkRd1ujvKjlj1m9RtHTBnZPa5WKU5uWRs5GoP5M/VqE81QFuMKI5k/SfNQUaOAA==";
+
+    #[test]
+    fn verifies_signature() -> Result<(), String> {
+        let tmp = TmpWorkingDir::new().map_err(|error| error.to_string())?;
+        let key = MinisignKey::from_base64(PUBLIC_KEY)?;
+        let file_path = tmp.join("test".as_ref());
+
+        fs::write(&file_path, "test").map_err(|error| error.to_string())?;
+        assert!(key.verify(&file_path, SIGNATURE)?);
+
+        fs::write(&file_path, "Test").map_err(|error| error.to_string())?;
+        assert!(!key.verify(&file_path, SIGNATURE)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_signature_of_another_file() -> Result<(), String> {
+        let tmp = TmpWorkingDir::new().map_err(|error| error.to_string())?;
+        let key = MinisignKey::from_base64(PUBLIC_KEY)?;
+        let file_path = tmp.join("other".as_ref());
+
+        fs::write(&file_path, "test").map_err(|error| error.to_string())?;
+        assert!(!key.verify(&file_path, SIGNATURE)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_key() {
+        assert!(MinisignKey::from_base64("not a key").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::path::Path;
+
+use reqwest::Url;
+use serde::Deserialize;
+
+use crate::cmd::exec_cmd;
+use crate::tmp::TmpWorkingDir;
+
+/// Proves the file was built by its source repository and CI, beyond its hash.
+#[derive(PartialEq, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Provenance {
+    /// Checked with `gh`, optionally restricted to the workflow that signs it.
+    GithubAttestation { repo: String, signer_workflow: Option<String> },
+
+    /// Like `<artifact>.intoto.jsonl`, checked with `slsa-verifier`.
+    Slsa { provenance_url: String, source_uri: String },
+}
+
+impl Provenance {
+    /// The SLSA provenance content is fetched beforehand.
+    pub fn verify(&self, file_path: &Path, provenance_raw: Option<&str>) -> Result<(), String> {
+        let file = file_path.to_str().unwrap_or_default();
+
+        match self {
+            Provenance::GithubAttestation { repo, signer_workflow } => {
+                check_tool("gh", "Install it with `system install gh` and log in with `gh auth login`")?;
+
+                let mut args = vec!["attestation", "verify", file, "--repo", repo];
+
+                if let Some(workflow) = signer_workflow {
+                    args.extend(["--signer-workflow", workflow]);
+                }
+
+                exec_cmd("gh", &args)
+                    .map(|_| ())
+                    .map_err(|error| format!("Fail to verify the GitHub attestation of {file}.\nCause: {error}"))
+            }
+
+            Provenance::Slsa { source_uri, .. } => {
+                check_tool("slsa-verifier", "Install it from https://github.com/slsa-framework/slsa-verifier")?;
+
+                let provenance_raw = provenance_raw
+                    .ok_or_else(|| format!("No SLSA provenance of {file} to verify"))?;
+
+                let tmp = TmpWorkingDir::new()
+                    .map_err(|error| error.to_string())?;
+
+                let provenance_path = tmp.join(Path::new("provenance.intoto.jsonl"));
+
+                fs::write(&provenance_path, provenance_raw)
+                    .map_err(|error| error.to_string())?;
+
+                let provenance_path = provenance_path.to_str().unwrap_or_default();
+
+                exec_cmd(
+                    "slsa-verifier",
+                    &["verify-artifact", file, "--provenance-path", provenance_path, "--source-uri", source_uri],
+                )
+                    .map(|_| ())
+                    .map_err(|error| format!("Fail to verify the SLSA provenance of {file}.\nCause: {error}"))
+            }
+        }
+    }
+
+    pub fn provenance_url(&self) -> Result<Option<Url>, String> {
+        match self {
+            Provenance::GithubAttestation { .. } => Ok(None),
+            Provenance::Slsa { provenance_url, .. } => Url::parse(provenance_url)
+                .map(Some)
+                .map_err(|error| format!("Invalid provenance URL {provenance_url}: {error}")),
+        }
+    }
+
+    pub fn needs_network(&self) -> bool {
+        matches!(self, Provenance::GithubAttestation { .. })
+    }
+}
+
+fn check_tool(tool: &str, hint: &str) -> Result<(), String> {
+    exec_cmd("bash", &["-c", &format!("command -v {tool}")])
+        .map(|_| ())
+        .map_err(|_| format!("{tool} is required to verify the provenance but is not installed. {hint}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::download::provenance::Provenance;
+
+    #[test]
+    fn reads_provenance() {
+        let attestation: Provenance = serde_json::from_str(r#"{ "github_attestation": { "repo": "astral-sh/uv" } }"#).unwrap();
+        let slsa: Provenance = serde_json::from_str(r#"{
+            "slsa": {
+                "provenance_url": "https://github.com/slsa-framework/slsa-verifier/releases/download/v2.6.0/slsa-verifier-linux-amd64.intoto.jsonl",
+                "source_uri": "github.com/slsa-framework/slsa-verifier"
+            }
+        }"#).unwrap();
+
+        assert_eq!(Provenance::GithubAttestation { repo: "astral-sh/uv".to_string(), signer_workflow: None }, attestation);
+        assert!(attestation.needs_network());
+        assert_eq!(Ok(None), attestation.provenance_url());
+
+        assert!(!slsa.needs_network());
+        assert!(slsa.provenance_url().unwrap().is_some());
+    }
+}
@@ -0,0 +1,227 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+
+const PIN_PREFIX: &str = "sha256//";
+
+/// In curl's `--pinnedpubkey` format, like `sha256//<base64>`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct PublicKeyPin(String);
+
+impl PublicKeyPin {
+    fn of_certificate(cert_der: &[u8]) -> Option<Self> {
+        subject_public_key_info(cert_der)
+            .map(|spki| PublicKeyPin(base64(&Sha256::digest(spki))))
+    }
+}
+
+impl FromStr for PublicKeyPin {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_base64 = |c: char| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=';
+
+        s.strip_prefix(PIN_PREFIX)
+            .filter(|digest| digest.len() == 44 && digest.chars().all(is_base64))
+            .map(|digest| PublicKeyPin(digest.to_string()))
+            .ok_or_else(|| format!("Invalid public key pin {s}, e.g., {PIN_PREFIX}<base64 SHA-256>"))
+    }
+}
+
+impl Display for PublicKeyPin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", PIN_PREFIX, self.0)
+    }
+}
+
+pub fn parse_host_pin(host_pin: &str) -> Result<(String, PublicKeyPin), String> {
+    let (host, pin) = host_pin
+        .split_once('=')
+        .filter(|(host, _)| !host.is_empty())
+        .ok_or_else(|| format!("Invalid host pin {host_pin}, e.g., sh.rustup.rs={PIN_PREFIX}<base64 SHA-256>"))?;
+
+    Ok((host.to_string(), pin.parse()?))
+}
+
+pub fn verify(host: &str, cert_der: Option<&[u8]>, pins: &[PublicKeyPin]) -> Result<(), String> {
+    if pins.is_empty() {
+        return Ok(());
+    }
+
+    let pin = cert_der
+        .and_then(PublicKeyPin::of_certificate)
+        .ok_or_else(|| format!("Unable to read the certificate of {host} to check its pins"))?;
+
+    if pins.contains(&pin) {
+        Ok(())
+    } else {
+        Err(format!("Certificate public key {pin} of {host} doesn't match its pins"))
+    }
+}
+
+/// curl checks the pins in the handshake, before the shell runs any line.
+pub fn curl_script(url: &Url, pins: &[PublicKeyPin]) -> String {
+    let pin_arg = if pins.is_empty() {
+        String::new()
+    } else {
+        let pins = pins.iter().map(|pin| pin.to_string()).collect::<Vec<String>>();
+
+        format!(" --pinnedpubkey '{}'", pins.join(";"))
+    };
+
+    format!("set -o pipefail; curl --proto '=https' --tlsv1.2{pin_arg} -sSf {url}")
+}
+
+/// Tag, whole encoding, content, and the bytes after a DER element.
+type DerElement<'a> = (u8, &'a [u8], &'a [u8], &'a [u8]);
+
+fn der_element(der: &[u8]) -> Option<DerElement<'_>> {
+    let tag = *der.first()?;
+    let len_byte = *der.get(1)? as usize;
+    let (header_len, content_len) = if len_byte < 0x80 {
+        (2, len_byte)
+    } else {
+        let len_bytes = len_byte & 0x7f;
+
+        if len_bytes == 0 || len_bytes > 4 {
+            return None;
+        }
+
+        let len = der
+            .get(2..2 + len_bytes)?
+            .iter()
+            .fold(0usize, |len, byte| (len << 8) | *byte as usize);
+
+        (2 + len_bytes, len)
+    };
+
+    let end = header_len.checked_add(content_len)?;
+    let element = der.get(..end)?;
+
+    Some((tag, element, &element[header_len..], &der[end..]))
+}
+
+/// The seventh field of the `tbsCertificate` if the explicit version comes first.
+fn subject_public_key_info(cert_der: &[u8]) -> Option<&[u8]> {
+    let (_, _, certificate, _) = der_element(cert_der)?;
+    let (_, _, tbs_certificate, _) = der_element(certificate)?;
+    let mut fields = tbs_certificate;
+
+    if fields.first() == Some(&0xa0) {
+        fields = der_element(fields)?.3;
+    }
+
+    // serialNumber, signature, issuer, validity, and subject
+    for _ in 0..5 {
+        fields = der_element(fields)?.3;
+    }
+
+    der_element(fields).map(|(_, spki, _, _)| spki)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    bytes
+        .chunks(3)
+        .flat_map(|chunk| {
+            let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+            let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+            (0..4).map(move |i| {
+                if i <= chunk.len() {
+                    ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char
+                } else {
+                    '='
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Url;
+
+    use crate::download::tls::{base64, curl_script, der_element, parse_host_pin, subject_public_key_info, verify, PublicKeyPin};
+
+    const PIN: &str = "sha256//YhKJKSzoTt2b5FP18fvpHo7fJYqQCjAa3HWY3tvRMwE=";
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!("", base64(b""));
+        assert_eq!("Zg==", base64(b"f"));
+        assert_eq!("Zm8=", base64(b"fo"));
+        assert_eq!("Zm9v", base64(b"foo"));
+        assert_eq!("Zm9vYmFy", base64(b"foobar"));
+    }
+
+    #[test]
+    fn parses_pins() {
+        let (host, pin) = parse_host_pin(&format!("sh.rustup.rs={PIN}")).unwrap();
+
+        assert_eq!("sh.rustup.rs", host);
+        assert_eq!(PIN, pin.to_string());
+        assert!(parse_host_pin(PIN).is_err());
+        assert!("sha256//short".parse::<PublicKeyPin>().is_err());
+        assert!("YhKJKSzoTt2b5FP18fvpHo7fJYqQCjAa3HWY3tvRMwE=".parse::<PublicKeyPin>().is_err());
+    }
+
+    #[test]
+    fn reads_long_der_lengths() {
+        let mut der = vec![0x30, 0x81, 0x80];
+        der.extend([0u8; 0x80]);
+        der.push(0x05);
+
+        let (tag, element, content, rest) = der_element(&der).unwrap();
+
+        assert_eq!(0x30, tag);
+        assert_eq!(0x83, element.len());
+        assert_eq!(0x80, content.len());
+        assert_eq!(&[0x05], rest);
+    }
+
+    #[test]
+    fn finds_subject_public_key_info() {
+        // Certificate { tbsCertificate { [0] version, serial, signature,
+        // issuer, validity, subject, spki }, ... }
+        let spki = [0x30, 0x03, 0x02, 0x01, 0x07];
+        let mut tbs = vec![0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01];
+        tbs.extend([0x30, 0x00, 0x30, 0x00, 0x30, 0x00, 0x30, 0x00]);
+        tbs.extend(spki);
+
+        let mut certificate = vec![0x30, tbs.len() as u8];
+        certificate.extend(tbs);
+
+        let mut cert = vec![0x30, certificate.len() as u8];
+        cert.extend(certificate);
+
+        assert_eq!(Some(&spki[..]), subject_public_key_info(&cert));
+        assert_eq!(None, subject_public_key_info(&cert[..10]));
+    }
+
+    #[test]
+    fn verifies_pinned_hosts_only() {
+        let pins = [PIN.parse().unwrap()];
+
+        assert!(verify("example.com", None, &[]).is_ok());
+        assert!(verify("pinned.example.com", None, &pins).is_err());
+    }
+
+    #[test]
+    fn pins_curl_scripts() {
+        let url = Url::parse("https://script.example.com/install.sh").unwrap();
+        let pinned = curl_script(&url, &[PIN.parse().unwrap()]);
+        let unpinned = curl_script(&url, &[]);
+
+        assert!(pinned.contains(&format!("--pinnedpubkey '{PIN}'")));
+        assert!(pinned.starts_with("set -o pipefail;"));
+        assert!(!unpinned.contains("--pinnedpubkey"));
+    }
+}
@@ -7,14 +7,26 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::PathBuf;
 
+use reqwest::Url;
 use serde::de::DeserializeOwned;
-
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::download::auth::Auth;
+use crate::download::provenance::Provenance;
+use crate::download::tls::PublicKeyPin;
+use crate::download::DownloadRequest;
+use crate::image::detection::Detection;
+use crate::image::pin::Pins;
 use crate::image::ImageOperationError::{InfoError, OperationNotImplemented};
 use crate::os::Os;
 use crate::package::Package;
-use ImageInfoError::{IoError, SerdeError};
+use ImageInfoError::{IoError, SerdeError, UnsupportedOs};
 
 pub(crate) mod repository;
+pub mod detection;
+pub mod pin;
+pub mod purge;
 mod desktop;
 mod server;
 
@@ -31,6 +43,12 @@ pub trait ToImageId where Self: Display {
     fn to_image_id(&self) -> ImageId;
 }
 
+impl ToImageId for ImageId {
+    fn to_image_id(&self) -> ImageId {
+        self.clone()
+    }
+}
+
 pub trait StrFind {
     fn str_find(s: &str) -> Option<Self> where Self: Sized;
 }
@@ -39,6 +57,8 @@ pub trait Image: Display {
     fn id(&self) -> ImageId;
 
     fn package(&self) -> Package;
+
+    fn package_mut(&mut self) -> &mut Package;
 }
 
 #[macro_export]
@@ -58,6 +78,10 @@ macro_rules! impl_image {
             fn package(&self) -> Package {
                 self.1.clone()
             }
+
+            fn package_mut(&mut self) -> &mut Package {
+                &mut self.1
+            }
         }
     };
 }
@@ -73,11 +97,33 @@ pub trait Uninstall {
 pub trait ImageOps: Install + Uninstall {
     fn image(&self) -> Box<dyn Image>;
 
+    fn image_mut(&mut self) -> &mut dyn Image;
+
     fn reinstall(&self) -> Result<(), String> {
         self.uninstall()?;
         self.install()?;
         Ok(())
     }
+
+    /// Defaults to a binary named as the image ID that prints its `--version`.
+    fn detection(&self) -> Detection {
+        Detection::binary(&self.image().id().to_string(), &["--version"])
+    }
+
+    /// Paths relative to the home that `uninstall --purge` removes.
+    fn purge_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Files the image downloads to install, so `fetch` can get them ahead.
+    fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Downloads without an integrity check, which `--require-integrity` refuses.
+    fn unverified_downloads(&self) -> Vec<Url> {
+        self.image().package().unverified_download().into_iter().collect()
+    }
 }
 
 #[macro_export]
@@ -86,6 +132,10 @@ macro_rules! image_ops_impl {
         fn image(&self) -> Box<dyn Image> {
             Box::new(self.0.clone())
         }
+
+        fn image_mut(&mut self) -> &mut dyn Image {
+            &mut self.0
+        }
     };
 }
 
@@ -117,6 +167,13 @@ where
 pub enum ImageInfoError {
     IoError(String),
     SerdeError(String),
+    UnsupportedOs(String),
+}
+
+impl ImageInfoError {
+    pub fn unsupported_os(id: &impl Display, os: &Os) -> Self {
+        UnsupportedOs(format!("Image {id} doesn't support {}", os.distro_id()))
+    }
 }
 
 impl Display for ImageInfoError {
@@ -124,6 +181,7 @@ impl Display for ImageInfoError {
         let msg = match self {
             IoError(msg) => format!("IO Error: {}", msg),
             SerdeError(msg) => format!("Serialization/Deserialization Error: {}", msg),
+            UnsupportedOs(msg) => format!("Unsupported OS: {}", msg),
         };
 
         write!(f, "{}", msg)
@@ -211,6 +269,11 @@ impl ImageInfoLoader {
     }
 
     pub fn load<D: DeserializeOwned>(&self) -> Result<D, ImageInfoError> {
+        serde_json::from_value(self.load_value()?)
+            .map_err(|error| SerdeError(error.to_string()))
+    }
+
+    fn load_value(&self) -> Result<Value, ImageInfoError> {
         let info_path = self.path();
         let file = File::open(info_path.clone())
             .map_err(|error| IoError(
@@ -218,9 +281,62 @@ impl ImageInfoLoader {
             ))?;
 
         let reader = BufReader::new(file);
+        let mut info: Value = serde_json::from_reader(reader)
+            .map_err(|error| SerdeError(error.to_string()))?;
 
-        serde_json::from_reader(reader)
-            .map_err(|error| SerdeError(error.to_string()))
+        if let InfoFileType::Image = self.file_type {
+            let pins = Pins::load().map_err(IoError)?;
+
+            if let Some(pin) = pins.get(&self.id) {
+                pin.apply(&mut info).map_err(SerdeError)?;
+            }
+        }
+
+        Ok(info)
+    }
+}
+
+#[derive(Deserialize)]
+struct ImageDownloadInfo {
+    #[serde(default)]
+    mirrors: Vec<String>,
+
+    #[serde(default)]
+    auth: Option<Auth>,
+
+    #[serde(default)]
+    tls_pins: Vec<String>,
+
+    #[serde(default)]
+    provenance: Option<Provenance>,
+}
+
+impl ImageDownloadInfo {
+    fn dirs(&self) -> Result<Vec<Url>, ImageInfoError> {
+        self.mirrors
+            .iter()
+            .map(|mirror| {
+                // Keep the last path segment when joining the filename
+                let dir = if mirror.ends_with('/') { mirror.clone() } else { format!("{mirror}/") };
+
+                Url::parse(&dir)
+                    .map_err(|error| SerdeError(format!("Invalid mirror {mirror}: {error}")))
+                    .and_then(|url| {
+                        if url.scheme() == "https" {
+                            Ok(url)
+                        } else {
+                            Err(SerdeError(format!("Mirror {mirror} protocol is not HTTPS")))
+                        }
+                    })
+            })
+            .collect()
+    }
+
+    fn pins(&self) -> Result<Vec<PublicKeyPin>, ImageInfoError> {
+        self.tls_pins
+            .iter()
+            .map(|pin| pin.parse().map_err(SerdeError))
+            .collect()
     }
 }
 
@@ -241,36 +357,58 @@ impl ImageLoadContext {
         Box::new(cons(os))
     }
 
-    fn image_from<D: DeserializeOwned, T: ImageOps + 'static>(
-        os: Os,
-        info: D,
+    pub fn load<D: DeserializeOwned, T: ImageOps + 'static>(
+        &self,
         cons: impl Fn(Os, D) -> T,
-    ) -> Box<dyn ImageOps> {
-        Box::new(cons(os, info))
+    ) -> Result<Box<dyn ImageOps>, ImageInfoError> {
+        self.try_load(|os, info| Ok(cons(os, info)))
     }
 
-    pub fn load<D: DeserializeOwned, T: ImageOps + 'static>(
+    /// Loads an image whose constructor fails on an OS it has no build for.
+    pub fn try_load<D: DeserializeOwned, T: ImageOps + 'static>(
         &self,
-        cons: impl Fn(Os, D) -> T,
+        cons: impl Fn(Os, D) -> Result<T, ImageInfoError>,
     ) -> Result<Box<dyn ImageOps>, ImageInfoError> {
-        let info = self.info_loader.load()?;
-        let image = Self::image_from(self.os.clone(), info, cons);
+        let info = self.info_loader.load_value()?;
+        let download_info = ImageDownloadInfo::deserialize(&info)
+            .map_err(|error| SerdeError(error.to_string()))?;
+
+        let info = serde_json::from_value(info)
+            .map_err(|error| SerdeError(error.to_string()))?;
+
+        let mut image = cons(self.os.clone(), info)?;
+        let package = image.image_mut().package_mut();
+
+        package.set_mirror_dirs(download_info.dirs()?);
+        package.set_pins(download_info.pins()?);
+
+        if let Some(auth) = download_info.auth.clone() {
+            package.set_auth(auth);
+        }
 
-        Ok(image)
+        if let Some(provenance) = download_info.provenance {
+            package.fetch = package.fetch.clone().with_provenance(provenance);
+        }
+
+        Ok(Box::new(image))
     }
 
     pub fn load_concrete<D: DeserializeOwned, T: ImageOps + 'static>(
         &self,
         cons: impl Fn(Os, D) -> T,
     ) -> Result<T, ImageOperationError> {
-        let info = self
+        self.try_load_concrete(|os, info| Ok(cons(os, info)))
+    }
+
+    pub fn try_load_concrete<D: DeserializeOwned, T: ImageOps + 'static>(
+        &self,
+        cons: impl Fn(Os, D) -> Result<T, ImageInfoError>,
+    ) -> Result<T, ImageOperationError> {
+        self
             .info_loader
             .load()
-            .map_err(ImageOperationError::from_image_info_error)?;
-
-        let image = cons(self.os.clone(), info);
-
-        Ok(image)
+            .and_then(|info| cons(self.os.clone(), info))
+            .map_err(ImageOperationError::from_image_info_error)
     }
 
     pub fn load_config<D: DeserializeOwned>(
@@ -312,7 +450,8 @@ pub trait ImageLoader: Display + ToImageId + LoadImage {}
 
 #[cfg(test)]
 mod tests {
-    use crate::image::{ImageId, ImageInfoLoader, InfoFileType};
+    use crate::download::auth::Auth;
+    use crate::image::{ImageDownloadInfo, ImageId, ImageInfoLoader, InfoFileType};
     use std::path::PathBuf;
 
     #[test]
@@ -336,4 +475,56 @@ mod tests {
             config.path(),
         );
     }
+
+    #[test]
+    fn reads_mirror_dirs() {
+        let mirrors: ImageDownloadInfo = serde_json::from_str(r#"{
+            "version": "1.22.5",
+            "mirrors": ["https://mirror.example.com/go", "https://dl.example.com/"]
+        }"#).unwrap();
+
+        let dirs = mirrors
+            .dirs()
+            .unwrap()
+            .iter()
+            .map(|dir| dir.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(vec!["https://mirror.example.com/go/", "https://dl.example.com/"], dirs);
+
+        let no_mirrors: ImageDownloadInfo = serde_json::from_str(r#"{ "version": "1.22.5" }"#).unwrap();
+
+        assert!(no_mirrors.dirs().unwrap().is_empty());
+
+        let insecure: ImageDownloadInfo = serde_json::from_str(r#"{ "mirrors": ["http://mirror.example.com"] }"#).unwrap();
+
+        assert!(insecure.dirs().is_err());
+    }
+
+    #[test]
+    fn reads_download_auth() {
+        let info: ImageDownloadInfo = serde_json::from_str(r#"{
+            "version": "1.0.0",
+            "auth": { "bearer": { "token_env": "GITHUB_TOKEN" } }
+        }"#).unwrap();
+
+        assert_eq!(Some(Auth::Bearer { token_env: "GITHUB_TOKEN".to_string() }), info.auth);
+
+        let no_auth: ImageDownloadInfo = serde_json::from_str(r#"{ "version": "1.0.0" }"#).unwrap();
+
+        assert_eq!(None, no_auth.auth);
+    }
+
+    #[test]
+    fn reads_tls_pins() {
+        let info: ImageDownloadInfo = serde_json::from_str(r#"{
+            "tls_pins": ["sha256//YhKJKSzoTt2b5FP18fvpHo7fJYqQCjAa3HWY3tvRMwE="]
+        }"#).unwrap();
+
+        assert_eq!(1, info.pins().unwrap().len());
+
+        let invalid: ImageDownloadInfo = serde_json::from_str(r#"{ "tls_pins": ["abc"] }"#).unwrap();
+
+        assert!(invalid.pins().is_err());
+    }
 }
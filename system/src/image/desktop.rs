@@ -6,7 +6,7 @@ use core::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-use DesktopImageId::{CLion, DataGrip, Goland, IntelliJIdea, JetBrainsToolbox, PyCharm, Rider, RustRover, VsCode};
+use DesktopImageId::{CLion, DataGrip, DataSpell, DevFonts, Fleet, Gimp, Goland, IntelliJIdea, JetBrainsToolbox, Obsidian, PyCharm, Rider, RustRover, VsCode, Writerside};
 
 use crate::image::desktop::DesktopImageId::{PhpStorm, RubyMine, WebStorm, Zoom};
 use crate::image::{Image, ImageId, StrFind, ToImageId};
@@ -28,6 +28,38 @@ pub enum DesktopImageId {
     Rider,
     PhpStorm,
     RubyMine,
+    Fleet,
+    DataSpell,
+    Writerside,
+    DevFonts,
+    Gimp,
+    Obsidian,
+}
+
+impl DesktopImageId {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Zoom,
+            VsCode,
+            JetBrainsToolbox,
+            IntelliJIdea,
+            WebStorm,
+            RustRover,
+            CLion,
+            PyCharm,
+            DataGrip,
+            Goland,
+            Rider,
+            PhpStorm,
+            RubyMine,
+            Fleet,
+            DataSpell,
+            Writerside,
+            DevFonts,
+            Gimp,
+            Obsidian,
+        ]
+    }
 }
 
 impl Display for DesktopImageId {
@@ -46,6 +78,12 @@ impl Display for DesktopImageId {
             Rider => "rider",
             PhpStorm => "phpstorm",
             RubyMine => "rubymine",
+            Fleet => "fleet",
+            DataSpell => "dataspell",
+            Writerside => "writerside",
+            DevFonts => "dev-fonts",
+            Gimp => "gimp",
+            Obsidian => "obsidian",
         };
 
         write!(f, "{}", msg)
@@ -68,6 +106,12 @@ impl StrFind for DesktopImageId {
             "rider" => Some(Rider),
             "phpstorm" => Some(PhpStorm),
             "rubymine" => Some(RubyMine),
+            "fleet" => Some(Fleet),
+            "dataspell" => Some(DataSpell),
+            "writerside" => Some(Writerside),
+            "dev-fonts" => Some(DevFonts),
+            "gimp" => Some(Gimp),
+            "obsidian" => Some(Obsidian),
             _ => None
         }
     }
@@ -94,6 +138,7 @@ pub struct DesktopImage(DesktopImageId, Package);
 impl_image!(DesktopImage);
 
 pub mod zoom {
+    use std::path::PathBuf;
     use reqwest::Url;
     use serde::{Deserialize, Serialize};
 
@@ -102,16 +147,19 @@ pub mod zoom {
     use crate::download::{DownloadRequest, Downloader, Integrity};
     use crate::image::desktop::DesktopImage;
     use crate::image::desktop::DesktopImageId::Zoom;
-    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
     use crate::image_ops_impl;
-    use crate::os::LinuxType::Ubuntu;
+    use crate::os::LinuxType::{Arch, Debian, Fedora, Ubuntu};
     use crate::os::Os;
-    use crate::os::Os::Linux;
+    use crate::os::Os::{Linux, Windows};
     use crate::os::OsArch::X64;
-    use crate::os::PkgType::Deb;
+    use crate::os::PkgType::{Deb, Pacman, Winget};
     use crate::package::{Package, SemVerRev, Software};
     use crate::tmp::TmpWorkingDir;
 
+    const WINGET_ID: &str = "Zoom.Zoom";
+
     #[derive(Debug, Serialize, Deserialize)]
     pub struct ZoomInfo {
         version: SemVerRev,
@@ -125,51 +173,81 @@ pub mod zoom {
         pub fn new(
             os: Os,
             ZoomInfo { version, public_key_version, key_fingerprint }: ZoomInfo,
-        ) -> Self {
+        ) -> Result<Self, ImageInfoError> {
             let id = Zoom;
             let pkg_id = id.to_string();
+            let software = Software::new("Zoom Video Communications, Inc", "Zoom", &version.to_string());
+            let doc = Url::parse("https://zoom.us/download").unwrap();
+
+            // winget downloads the native installer and checks its hash
+            if let Windows(_) = os {
+                return Ok(ZoomImage(DesktopImage(id, Package::new_managed(WINGET_ID, os, software, doc))));
+            }
+
             let filename = match os {
-                Linux(X64, Ubuntu) => "zoom_amd64.deb"
+                Linux(X64, Fedora) => "zoom_x86_64.rpm",
+
+                // Arch has no signed vendor package, so the install refuses it
+                Linux(X64, Ubuntu | Debian | Arch) => "zoom_amd64.deb",
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
             };
             let fetch_url = format!("https://zoom.us/client/{}/{}", version, filename);
             let gpg_key_url = Url::parse(format!("https://zoom.us/linux/download/pubkey?version={}", public_key_version).as_str()).unwrap();
             let gpg_key = GpgKey::new(gpg_key_url, key_fingerprint);
 
-            ZoomImage(
+            Ok(ZoomImage(
                 DesktopImage(
                     id,
                     Package::new(
                         &pkg_id,
                         os,
-                        Software::new("Zoom Video Communications, Inc", "Zoom", &version.to_string()),
-                        Url::parse("https://zoom.us/download").unwrap(),
+                        software,
+                        doc,
                         DownloadRequest::new(&fetch_url, Integrity::Gpg(gpg_key)).unwrap(),
-                    )))
+                    ))))
         }
     }
 
     impl Install for ZoomImage {
         fn install(&self) -> Result<(), String> {
             let package = self.0.package();
+
+            if package.os.app_pkg_type() == Winget {
+                info!("Installing Zoom with winget...");
+
+                return package.to_os_pkg(Winget).install_managed();
+            }
+
             let tmp = TmpWorkingDir::new()
                 .map_err(|error| error.to_string())?;
 
             let downloader = Downloader::from(package.fetch.clone(), &tmp);
             let file_path = downloader.path.clone();
 
-            println!("Downloading Zoom...");
+            info!("Downloading Zoom...");
 
             downloader
                 .download_blocking()
                 .map_err(|error| error.to_string())?;
 
-            println!("Installing Zoom...");
+            let pkg_type = package.os.app_pkg_type();
+
+            if pkg_type == Pacman {
+                return Err("Zoom publishes no signed Arch package, so install it from the AUR".to_string());
+            }
+
+            info!("Installing Zoom...");
 
             package
-                .to_os_pkg(Deb)
+                .to_os_pkg(pkg_type.clone())
                 .install(&file_path)?;
 
-            println!("Installing unmet dependencies...");
+            // dnf installs the dependencies along with the package
+            if pkg_type != Deb {
+                return Ok(());
+            }
+
+            info!("Installing unmet dependencies...");
 
             let output = exec_cmd(
                 "sudo",
@@ -177,7 +255,7 @@ pub mod zoom {
             ).map_err(|error| error.to_string())?;
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
             Ok(())
         }
@@ -185,11 +263,35 @@ pub mod zoom {
 
     impl Uninstall for ZoomImage {
         fn uninstall(&self) -> Result<(), String> {
-            self.0.package().to_os_pkg(Deb).uninstall()
+            let package = self.0.package();
+
+            package.to_os_pkg(package.os.app_pkg_type()).uninstall()
         }
     }
 
-    impl ImageOps for ZoomImage { image_ops_impl!(); }
+    impl ImageOps for ZoomImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            let package = self.0.package();
+
+            Detection::os_package(&package.os, &package.name)
+        }
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".zoom"), PathBuf::from(".config/zoomus.conf")]
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            let package = self.0.package();
+
+            if package.is_managed() {
+                return Ok(Vec::new());
+            }
+
+            Ok(vec![package.fetch])
+        }
+    }
 
     #[cfg(test)]
     mod tests {
@@ -202,8 +304,9 @@ pub mod zoom {
         use crate::image::desktop::zoom::{ZoomImage, ZoomInfo};
         use crate::image::desktop::DesktopImage;
         use crate::image::desktop::DesktopImageId::Zoom;
-        use crate::image::ImageInfoLoader;
-        use crate::os::UBUNTU_X64;
+        use crate::image::detection::Detection;
+        use crate::image::{Image, ImageInfoLoader, ImageOps};
+        use crate::os::{FEDORA_X64, UBUNTU_X64, WINDOWS_X64};
         use crate::package::SemVerRev;
 
         #[test]
@@ -227,7 +330,7 @@ pub mod zoom {
                 public_key_version: "5-12-6".to_string(),
                 key_fingerprint: "59C8 6188 E22A BB19 BD55 4047 7B04 A1B8 DD79 B481".to_string(),
             };
-            let ZoomImage(DesktopImage(id, package)) = ZoomImage::new(UBUNTU_X64, zoom_info);
+            let ZoomImage(DesktopImage(id, package)) = ZoomImage::new(UBUNTU_X64, zoom_info).unwrap();
             let expected_gpg_key = GpgKey::new(
                 Url::parse("https://zoom.us/linux/download/pubkey?version=5-12-6").unwrap(),
                 "59C8 6188 E22A BB19 BD55 4047 7B04 A1B8 DD79 B481".to_string(),
@@ -239,59 +342,115 @@ pub mod zoom {
             assert_eq!("6.1.1.443", package.software.version);
             assert_eq!("https://zoom.us/client/6.1.1.443/zoom_amd64.deb", package.fetch.url().as_str());
             assert_eq!(Integrity::Gpg(expected_gpg_key), package.fetch.integrity());
+
+            let zoom_info = ZoomInfo {
+                version: SemVerRev(6, 1, 1, 443),
+                public_key_version: "5-12-6".to_string(),
+                key_fingerprint: "59C8 6188 E22A BB19 BD55 4047 7B04 A1B8 DD79 B481".to_string(),
+            };
+            let ZoomImage(DesktopImage(_, package)) = ZoomImage::new(FEDORA_X64, zoom_info).unwrap();
+
+            assert_eq!("https://zoom.us/client/6.1.1.443/zoom_x86_64.rpm", package.fetch.url().as_str());
+        }
+
+        #[test]
+        fn installs_zoom_with_winget_on_windows() {
+            let zoom_info = ZoomInfo {
+                version: SemVerRev(6, 1, 1, 443),
+                public_key_version: "5-12-6".to_string(),
+                key_fingerprint: "59C8 6188 E22A BB19 BD55 4047 7B04 A1B8 DD79 B481".to_string(),
+            };
+            let image = ZoomImage::new(WINDOWS_X64, zoom_info).unwrap();
+
+            assert_eq!("Zoom.Zoom", image.0.package().name);
+            assert_eq!(Detection::WingetPackage("Zoom.Zoom".to_string()), image.detection());
+            assert!(image.artifacts().unwrap().is_empty());
         }
     }
 }
 
 pub mod vscode {
-    use reqwest::redirect::Policy;
-    use reqwest::{blocking, Url};
+    use std::fs;
+    use std::path::PathBuf;
+    use reqwest::Url;
     use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Value};
 
     use Os::Linux;
 
+    use crate::cmd::{exec_cmd, exec_powershell, print_output};
+    use crate::download::gpg::GpgKey;
     use crate::download::hashing::Hash;
     use crate::download::hashing::HashAlgorithm::Sha256;
-    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::download::DownloadRequestError::UnexpectedVersion;
+    use crate::download::{DownloadRequest, Downloader, Integrity, VersionPolicy};
     use crate::image::desktop::DesktopImage;
     use crate::image::desktop::DesktopImageId::VsCode;
-    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image::detection::Detection;
+    use crate::image::{Config, Image, ImageConfig, ImageInfoError, ImageOps, Install, ToImageConfig, Uninstall};
     use crate::image_ops_impl;
+    use crate::os::LinuxType::{Arch, Debian, Fedora, Ubuntu};
     use crate::os::Os;
+    use crate::os::Os::Windows;
     use crate::os::OsArch::X64;
-    use crate::os::PkgType::Deb;
+    use crate::os::PkgType::{Pacman, Rpm, Winget};
     use crate::package::{Package, SemVer, Software};
     use crate::tmp::TmpWorkingDir;
 
+    const WINGET_ID: &str = "Microsoft.VisualStudioCode";
+
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct VsCodeInfo {
         version: SemVer,
         hash_sha256: String,
+        rpm_key_fingerprint: String,
         use_latest_if_version_is_old: bool,
     }
 
+    #[derive(Clone)]
     pub struct VsCodeImage(DesktopImage, VsCodeInfo);
 
     impl VsCodeImage {
-        pub fn new(os: Os, info: VsCodeInfo) -> Self {
-            let VsCodeInfo { version, hash_sha256, .. } = info.clone();
+        pub fn new(os: Os, info: VsCodeInfo) -> Result<Self, ImageInfoError> {
+            let VsCodeInfo { version, hash_sha256, rpm_key_fingerprint, .. } = info.clone();
             let id = VsCode;
             let pkg_name = "code";
-            let fetch_url = match os {
-                Linux(X64, _) => "https://code.visualstudio.com/sha/download?build=stable&os=linux-deb-x64",
+            let software = Software::new("Microsoft Corporation", "Visual Studio Code", &version.to_string());
+            let doc = Url::parse("https://code.visualstudio.com/download").unwrap();
+
+            // winget downloads the native installer and checks its hash
+            if let Windows(_) = os {
+                return Ok(VsCodeImage(DesktopImage(id, Package::new_managed(WINGET_ID, os, software, doc)), info));
+            }
+
+            // The .rpm build has its own hash, but Microsoft signs it, so it
+            // checks against the key for any version
+            let (fetch_url, integrity) = match os {
+                // Arch has no vendor package, so the install refuses it
+                Linux(X64, Ubuntu | Debian | Arch) => (
+                    "https://code.visualstudio.com/sha/download?build=stable&os=linux-deb-x64",
+                    Integrity::Hash(Hash::new(Sha256, hash_sha256)),
+                ),
+                Linux(X64, Fedora) => (
+                    "https://code.visualstudio.com/sha/download?build=stable&os=linux-rpm-x64",
+                    Integrity::Gpg(GpgKey::new(
+                        Url::parse("https://packages.microsoft.com/keys/microsoft.asc").unwrap(),
+                        rpm_key_fingerprint,
+                    )),
+                ),
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
             };
-            let hash = Hash::new(Sha256, hash_sha256);
 
-            VsCodeImage(DesktopImage(
+            Ok(VsCodeImage(DesktopImage(
                 id,
                 Package::new(
                     pkg_name,
                     os,
-                    Software::new("Microsoft Corporation", "Visual Studio Code", &version.to_string()),
-                    Url::parse("https://code.visualstudio.com/download").unwrap(),
-                    DownloadRequest::new(fetch_url, Integrity::Hash(hash)).unwrap(),
+                    software,
+                    doc,
+                    DownloadRequest::new(fetch_url, integrity).unwrap(),
                 ),
-            ), info)
+            ), info))
         }
 
         /// The original fetch URL is generic for the `latest` version, so the
@@ -299,52 +458,51 @@ pub mod vscode {
         /// and direct download. The program should download from the actual URL
         /// to check the expected version (VsCodeInfo) hash correctly.
         fn get_actual_download_request(&self) -> Result<DownloadRequest, String> {
-            let final_url = blocking::Client::builder()
-                .redirect(Policy::limited(10))
-                .build()
-                .map_err(|error| error.to_string())?
-                .head(self.0.package().fetch.url())
-                .send()
-                .map_err(|error| error.to_string())?
-                .url()
-                .clone();
-
             let package = self.0.package();
-            let original_fetch = package.fetch;
-            let original_version = package.software.version;
-            let expected_name = format!("/code_{original_version}");
-
-            if final_url.to_string().contains(&expected_name) {
-                let actual_req = DownloadRequest::new(
-                    &final_url.to_string(),
-                    original_fetch.integrity(),
-                ).map_err(|error| error.to_string())?;
-
-                Ok(actual_req)
-            } else if self.1.use_latest_if_version_is_old {
-                let actual_req = DownloadRequest::new(
-                    &final_url.to_string(),
-                    Integrity::None,
-                ).map_err(|error| error.to_string())?;
-
-                println!("Unable to fetch version {}.", original_version);
-                println!("Fetching the latest version without hash integrity check since use_latest_if_version_is_old is true.");
-
-                Ok(actual_req)
+            let version = package.software.version;
+            let policy = if self.1.use_latest_if_version_is_old {
+                VersionPolicy::AllowLatest
             } else {
-                let msg = format!("Unable to fetch required version {original_version}.");
+                VersionPolicy::RequireExpected
+            };
 
-                eprintln!("{}", msg);
-                println!("Redirect URL: {final_url}.");
-                println!("Hint: Make sure to update the vscode.json to the latest version or set use_latest_if_version_is_old to true.");
+            // For example, code_1.92.1-1723066302_amd64.deb or
+            // code-1.92.1-1723066302.el8.x86_64.rpm
+            let version_path = match package.os.app_pkg_type() {
+                Rpm => format!("/code-{version}"),
+                _ => format!("/code_{version}"),
+            };
 
-                Err(msg)
-            }
+            package.fetch
+                .resolve_final_url(&version_path, policy)
+                .map_err(|error| {
+                    if let UnexpectedVersion { .. } = error {
+                        info!("Hint: Make sure to update the vscode.json to the latest version or set use_latest_if_version_is_old to true.");
+                    }
+
+                    error.to_string()
+                })
         }
     }
 
     impl Install for VsCodeImage {
         fn install(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            if package.os.is_wsl() {
+                return Err("On WSL, install Visual Studio Code on Windows instead, which opens WSL folders with `code .` through its WSL extension".to_string());
+            }
+
+            match package.os.app_pkg_type() {
+                Pacman => return Err("Microsoft publishes no Arch package of Visual Studio Code, so install it from the AUR".to_string()),
+                Winget => {
+                    info!("Installing Visual Studio Code with winget...");
+
+                    return package.to_os_pkg(Winget).install_managed();
+                }
+                _ => {}
+            }
+
             let tmp = TmpWorkingDir::new()
                 .map_err(|error| error.to_string())?;
 
@@ -354,17 +512,17 @@ pub mod vscode {
             let downloader = Downloader::from(req, &tmp);
             let installer_file = downloader.path.clone();
 
-            println!("Downloading Visual Studio Code installer...");
+            info!("Downloading Visual Studio Code installer...");
 
             downloader
                 .download_blocking()
                 .map_err(|error| error.to_string())?;
 
-            println!("Installing Visual Studio Code...");
+            info!("Installing Visual Studio Code...");
 
-            self.0.package().to_os_pkg(Deb).install(&installer_file)?;
+            package.to_os_pkg(package.os.app_pkg_type()).install(&installer_file)?;
 
-            println!("Visual Studio Code installed.");
+            info!("Visual Studio Code installed.");
 
             Ok(())
         }
@@ -372,33 +530,157 @@ pub mod vscode {
 
     impl Uninstall for VsCodeImage {
         fn uninstall(&self) -> Result<(), String> {
-            println!("Uninstalling Visual Studio Code...");
+            info!("Uninstalling Visual Studio Code...");
+
+            let package = self.0.package();
+
+            package.to_os_pkg(package.os.app_pkg_type()).uninstall()?;
+
+            info!("Visual Studio Code uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for VsCodeImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            let package = self.0.package();
+
+            match package.os {
+                Windows(_) => Detection::os_package(&package.os, &package.name),
+                _ => Detection::binary("code", &["--version"]),
+            }
+        }
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".config/Code"), PathBuf::from(".vscode")]
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            if self.0.package().is_managed() {
+                return Ok(Vec::new());
+            }
+
+            self.get_actual_download_request().map(|req| vec![req])
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct VsCodeConfig {
+        extensions: Vec<String>,
+        settings: Map<String, Value>,
+    }
+
+    type VsCodeImageConfig = ImageConfig<VsCodeImage, VsCodeConfig>;
+
+    impl ToImageConfig<VsCodeConfig> for VsCodeImage {
+        fn to_image_config(&self, config: VsCodeConfig) -> VsCodeImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for VsCodeImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let VsCodeConfig { extensions, settings } = &self.1;
+            let os = self.0.0.package().os;
+
+            for extension in extensions {
+                info!("Installing extension {extension}...");
+
+                install_extension(&os, extension)?;
+            }
+
+            if settings.is_empty() {
+                return Ok(());
+            }
+
+            let settings_path = settings_path()?;
+
+            info!("Merging settings into {}...", settings_path.display());
+
+            let current = match fs::read_to_string(&settings_path) {
+                Ok(raw) if !raw.trim().is_empty() => serde_json::from_str(&raw)
+                    .map_err(|error| format!("Fail to read {}, which must be plain JSON without comments to merge into.\nCause: {error}", settings_path.display()))?,
+                _ => Map::new(),
+            };
+
+            let merged = Value::Object(merge_settings(current, settings));
+            let merged = serde_json::to_string_pretty(&merged)
+                .map_err(|error| error.to_string())?;
+
+            if let Some(parent) = settings_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|error| error.to_string())?;
+            }
+
+            fs::write(&settings_path, merged + "\n")
+                .map_err(|error| format!("Fail to write {}: {error}", settings_path.display()))
+        }
+    }
 
-            self.0.package().to_os_pkg(Deb).uninstall()?;
+    fn settings_path() -> Result<PathBuf, String> {
+        dirs::config_dir()
+            .map(|dir| dir.join("Code").join("User").join("settings.json"))
+            .ok_or_else(|| "Fail to read the user config directory".to_string())
+    }
 
-            println!("Visual Studio Code uninstalled.");
+    fn check_extension_id(extension: &str) -> Result<(), String> {
+        let valid = extension
+            .split_once('.')
+            .is_some_and(|(publisher, name)| !publisher.is_empty() && !name.is_empty())
+            && extension
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'));
 
+        if valid {
             Ok(())
+        } else {
+            Err(format!("Invalid VS Code extension ID {extension}"))
         }
     }
 
-    impl ImageOps for VsCodeImage { image_ops_impl!(); }
+    /// `code` is a `code.cmd` script on Windows, so PowerShell resolves it.
+    fn install_extension(os: &Os, extension: &str) -> Result<(), String> {
+        check_extension_id(extension)?;
+
+        let output = match os {
+            Windows(_) => exec_powershell(&format!("code --install-extension {extension}")),
+            _ => exec_cmd("code", &["--install-extension", extension]),
+        }.map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    /// Settings keys are flat, like `editor.fontSize`.
+    fn merge_settings(mut current: Map<String, Value>, fragment: &Map<String, Value>) -> Map<String, Value> {
+        current.extend(fragment.clone());
+
+        current
+    }
 
     #[cfg(test)]
     mod tests {
         use std::str::FromStr;
 
-        use crate::image::desktop::vscode::{VsCodeImage, VsCodeInfo};
+        use serde_json::{json, Map, Value};
+
+        use crate::image::desktop::vscode::{check_extension_id, merge_settings, VsCodeImage, VsCodeInfo};
         use crate::image::desktop::DesktopImageId;
         use crate::image::desktop::DesktopImageId::VsCode;
+        use crate::download::Integrity;
         use crate::image::{Image, ToImageId};
-        use crate::os::UBUNTU_X64;
+        use crate::os::{FEDORA_X64, UBUNTU_X64};
         use crate::package::SemVer;
 
         fn dummy_info() -> VsCodeInfo {
             VsCodeInfo {
                 version: SemVer(1, 92, 1),
                 hash_sha256: "d0f161ec79145772445d5a14b15030592498aaafa59237a602d66f43653e5309".to_string(),
+                rpm_key_fingerprint: "BC52 8686 B50D 79E3 39D3 721C EB3E 94AD BE12 29CF".to_string(),
                 use_latest_if_version_is_old: true,
             }
         }
@@ -410,7 +692,7 @@ pub mod vscode {
             assert_eq!(Ok(VsCode), id);
 
             let info = dummy_info();
-            let VsCodeImage(image, _) = VsCodeImage::new(UBUNTU_X64, info);
+            let VsCodeImage(image, _) = VsCodeImage::new(UBUNTU_X64, info).unwrap();
 
             assert_eq!("vscode".to_string(), image.id().to_string());
         }
@@ -418,13 +700,48 @@ pub mod vscode {
         #[test]
         fn uses_correct_low_level_package_name() {
             let info = dummy_info();
-            let VsCodeImage(image, _) = VsCodeImage::new(UBUNTU_X64, info);
+            let VsCodeImage(image, _) = VsCodeImage::new(UBUNTU_X64, info).unwrap();
 
             assert_eq!(VsCode.to_image_id(), image.id());
 
             // The low-level package name is "code" not "vscode"
             assert_eq!("code", image.package().name);
         }
+
+        #[test]
+        fn fetches_signed_rpm_on_fedora() {
+            let VsCodeImage(image, _) = VsCodeImage::new(FEDORA_X64, dummy_info()).unwrap();
+            let fetch = image.package().fetch;
+
+            assert_eq!(
+                "https://code.visualstudio.com/sha/download?build=stable&os=linux-rpm-x64",
+                fetch.url().as_str(),
+            );
+            assert!(matches!(fetch.integrity(), Integrity::Gpg(_)));
+        }
+
+        #[test]
+        fn checks_extension_id() {
+            assert!(check_extension_id("rust-lang.rust-analyzer").is_ok());
+            assert!(check_extension_id("ms-python.python").is_ok());
+            assert!(check_extension_id("rust-analyzer").is_err());
+            assert!(check_extension_id("ms-python.python; rm -rf ~").is_err());
+        }
+
+        #[test]
+        fn merges_settings_by_key() {
+            let as_map = |value: Value| match value {
+                Value::Object(map) => map,
+                _ => Map::new(),
+            };
+            let current = as_map(json!({ "editor.fontSize": 12, "files.autoSave": "off" }));
+            let fragment = as_map(json!({ "editor.fontSize": 14, "editor.formatOnSave": true }));
+
+            assert_eq!(
+                as_map(json!({ "editor.fontSize": 14, "files.autoSave": "off", "editor.formatOnSave": true })),
+                merge_settings(current, &fragment),
+            );
+        }
     }
 }
 
@@ -433,7 +750,7 @@ pub mod jetbrains_toolbox {
     use serde::{Deserialize, Serialize};
     use std::path::PathBuf;
     use std::{env, fs};
-    use Os::Linux;
+    use Os::{Linux, Windows};
 
     use crate::cmd::{exec_cmd, exec_cmd_async};
     use crate::download::hashing::Hash;
@@ -442,13 +759,17 @@ pub mod jetbrains_toolbox {
     use crate::image::desktop::DesktopImage;
     use crate::image::desktop::DesktopImageId::JetBrainsToolbox;
     use crate::image::Image;
-    use crate::image::{ImageOps, Install, Uninstall};
+    use crate::image::detection::Detection;
+    use crate::image::{ImageInfoError, ImageOps, Install, Uninstall};
     use crate::image_ops_impl;
     use crate::os::OsArch::X64;
+    use crate::os::PkgType::Winget;
     use crate::os::{get_running_processes, kill_process_and_wait, Os};
     use crate::package::{Package, SemVerRev, Software};
     use crate::tmp::TmpWorkingDir;
 
+    const WINGET_ID: &str = "JetBrains.Toolbox";
+
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct JetbrainsToolboxInfo {
         version: SemVerRev,
@@ -474,6 +795,12 @@ pub mod jetbrains_toolbox {
     }
 
     pub fn restart_jetbrains_toolbox(os: Os) -> Result<(), String> {
+        if os.is_wsl() {
+            info!("Skipping the JetBrains Toolbox restart since WSL has no desktop session to autostart it, so open the Toolbox to complete the installation.");
+
+            return Ok(());
+        }
+
         let bin_name = "jetbrains-toolbox";
         let bin_name_prefix = "jetbrains-tool";
         let toolbox_bin = env::var("HOME")
@@ -491,13 +818,13 @@ pub mod jetbrains_toolbox {
             .any(|process| process.starts_with("jetbrains-tool"));
 
         if is_running {
-            println!("Killing process {}...", bin_name);
+            info!("Killing process {}...", bin_name);
 
             let kill_result = kill_process_and_wait(os, bin_name, bin_name_prefix);
 
             if let Err(error) = kill_result {
                 eprintln!("Fail to kill process {bin_name}.");
-                println!("Opening process {} anyways to attempt restart (async)...", bin_name);
+                info!("Opening process {} anyways to attempt restart (async)...", bin_name);
 
                 exec_cmd_async(toolbox_bin.to_str().unwrap(), &[])
                     .map(|_| ())
@@ -507,7 +834,7 @@ pub mod jetbrains_toolbox {
             }
         }
 
-        println!("Opening process {} (async)...", bin_name);
+        info!("Opening process {} (async)...", bin_name);
 
         exec_cmd_async(toolbox_bin.to_str().unwrap(), &[])
             .map(|_| ())
@@ -520,38 +847,55 @@ pub mod jetbrains_toolbox {
         pub fn new(
             os: Os,
             JetbrainsToolboxInfo { version, hash_sha256 }: JetbrainsToolboxInfo,
-        ) -> Self {
+        ) -> Result<Self, ImageInfoError> {
             let id = JetBrainsToolbox;
             let pkg_name = id.to_string();
+            let software = Software::new("JetBrains s.r.o.", "JetBrains Toolbox", &version.to_string());
+            let doc = Url::parse("https://www.jetbrains.com/toolbox-app").unwrap();
+
+            // winget downloads the native installer and checks its hash
+            if let Windows(_) = os {
+                return Ok(JetBrainsToolboxImage(DesktopImage(id, Package::new_managed(WINGET_ID, os, software, doc))));
+            }
+
             let fetch_url = match os {
-                Linux(X64, _) => format!("https://download.jetbrains.com/toolbox/jetbrains-toolbox-{version}.tar.gz")
+                Linux(X64, _) => format!("https://download.jetbrains.com/toolbox/jetbrains-toolbox-{version}.tar.gz"),
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
             };
             let hash = Hash::new(Sha256, hash_sha256);
 
-            JetBrainsToolboxImage(DesktopImage(
+            Ok(JetBrainsToolboxImage(DesktopImage(
                 id,
                 Package::new(
                     &pkg_name,
                     os,
-                    Software::new("JetBrains s.r.o.", "JetBrains Toolbox", &version.to_string()),
-                    Url::parse("https://www.jetbrains.com/toolbox-app").unwrap(),
+                    software,
+                    doc,
                     DownloadRequest::new(&fetch_url, Integrity::Hash(hash)).unwrap(),
                 ),
-            ))
+            )))
         }
     }
 
     impl Install for JetBrainsToolboxImage {
         fn install(&self) -> Result<(), String> {
-            println!("Installing dependencies (FUSE)...");
+            let package = self.0.package();
+
+            if package.os.app_pkg_type() == Winget {
+                info!("Installing JetBrains Toolbox with winget...");
+
+                return package.to_os_pkg(Winget).install_managed();
+            }
+
+            info!("Installing dependencies (FUSE)...");
 
             let output = exec_cmd(
                 "sudo",
                 &["apt-get", "install", "libfuse2"],
             ).map_err(|error| error.to_string())?;
 
-            println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
-            println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+            verbose!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+            verbose!("stderr: {}", String::from_utf8_lossy(&output.stderr));
 
             let tmp = TmpWorkingDir::new()
                 .map_err(|error| error.to_string())?;
@@ -560,13 +904,13 @@ pub mod jetbrains_toolbox {
             let downloader = Downloader::from(self.0.package().fetch, &tmp);
             let tar_file = downloader.path.clone();
 
-            println!("Downloading JetBrains Toolbox installer...");
+            info!("Downloading JetBrains Toolbox installer...");
 
             downloader
                 .download_blocking()
                 .map_err(|error| error.to_string())?;
 
-            println!("Extracting JetBrains Toolbox installer...");
+            info!("Extracting JetBrains Toolbox installer...");
 
             let output = exec_cmd(
                 "tar",
@@ -584,19 +928,19 @@ pub mod jetbrains_toolbox {
                 .last() // The tar only contains one single file (the installer binary)
                 .ok_or("Fail to read installer path from output of command tar")?;
 
-            println!("stdout: {}", stdout);
-            println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+            verbose!("stdout: {}", stdout);
+            verbose!("stderr: {}", String::from_utf8_lossy(&output.stderr));
 
-            println!("Installing JetBrains Toolbox...");
+            info!("Installing JetBrains Toolbox...");
 
             let installer_file = tmp_path.join(installer_rel_path);
             let install_cmd = format!("{}", installer_file.to_str().unwrap());
             let output = exec_cmd(&install_cmd, &[])
                 .map_err(|error| error.to_string())?;
 
-            println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
-            println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
-            println!("JetBrains Toolbox installed.");
+            verbose!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+            verbose!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+            info!("JetBrains Toolbox installed.");
 
             Ok(())
         }
@@ -604,20 +948,32 @@ pub mod jetbrains_toolbox {
 
     impl Uninstall for JetBrainsToolboxImage {
         fn uninstall(&self) -> Result<(), String> {
-            println!("Uninstalling JetBrains Toolbox softly, IDEs will keep installed...");
+            let package = self.0.package();
+
+            if package.os.app_pkg_type() == Winget {
+                return package.to_os_pkg(Winget).uninstall();
+            }
+
+            info!("Uninstalling JetBrains Toolbox softly, IDEs will keep installed...");
 
             let home = env::var("HOME")
                 .map(|home| PathBuf::from(&home))
                 .map_err(|error| error.to_string())?;
 
+            // The Toolbox may not add its desktop files on WSL, which has no
+            // Linux desktop session
+            let is_gui_file_removable = |file: &PathBuf| !package.os.is_wsl() || file.exists();
+
             // Delete autostart file
             let toolbox_autostart_file = home
                 .join(".config")
                 .join("autostart")
                 .join("jetbrains-toolbox.desktop");
 
-            fs::remove_file(toolbox_autostart_file)
-                .map_err(|error| error.to_string())?;
+            if is_gui_file_removable(&toolbox_autostart_file) {
+                fs::remove_file(toolbox_autostart_file)
+                    .map_err(|error| error.to_string())?;
+            }
 
             // Delete Toolbox files but ./apps
             let toolbox_dir = home.join(jetbrains_toolbox_rel_dir());
@@ -644,16 +1000,43 @@ pub mod jetbrains_toolbox {
                 .join("applications")
                 .join("jetbrains-toolbox.desktop");
 
-            fs::remove_file(apps_toolbox_file)
-                .map_err(|error| error.to_string())?;
+            if is_gui_file_removable(&apps_toolbox_file) {
+                fs::remove_file(apps_toolbox_file)
+                    .map_err(|error| error.to_string())?;
+            }
 
-            println!("JetBrains Toolbox uninstalled.");
+            info!("JetBrains Toolbox uninstalled.");
 
             Ok(())
         }
     }
 
-    impl ImageOps for JetBrainsToolboxImage { image_ops_impl!(); }
+    impl ImageOps for JetBrainsToolboxImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            let package = self.0.package();
+
+            match package.os {
+                Windows(_) => Detection::os_package(&package.os, &package.name),
+                _ => Detection::home_dir(".local/share/JetBrains/Toolbox/bin"),
+            }
+        }
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".cache/JetBrains/Toolbox")]
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            let package = self.0.package();
+
+            if package.is_managed() {
+                return Ok(Vec::new());
+            }
+
+            Ok(vec![package.fetch])
+        }
+    }
 }
 
 pub mod jetbrains_ide {
@@ -665,18 +1048,20 @@ pub mod jetbrains_ide {
     use crate::image::desktop::jetbrains_toolbox::{is_jetbrains_toolbox_installed, jetbrains_toolbox_rel_dir, restart_jetbrains_toolbox};
     use crate::image::desktop::{DesktopImage, DesktopImageId};
     use crate::image::Image;
-    use crate::image::{ImageOps, Install, Uninstall};
+    use crate::image::detection::Detection;
+    use crate::image::purge;
+    use crate::image::{Config, ImageConfig, ImageInfoError, ImageOps, Install, ToImageConfig, Uninstall};
     use crate::os::Os;
     use crate::os::Os::Linux;
     use crate::os::OsArch::X64;
-    use crate::package::{Package, Software, YearSemVer};
+    use crate::package::{Package, SemVer, Software, YearSemVer};
     use crate::tmp::TmpWorkingDir;
     use crate::{cmd, image_ops_impl};
     use reqwest::Url;
     use serde::{Deserialize, Serialize};
     use std::path::{Path, PathBuf};
     use std::{env, fs};
-    use JetBrainsIdeImageId::{CLion, DataGrip, Goland, IntelliJIdea, PhpStorm, Rider, RubyMine, RustRover};
+    use JetBrainsIdeImageId::{CLion, DataGrip, DataSpell, Fleet, Goland, IntelliJIdea, PhpStorm, Rider, RubyMine, RustRover, Writerside};
 
     #[derive(Clone)]
     pub enum JetBrainsIdeImageId {
@@ -690,6 +1075,9 @@ pub mod jetbrains_ide {
         Rider,
         PhpStorm,
         RubyMine,
+        Fleet,
+        DataSpell,
+        Writerside,
     }
 
     impl JetBrainsIdeImageId {
@@ -705,6 +1093,9 @@ pub mod jetbrains_ide {
                 Rider => DesktopImageId::Rider,
                 PhpStorm => DesktopImageId::PhpStorm,
                 RubyMine => DesktopImageId::RubyMine,
+                Fleet => DesktopImageId::Fleet,
+                DataSpell => DesktopImageId::DataSpell,
+                Writerside => DesktopImageId::Writerside,
             }
         }
 
@@ -727,31 +1118,38 @@ pub mod jetbrains_ide {
                 Rider => "Rider",
                 PhpStorm => "PhpStorm",
                 RubyMine => "RubyMine",
+                Fleet => "Fleet",
+                DataSpell => "DataSpell",
+                Writerside => "Writerside",
             }
         }
     }
 
+    /// Fleet versions aren't year-based, like `1.38.89`.
     #[derive(Clone, Debug, Serialize, Deserialize)]
-    pub struct JetBrainsIdeInfo {
-        version: YearSemVer,
-        hash_sha256: String,
+    pub struct JetBrainsIdeInfo<V = YearSemVer> {
+        version: V,
+
+        #[serde(default)]
+        hash_sha256: Option<String>,
     }
 
+    #[derive(Clone)]
     pub struct JetBrainsIdeImage(DesktopImage);
 
     impl JetBrainsIdeImage {
         fn new_fetch_url(
             os: Os,
             id: JetBrainsIdeImageId,
-            version: YearSemVer,
-        ) -> String {
-            let simplified_version = version.to_simplified_string();
+            simplified_version: &str,
+        ) -> Result<String, ImageInfoError> {
             let base_url = "https://download.jetbrains.com";
             let file_ext = match os {
-                Linux(X64, _) => format!("{simplified_version}.tar.gz")
+                Linux(X64, _) => format!("{simplified_version}.tar.gz"),
+                _ => return Err(ImageInfoError::unsupported_os(&id.to_desktop_image_id(), &os)),
             };
 
-            match id {
+            let fetch_url = match id {
                 IntelliJIdea => format!("{base_url}/idea/ideaIU-{file_ext}"),
                 WebStorm => format!("{base_url}/webstorm/WebStorm-{file_ext}"),
                 RustRover => format!("{base_url}/rustrover/RustRover-{file_ext}"),
@@ -762,68 +1160,102 @@ pub mod jetbrains_ide {
                 Rider => format!("{base_url}/rider/JetBrains.Rider-{file_ext}"),
                 PhpStorm => format!("{base_url}/webide/PhpStorm-{file_ext}"),
                 RubyMine => format!("{base_url}/ruby/RubyMine-{file_ext}"),
-            }
+                DataSpell => format!("{base_url}/python/dataspell-{file_ext}"),
+                Writerside => format!("{base_url}/writerside/writerside-{file_ext}"),
+
+                // Fleet installers live in a directory per platform
+                Fleet => format!("{base_url}/fleet/installers/linux_x64/Fleet-{file_ext}"),
+            };
+
+            Ok(fetch_url)
         }
 
-        pub fn new(id: JetBrainsIdeImageId) -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
-            move |os: Os, JetBrainsIdeInfo { version, hash_sha256 }: JetBrainsIdeInfo| {
-                let did = id.to_desktop_image_id();
-                let pkg_name = id.pkg_name();
-                let fetch_url = Self::new_fetch_url(os.clone(), id.clone(), version.clone());
-                let hash = Hash::new(Sha256, hash_sha256);
+        fn from_version(
+            os: Os,
+            id: JetBrainsIdeImageId,
+            version: &str,
+            simplified_version: &str,
+            hash_sha256: Option<String>,
+        ) -> Result<JetBrainsIdeImage, ImageInfoError> {
+            let did = id.to_desktop_image_id();
+            let pkg_name = id.pkg_name();
+            let fetch_url = Self::new_fetch_url(os.clone(), id.clone(), simplified_version)?;
+            let integrity = match hash_sha256 {
+                Some(hash_sha256) => Integrity::Hash(Hash::new(Sha256, hash_sha256)),
+                None => Integrity::RemoteSha256 { url: Url::parse(&format!("{fetch_url}.sha256")).unwrap() },
+            };
 
-                JetBrainsIdeImage(DesktopImage(
-                    did.clone(),
-                    Package::new(
-                        &pkg_name,
-                        os,
-                        Software::new("JetBrains s.r.o.", id.name(), &version.to_string()),
-                        Url::parse(&format!("https://www.jetbrains.com/{did}/download")).unwrap(),
-                        DownloadRequest::new(&fetch_url, Integrity::Hash(hash)).unwrap(),
-                    ),
-                ))
+            Ok(JetBrainsIdeImage(DesktopImage(
+                did.clone(),
+                Package::new(
+                    &pkg_name,
+                    os,
+                    Software::new("JetBrains s.r.o.", id.name(), version),
+                    Url::parse(&format!("https://www.jetbrains.com/{did}/download")).unwrap(),
+                    DownloadRequest::new(&fetch_url, integrity).unwrap(),
+                ),
+            )))
+        }
+
+        pub fn new(id: JetBrainsIdeImageId) -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
+            move |os: Os, JetBrainsIdeInfo { version, hash_sha256 }: JetBrainsIdeInfo| {
+                Self::from_version(os, id.clone(), &version.to_string(), &version.to_simplified_string(), hash_sha256)
             }
         }
 
-        pub fn intellij_idea() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn intellij_idea() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(IntelliJIdea)
         }
 
-        pub fn webstorm() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn webstorm() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(WebStorm)
         }
 
-        pub fn rustrover() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn rustrover() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(RustRover)
         }
 
-        pub fn clion() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn clion() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(CLion)
         }
 
-        pub fn pycharm() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn pycharm() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(PyCharm)
         }
 
-        pub fn datagrip() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn datagrip() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(DataGrip)
         }
 
-        pub fn goland() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn goland() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(Goland)
         }
 
-        pub fn rider() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn rider() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(Rider)
         }
 
-        pub fn phpstorm() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn phpstorm() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(PhpStorm)
         }
 
-        pub fn rubymine() -> impl Fn(Os, JetBrainsIdeInfo) -> JetBrainsIdeImage {
+        pub fn rubymine() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
             Self::new(RubyMine)
         }
+
+        pub fn fleet() -> impl Fn(Os, JetBrainsIdeInfo<SemVer>) -> Result<JetBrainsIdeImage, ImageInfoError> {
+            |os: Os, JetBrainsIdeInfo { version, hash_sha256 }: JetBrainsIdeInfo<SemVer>| {
+                Self::from_version(os, Fleet, &version.to_string(), &version.to_string(), hash_sha256)
+            }
+        }
+
+        pub fn dataspell() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
+            Self::new(DataSpell)
+        }
+
+        pub fn writerside() -> impl Fn(Os, JetBrainsIdeInfo) -> Result<JetBrainsIdeImage, ImageInfoError> {
+            Self::new(Writerside)
+        }
     }
 
     impl Install for JetBrainsIdeImage {
@@ -835,7 +1267,11 @@ pub mod jetbrains_ide {
                 return Err("JetBrains Toolbox is required to install JetBrains IDEs but is not installed in your system. Install JetBrains Toolbox first.".to_string());
             }
 
-            println!("Installing {ide_name}");
+            if self.0.package().os.is_wsl() {
+                info!("⚠️ {ide_name} will only run on WSL through WSLg. The Windows IDE can open WSL projects through its WSL support instead.");
+            }
+
+            info!("Installing {ide_name}");
 
             let tmp = TmpWorkingDir::new()
                 .map_err(|error| error.to_string())?;
@@ -844,13 +1280,13 @@ pub mod jetbrains_ide {
             let downloader = Downloader::from(self.0.package().fetch, &tmp);
             let tar_file = downloader.path.clone();
 
-            println!("Downloading {ide_name}...");
+            info!("Downloading {ide_name}...");
 
             downloader
                 .download_blocking()
                 .map_err(|error| error.to_string())?;
 
-            println!("Extracting {ide_name}...");
+            info!("Extracting {ide_name}...");
 
             let home = env::var("HOME")
                 .map(|home| PathBuf::from(&home))
@@ -877,7 +1313,7 @@ pub mod jetbrains_ide {
 
             let extracted_dir_name = get_tar_root_dir_name(tar_file)?;
 
-            println!("Moving {ide_name} files...");
+            info!("Moving {ide_name} files...");
 
             let ide_id = self.0.package().name;
             let extracted_dir_rel_path = Path::new(&extracted_dir_name);
@@ -887,7 +1323,7 @@ pub mod jetbrains_ide {
             fs::rename(ide_tmp_dir.clone(), ide_dir.clone())
                 .map_err(|error| format!("Fail to move {:?} to {:?}: {}", ide_tmp_dir, ide_dir, error))?;
 
-            println!("Restarting JetBrains Toolbox to complete the installation...");
+            info!("Restarting JetBrains Toolbox to complete the installation...");
 
             let restart_result = restart_jetbrains_toolbox(self.0.package().os);
 
@@ -895,7 +1331,7 @@ pub mod jetbrains_ide {
                 eprintln!("Unable to restart JetBrains Toolbox. The installation may be incomplete, so you should restart the Toolbox app manually to complete the installation.\nCause: {error}")
             }
 
-            println!("{ide_name} installed.");
+            info!("{ide_name} installed.");
 
             Ok(())
         }
@@ -905,7 +1341,7 @@ pub mod jetbrains_ide {
         fn uninstall(&self) -> Result<(), String> {
             let ide_name = self.0.package().software.name;
 
-            println!("Uninstalling {ide_name}");
+            info!("Uninstalling {ide_name}");
 
             let home = env::var("HOME")
                 .map(|home| PathBuf::from(&home))
@@ -917,7 +1353,7 @@ pub mod jetbrains_ide {
                 .join("JetBrains")
                 .join("Toolbox");
 
-            println!("Removing {ide_name} files...");
+            info!("Removing {ide_name} files...");
 
             let ide_id = self.0.package().name;
             let ide_dir = toolbox_dir
@@ -927,7 +1363,7 @@ pub mod jetbrains_ide {
             fs::remove_dir_all(ide_dir)
                 .map_err(|error| error.to_string())?;
 
-            println!("Restarting JetBrains Toolbox to complete the uninstallation...");
+            info!("Restarting JetBrains Toolbox to complete the uninstallation...");
 
             let restart_result = restart_jetbrains_toolbox(self.0.package().os);
 
@@ -935,13 +1371,121 @@ pub mod jetbrains_ide {
                 eprintln!("Unable to restart JetBrains Toolbox. The installation may be incomplete, so you should restart the Toolbox app manually to complete the installation.\nCause: {error}")
             }
 
-            println!("{ide_name} uninstalled.");
+            info!("{ide_name} uninstalled.");
 
             Ok(())
         }
     }
 
-    impl ImageOps for JetBrainsIdeImage { image_ops_impl!(); }
+    impl ImageOps for JetBrainsIdeImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::home_dir(&format!(".local/share/JetBrains/Toolbox/apps/{}", self.0.package().name))
+        }
+
+        /// Named after the version, like `.config/JetBrains/GoLand2024.1`.
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            let prefix = match self.0.0 {
+                DesktopImageId::IntelliJIdea => "IntelliJIdea".to_string(),
+                DesktopImageId::Fleet => return vec![PathBuf::from(".fleet")],
+                _ => self.0.package().software.name,
+            };
+
+            [".config/JetBrains", ".cache/JetBrains", ".local/share/JetBrains"]
+                .iter()
+                .flat_map(|parent| purge::versioned_dirs(parent, &prefix))
+                .collect()
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct JetBrainsIdeConfig {
+        plugins: Vec<String>,
+    }
+
+    type JetBrainsIdeImageConfig = ImageConfig<JetBrainsIdeImage, JetBrainsIdeConfig>;
+
+    impl ToImageConfig<JetBrainsIdeConfig> for JetBrainsIdeImage {
+        fn to_image_config(&self, config: JetBrainsIdeConfig) -> JetBrainsIdeImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for JetBrainsIdeImageConfig {
+        /// The launcher runs headless, so the IDE should be closed.
+        fn config(&self) -> Result<(), String> {
+            let JetBrainsIdeConfig { plugins } = &self.1;
+            let package = self.0.0.package();
+            let ide_name = package.software.name;
+
+            if let DesktopImageId::Fleet = self.0.0.0 {
+                return Err("Fleet has no plugin installer in its launcher, so it takes no plugins config".to_string());
+            }
+
+            if plugins.is_empty() {
+                info!("No {ide_name} plugins to install.");
+
+                return Ok(());
+            }
+
+            plugins
+                .iter()
+                .try_for_each(|plugin| check_plugin_id(plugin))?;
+
+            let home = env::var("HOME")
+                .map(|home| PathBuf::from(&home))
+                .map_err(|error| error.to_string())?;
+
+            let launcher = home
+                .join(jetbrains_toolbox_rel_dir())
+                .join("apps")
+                .join(&package.name)
+                .join(launcher_rel_path(&package.name));
+
+            if !launcher.is_file() {
+                return Err(format!("{ide_name} launcher {} not found. Install {ide_name} first.", launcher.display()));
+            }
+
+            info!("Installing {ide_name} plugins {}...", plugins.join(", "));
+
+            let args = ["installPlugins"]
+                .into_iter()
+                .chain(plugins.iter().map(String::as_str))
+                .collect::<Vec<&str>>();
+
+            let output = exec_cmd(&launcher.to_string_lossy(), &args)
+                .map_err(|error| error.to_string())?;
+
+            cmd::print_output(output);
+
+            info!("{ide_name} plugins installed.");
+
+            Ok(())
+        }
+    }
+
+    fn launcher_rel_path(pkg_name: &str) -> PathBuf {
+        Path::new("bin").join(format!("{pkg_name}.sh"))
+    }
+
+    fn check_plugin_id(plugin: &str) -> Result<(), String> {
+        let valid = !plugin.is_empty()
+            && !plugin.starts_with('-')
+            && plugin
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'));
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Invalid JetBrains plugin ID {plugin}"))
+        }
+    }
 
     fn get_tar_root_dir_name(tar_file: &str) -> Result<String, String> {
         let tar_cmd = format!("tar -tf {tar_file} | grep -o '^[^/]*' | sort -u | head -n 1");
@@ -952,4 +1496,475 @@ pub mod jetbrains_ide {
 
         Ok(stdout.trim().to_string())
     }
+
+    #[cfg(test)]
+    mod tests {
+        use std::path::PathBuf;
+
+        use crate::image::desktop::jetbrains_ide::{check_plugin_id, launcher_rel_path, JetBrainsIdeImage};
+        use crate::image::desktop::DesktopImage;
+        use crate::os::UBUNTU_X64;
+
+        #[test]
+        fn keeps_fleet_semver() {
+            let info = serde_json::from_str(r#"{ "version": "1.38.89" }"#).unwrap();
+            let JetBrainsIdeImage(DesktopImage(_, package)) = JetBrainsIdeImage::fleet()(UBUNTU_X64, info).unwrap();
+
+            assert_eq!("1.38.89", package.software.version);
+            assert_eq!(
+                "https://download.jetbrains.com/fleet/installers/linux_x64/Fleet-1.38.89.tar.gz",
+                package.fetch.url().as_str(),
+            );
+        }
+
+        #[test]
+        fn runs_ide_launcher() {
+            assert_eq!(PathBuf::from("bin/idea.sh"), launcher_rel_path("idea"));
+            assert_eq!(PathBuf::from("bin/rustrover.sh"), launcher_rel_path("rustrover"));
+        }
+
+        #[test]
+        fn checks_plugin_id() {
+            assert!(check_plugin_id("org.rust.lang").is_ok());
+            assert!(check_plugin_id("com.github.copilot").is_ok());
+            assert!(check_plugin_id("IdeaVIM").is_ok());
+            assert!(check_plugin_id("").is_err());
+            assert!(check_plugin_id("--help").is_err());
+            assert!(check_plugin_id("a b").is_err());
+        }
+    }
 }
+
+pub mod dev_fonts {
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::hashing::Hash;
+    use crate::download::hashing::HashAlgorithm::Sha256;
+    use crate::download::{download_all, DownloadRequest, Downloader, Integrity};
+    use crate::image::desktop::DesktopImage;
+    use crate::image::desktop::DesktopImageId::DevFonts;
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    const FONT_DOWNLOADS: usize = 4;
+
+    /// Font releases don't follow SemVer, like JetBrains Mono `2.304`.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct FontInfo {
+        version: String,
+
+        #[serde(default)]
+        hash_sha256: Option<String>,
+    }
+
+    /// Checked against the `SHA-256.txt` of their release.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct NerdFontsInfo {
+        version: SemVer,
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct DevFontsInfo {
+        jetbrains_mono: FontInfo,
+        fira_code: FontInfo,
+        nerd_fonts: Option<NerdFontsInfo>,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct FontRelease {
+        name: String,
+        fetch: DownloadRequest,
+        ttf_dir: String,
+    }
+
+    impl FontRelease {
+        fn new(name: &str, fetch_url: &str, integrity: Integrity, ttf_dir: &str) -> Self {
+            FontRelease {
+                name: name.to_string(),
+                fetch: DownloadRequest::new(fetch_url, integrity).unwrap(),
+                ttf_dir: ttf_dir.to_string(),
+            }
+        }
+    }
+
+    fn font_integrity(FontInfo { hash_sha256, .. }: &FontInfo) -> Integrity {
+        match hash_sha256 {
+            Some(hash_sha256) => Integrity::Hash(Hash::new(Sha256, hash_sha256.clone())),
+            None => Integrity::None,
+        }
+    }
+
+    fn font_releases(
+        DevFontsInfo { jetbrains_mono, fira_code, nerd_fonts }: DevFontsInfo,
+    ) -> Vec<FontRelease> {
+        let mut releases = vec![
+            FontRelease::new(
+                "JetBrainsMono",
+                &format!("https://github.com/JetBrains/JetBrainsMono/releases/download/v{0}/JetBrainsMono-{0}.zip", jetbrains_mono.version),
+                font_integrity(&jetbrains_mono),
+                "fonts/ttf",
+            ),
+            FontRelease::new(
+                "FiraCode",
+                &format!("https://github.com/tonsky/FiraCode/releases/download/{0}/Fira_Code_v{0}.zip", fira_code.version),
+                font_integrity(&fira_code),
+                "ttf",
+            ),
+        ];
+
+        if let Some(nerd_fonts) = nerd_fonts {
+            let base_url = format!("https://github.com/ryanoasis/nerd-fonts/releases/download/v{}", nerd_fonts.version);
+            let checksums = || Integrity::RemoteSha256 { url: Url::parse(&format!("{base_url}/SHA-256.txt")).unwrap() };
+
+            releases.push(FontRelease::new(
+                "JetBrainsMonoNerdFont",
+                &format!("{base_url}/JetBrainsMono.tar.xz"),
+                checksums(),
+                "",
+            ));
+            releases.push(FontRelease::new(
+                "FiraCodeNerdFont",
+                &format!("{base_url}/FiraCode.tar.xz"),
+                checksums(),
+                "",
+            ));
+        }
+
+        releases
+    }
+
+    pub struct DevFontsImage(DesktopImage, Vec<FontRelease>);
+
+    impl DevFontsImage {
+        pub fn new(os: Os, info: DevFontsInfo) -> Self {
+            let id = DevFonts;
+            let pkg_name = id.to_string();
+            let version = format!(
+                "JetBrains Mono {}, Fira Code {}",
+                info.jetbrains_mono.version,
+                info.fira_code.version,
+            );
+
+            DevFontsImage(
+                DesktopImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("JetBrains s.r.o., Nikita Prokopov", "Developer Fonts", &version),
+                        Url::parse("https://www.jetbrains.com/lp/mono").unwrap(),
+                    ),
+                ),
+                font_releases(info),
+            )
+        }
+
+        fn font_request(&self, font: &FontRelease) -> DownloadRequest {
+            self.0.package().download_request(font.fetch.clone())
+        }
+    }
+
+    fn fonts_dir() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(|home| PathBuf::from(home)
+                .join(".local")
+                .join("share")
+                .join("fonts")
+                .join("dev-fonts")
+            )
+            .map_err(|error| error.to_string())
+    }
+
+    fn refresh_font_cache() -> Result<(), String> {
+        info!("Refreshing font cache...");
+
+        let output = exec_cmd("fc-cache", &["-f"])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    fn install_font(font: &FontRelease, archive_file: &Path, fonts_dir: &Path) -> Result<(), String> {
+        let FontRelease { name, ttf_dir, .. } = font;
+        let tmp = TmpWorkingDir::new()
+            .map_err(|error| error.to_string())?;
+
+        let extract_dir = tmp.path().join(name);
+        let archive_path = archive_file.to_str().unwrap();
+
+        info!("Extracting font {name}...");
+
+        fs::create_dir_all(&extract_dir)
+            .map_err(|error| error.to_string())?;
+
+        let extract_path = extract_dir.to_str().unwrap();
+        let output = if archive_path.ends_with(".zip") {
+            exec_cmd("unzip", &["-q", archive_path, "-d", extract_path])
+        } else {
+            exec_cmd("tar", &["-xJf", archive_path, "--directory", extract_path])
+        }.map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        let font_dir = fonts_dir.join(name);
+
+        fs::create_dir_all(&font_dir)
+            .map_err(|error| error.to_string())?;
+
+        let ttf_files = fs::read_dir(extract_dir.join(ttf_dir))
+            .map_err(|error| error.to_string())?
+            .filter_map(|res| res.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ttf"));
+
+        for ttf_file in ttf_files {
+            fs::copy(&ttf_file, font_dir.join(ttf_file.file_name().unwrap()))
+                .map_err(|error| error.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    impl Install for DevFontsImage {
+        fn install(&self) -> Result<(), String> {
+            let fonts_dir = fonts_dir()?;
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let downloaders = self.1
+                .iter()
+                .map(|font| Downloader::from(self.font_request(font), &tmp))
+                .collect::<Vec<Downloader>>();
+
+            let archive_files = downloaders
+                .iter()
+                .map(|downloader| downloader.path.clone())
+                .collect::<Vec<PathBuf>>();
+
+            info!("Downloading {} fonts...", downloaders.len());
+
+            download_all(downloaders, FONT_DOWNLOADS)
+                .into_iter()
+                .collect::<Result<Vec<()>, _>>()
+                .map_err(|error| error.to_string())?;
+
+            for (font, archive_file) in self.1.iter().zip(archive_files) {
+                install_font(font, &archive_file, &fonts_dir)?;
+            }
+
+            refresh_font_cache()?;
+
+            info!("Developer fonts installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for DevFontsImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing developer fonts...");
+
+            fs::remove_dir_all(fonts_dir()?)
+                .map_err(|error| error.to_string())?;
+
+            refresh_font_cache()?;
+
+            info!("Developer fonts uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for DevFontsImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::home_dir(".local/share/fonts/dev-fonts")
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(self.1.iter().map(|font| self.font_request(font)).collect())
+        }
+
+        fn unverified_downloads(&self) -> Vec<Url> {
+            self.1
+                .iter()
+                .filter(|font| font.fetch.integrity() == Integrity::None)
+                .map(|font| font.fetch.url())
+                .collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use reqwest::Url;
+
+        use crate::download::Integrity;
+        use crate::image::desktop::dev_fonts::{font_releases, DevFontsInfo, FontInfo, NerdFontsInfo};
+        use crate::package::SemVer;
+
+        fn font_info(version: &str) -> FontInfo {
+            FontInfo { version: version.to_string(), hash_sha256: None }
+        }
+
+        #[test]
+        fn includes_nerd_fonts_only_when_given() {
+            let info = DevFontsInfo {
+                jetbrains_mono: font_info("2.304"),
+                fira_code: font_info("6.2"),
+                nerd_fonts: None,
+            };
+            let releases = font_releases(info.clone());
+
+            assert_eq!(2, releases.len());
+            assert_eq!(
+                "https://github.com/JetBrains/JetBrainsMono/releases/download/v2.304/JetBrainsMono-2.304.zip",
+                releases[0].fetch.url().as_str()
+            );
+            assert_eq!(Integrity::None, releases[0].fetch.integrity());
+
+            let nerd_fonts = NerdFontsInfo { version: SemVer(3, 2, 1) };
+            let releases = font_releases(DevFontsInfo { nerd_fonts: Some(nerd_fonts), ..info });
+
+            assert_eq!(4, releases.len());
+            assert_eq!(
+                "https://github.com/ryanoasis/nerd-fonts/releases/download/v3.2.1/FiraCode.tar.xz",
+                releases[3].fetch.url().as_str()
+            );
+            assert_eq!(
+                Integrity::RemoteSha256 { url: Url::parse("https://github.com/ryanoasis/nerd-fonts/releases/download/v3.2.1/SHA-256.txt").unwrap() },
+                releases[3].fetch.integrity()
+            );
+        }
+    }
+}
+
+pub mod flatpak_app {
+    use std::path::PathBuf;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::image::desktop::DesktopImageId::{Gimp, Obsidian};
+    use crate::image::desktop::{DesktopImage, DesktopImageId};
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::os::PkgType::Flatpak;
+    use crate::os::FlatpakScope;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct FlatpakAppInfo {
+        scope: FlatpakScope,
+    }
+
+    /// Flathub keeps the app updated, so it has no pinned version.
+    pub struct FlatpakAppImage(DesktopImage, FlatpakScope);
+
+    impl FlatpakAppImage {
+        fn new(
+            id: DesktopImageId,
+            app_id: &'static str,
+            software: Software,
+            doc: &'static str,
+        ) -> impl Fn(Os, FlatpakAppInfo) -> Result<FlatpakAppImage, ImageInfoError> {
+            move |os: Os, FlatpakAppInfo { scope }: FlatpakAppInfo| {
+                if !matches!(os, Linux(_, _)) {
+                    return Err(ImageInfoError::unsupported_os(&id, &os));
+                }
+
+                let doc = Url::parse(doc).unwrap();
+
+                Ok(FlatpakAppImage(
+                    DesktopImage(id.clone(), Package::new_managed(app_id, os, software.clone(), doc)),
+                    scope,
+                ))
+            }
+        }
+
+        pub fn gimp() -> impl Fn(Os, FlatpakAppInfo) -> Result<FlatpakAppImage, ImageInfoError> {
+            Self::new(
+                Gimp,
+                "org.gimp.GIMP",
+                Software::new("The GIMP Team", "GIMP", "latest"),
+                "https://www.gimp.org/downloads",
+            )
+        }
+
+        pub fn obsidian() -> impl Fn(Os, FlatpakAppInfo) -> Result<FlatpakAppImage, ImageInfoError> {
+            Self::new(
+                Obsidian,
+                "md.obsidian.Obsidian",
+                Software::new("Dynalist Inc.", "Obsidian", "latest"),
+                "https://obsidian.md/download",
+            )
+        }
+    }
+
+    impl Install for FlatpakAppImage {
+        fn install(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            info!("Installing {} from Flathub...", package.software.name);
+
+            package.to_os_pkg(Flatpak(self.1.clone())).install_managed()
+        }
+    }
+
+    impl Uninstall for FlatpakAppImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            info!("Uninstalling {}...", package.software.name);
+
+            package.to_os_pkg(Flatpak(self.1.clone())).uninstall()
+        }
+    }
+
+    impl ImageOps for FlatpakAppImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::package(&Flatpak(self.1.clone()), &self.0.package().name)
+        }
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".var/app").join(self.0.package().name)]
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::path::PathBuf;
+
+        use crate::image::desktop::flatpak_app::{FlatpakAppImage, FlatpakAppInfo};
+        use crate::image::detection::Detection;
+        use crate::image::ImageOps;
+        use crate::os::FlatpakScope::User;
+        use crate::os::{MACOS_ARM64, UBUNTU_X64};
+
+        #[test]
+        fn installs_gimp_from_flathub() {
+            let image = FlatpakAppImage::gimp()(UBUNTU_X64, FlatpakAppInfo { scope: User }).unwrap();
+
+            assert_eq!(Detection::FlatpakPackage("org.gimp.GIMP".to_string()), image.detection());
+            assert_eq!(vec![PathBuf::from(".var/app/org.gimp.GIMP")], image.purge_paths());
+            assert!(image.artifacts().unwrap().is_empty());
+            assert!(FlatpakAppImage::gimp()(MACOS_ARM64, FlatpakAppInfo { scope: User }).is_err());
+        }
+    }
+}
+
@@ -0,0 +1,329 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use crate::cmd::{exec_cmd, exec_powershell};
+use crate::image::detection::InstallStatus::{Installed, NotInstalled};
+use crate::os::{Os, PkgType};
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Detection {
+    /// With the arguments that print its version, if it can print it cheaply.
+    Binary(String, Vec<String>),
+    DpkgPackage(String),
+    RpmPackage(String),
+    PacmanPackage(String),
+    BrewPackage(String),
+    WingetPackage(String),
+    FlatpakPackage(String),
+    HomeDir(PathBuf),
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum InstallStatus {
+    Installed(Option<String>),
+    NotInstalled,
+}
+
+impl Display for InstallStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Installed(Some(version)) => format!("installed ({version})"),
+            Installed(None) => "installed".to_string(),
+            NotInstalled => "not installed".to_string(),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl InstallStatus {
+    /// Compares the numeric core, like `21.0.2` for `21.0.2-tem`, if comparable.
+    pub fn has_version(&self, version: &str) -> Option<bool> {
+        let core = version_core(version);
+
+        match self {
+            Installed(Some(installed)) if !core.is_empty() => Some(has_version_core(installed, &core)),
+            _ => None,
+        }
+    }
+}
+
+impl Detection {
+    pub fn binary(name: &str, version_args: &[&str]) -> Self {
+        Detection::Binary(
+            name.to_string(),
+            version_args.iter().map(|arg| arg.to_string()).collect(),
+        )
+    }
+
+    pub fn dpkg_package(name: &str) -> Self {
+        Detection::DpkgPackage(name.to_string())
+    }
+
+    pub fn os_package(os: &Os, name: &str) -> Self {
+        Self::package(&os.pkg_type(), name)
+    }
+
+    pub fn package(pkg_type: &PkgType, name: &str) -> Self {
+        match pkg_type {
+            PkgType::Deb => Detection::DpkgPackage(name.to_string()),
+            PkgType::Rpm => Detection::RpmPackage(name.to_string()),
+            PkgType::Pacman => Detection::PacmanPackage(name.to_string()),
+            PkgType::Brew | PkgType::BrewCask => Detection::BrewPackage(name.to_string()),
+            PkgType::Winget => Detection::WingetPackage(name.to_string()),
+            PkgType::Flatpak(_) => Detection::FlatpakPackage(name.to_string()),
+        }
+    }
+
+    pub fn home_dir(rel_path: &str) -> Self {
+        Detection::HomeDir(PathBuf::from(rel_path))
+    }
+
+    pub fn detect(&self) -> Result<InstallStatus, String> {
+        match self {
+            Detection::Binary(name, version_args) => detect_binary(name, version_args),
+            Detection::DpkgPackage(name) => detect_dpkg_package(name),
+            Detection::RpmPackage(name) => detect_rpm_package(name),
+            Detection::PacmanPackage(name) => detect_pacman_package(name),
+            Detection::BrewPackage(name) => detect_brew_package(name),
+            Detection::WingetPackage(id) => detect_winget_package(id),
+            Detection::FlatpakPackage(id) => detect_flatpak_package(id),
+            Detection::HomeDir(rel_path) => detect_home_dir(rel_path),
+        }
+    }
+}
+
+fn detect_binary(name: &str, version_args: &[String]) -> Result<InstallStatus, String> {
+    let on_path = exec_cmd("bash", &["-c", &format!("command -v {name}")]).is_ok();
+
+    if !on_path {
+        return Ok(NotInstalled);
+    }
+
+    if version_args.is_empty() {
+        return Ok(Installed(None));
+    }
+
+    let args = version_args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<&str>>();
+
+    let version = exec_cmd(name, &args)
+        .ok()
+        .and_then(|output| first_output_line(&output));
+
+    Ok(Installed(version))
+}
+
+fn detect_dpkg_package(name: &str) -> Result<InstallStatus, String> {
+    let query = exec_cmd("dpkg-query", &["-W", "-f=${Status} ${Version}", name]);
+
+    let status = match query {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(_) => return Ok(NotInstalled),
+    };
+
+    Ok(parse_dpkg_status(&status))
+}
+
+fn detect_rpm_package(name: &str) -> Result<InstallStatus, String> {
+    let query = exec_cmd("rpm", &["-q", "--queryformat", "%{VERSION}-%{RELEASE}", name]);
+
+    match query {
+        Ok(output) => {
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+            Ok(Installed(Some(version).filter(|version| !version.is_empty())))
+        }
+        Err(_) => Ok(NotInstalled),
+    }
+}
+
+fn detect_pacman_package(name: &str) -> Result<InstallStatus, String> {
+    let query = exec_cmd("pacman", &["-Q", name]);
+
+    match query {
+        Ok(output) => {
+            let version = String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .nth(1)
+                .map(str::to_string);
+
+            Ok(Installed(version))
+        }
+        Err(_) => Ok(NotInstalled),
+    }
+}
+
+fn detect_brew_package(name: &str) -> Result<InstallStatus, String> {
+    let query = exec_cmd("brew", &["list", "--versions", name]);
+
+    match query {
+        Ok(output) => {
+            let version = String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .last()
+                .filter(|version| *version != name)
+                .map(str::to_string);
+
+            Ok(Installed(version))
+        }
+        Err(_) => Ok(NotInstalled),
+    }
+}
+
+fn detect_winget_package(id: &str) -> Result<InstallStatus, String> {
+    let query = exec_powershell(&format!("winget list --id {id} --exact --accept-source-agreements"));
+
+    match query {
+        Ok(output) => Ok(Installed(parse_winget_version(&String::from_utf8_lossy(&output.stdout), id))),
+        Err(_) => Ok(NotInstalled),
+    }
+}
+
+/// The version follows the ID, like `Zoom.Zoom  6.1.1.443  winget`.
+fn parse_winget_version(list: &str, id: &str) -> Option<String> {
+    list
+        .lines()
+        .find_map(|line| {
+            let mut columns = line.split_whitespace().skip_while(|column| *column != id);
+
+            columns.next()?;
+            columns.next().map(str::to_string)
+        })
+}
+
+fn detect_flatpak_package(id: &str) -> Result<InstallStatus, String> {
+    let query = exec_cmd("flatpak", &["info", id]);
+
+    match query {
+        Ok(output) => Ok(Installed(parse_flatpak_version(&String::from_utf8_lossy(&output.stdout)))),
+        Err(_) => Ok(NotInstalled),
+    }
+}
+
+fn parse_flatpak_version(info: &str) -> Option<String> {
+    info
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Version:"))
+        .map(|version| version.trim().to_string())
+}
+
+fn detect_home_dir(rel_path: &Path) -> Result<InstallStatus, String> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| "Fail to read the user home directory".to_string())?;
+
+    if home.join(rel_path).is_dir() {
+        Ok(Installed(None))
+    } else {
+        Ok(NotInstalled)
+    }
+}
+
+/// Some tools like `java -version` print their version to `stderr`.
+fn first_output_line(output: &Output) -> Option<String> {
+    [&output.stdout, &output.stderr]
+        .iter()
+        .map(|bytes| String::from_utf8_lossy(bytes).to_string())
+        .find_map(|text| text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+        )
+}
+
+fn version_core(version: &str) -> String {
+    version
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect::<String>()
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// At a component boundary, like `1.2` in `1.2.3` but not in `11.2.3`.
+fn has_version_core(text: &str, core: &str) -> bool {
+    text.split(|c: char| !c.is_ascii_digit() && c != '.')
+        .map(|number| number.trim_matches('.'))
+        .any(|number| number == core || number.starts_with(&format!("{core}.")))
+}
+
+fn parse_dpkg_status(status: &str) -> InstallStatus {
+    match status.trim().strip_prefix("install ok installed") {
+        Some(version) if !version.trim().is_empty() => Installed(Some(version.trim().to_string())),
+        Some(_) => Installed(None),
+        None => NotInstalled,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::detection::{parse_dpkg_status, parse_flatpak_version, parse_winget_version};
+    use crate::image::detection::InstallStatus::{Installed, NotInstalled};
+
+    #[test]
+    fn parses_dpkg_status() {
+        assert_eq!(
+            Installed(Some("6.1.1.443".to_string())),
+            parse_dpkg_status("install ok installed 6.1.1.443"),
+        );
+
+        assert_eq!(
+            NotInstalled,
+            parse_dpkg_status("deinstall ok config-files 6.1.1.443"),
+        );
+
+        assert_eq!(NotInstalled, parse_dpkg_status(""));
+    }
+
+    #[test]
+    fn compares_installed_version_core() {
+        let java = Installed(Some("openjdk version \"21.0.2\" 2024-01-16".to_string()));
+
+        assert_eq!(Some(true), java.has_version("21.0.2-tem"));
+        assert_eq!(Some(false), java.has_version("22.0.1-tem"));
+        assert_eq!(None, java.has_version("latest"));
+        assert_eq!(None, Installed(None).has_version("21.0.2"));
+        assert_eq!(None, NotInstalled.has_version("21.0.2"));
+    }
+
+    #[test]
+    fn matches_whole_version_numbers() {
+        let java = Installed(Some("openjdk version \"21.0.20\" 2024-01-16".to_string()));
+        let go = Installed(Some("go version go11.2.3 linux/amd64".to_string()));
+
+        assert_eq!(Some(false), java.has_version("21.0.2"));
+        assert_eq!(Some(true), java.has_version("21.0.20"));
+        assert_eq!(Some(false), go.has_version("1.2"));
+        assert_eq!(Some(true), go.has_version("11.2"));
+    }
+
+    #[test]
+    fn parses_winget_version() {
+        let list = "Name           Id         Version    Source\n\
+            ---------------------------------------------\n\
+            Zoom Workplace Zoom.Zoom  6.1.1.443  winget\n";
+
+        assert_eq!(Some("6.1.1.443".to_string()), parse_winget_version(list, "Zoom.Zoom"));
+        assert_eq!(None, parse_winget_version("No installed package found matching input criteria.", "Zoom.Zoom"));
+    }
+
+    #[test]
+    fn parses_flatpak_version() {
+        let info = "GNU Image Manipulation Program - Create images and edit photographs\n\n\
+                     ID: org.gimp.GIMP\n\
+                    Ref: app/org.gimp.GIMP/x86_64/stable\n\
+                Version: 2.10.38\n\
+                 Origin: flathub\n";
+
+        assert_eq!(Some("2.10.38".to_string()), parse_flatpak_version(info));
+        assert_eq!(None, parse_flatpak_version("ID: md.obsidian.Obsidian\n"));
+    }
+}
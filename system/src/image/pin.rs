@@ -0,0 +1,123 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::image::ImageId;
+use crate::tmp;
+
+/// User-level version of an image over the repository one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pin {
+    pub version: String,
+
+    /// The repository hash only matches the repository version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_sha256: Option<String>,
+}
+
+impl Pin {
+    pub fn apply(&self, info: &mut Value) -> Result<(), String> {
+        let fields = info
+            .as_object_mut()
+            .filter(|fields| fields.contains_key("version"))
+            .ok_or_else(|| "Image information has no version to pin".to_string())?;
+
+        fields.insert("version".to_string(), Value::String(self.version.clone()));
+
+        if let Some(hash) = &self.hash_sha256 {
+            fields.insert("hash_sha256".to_string(), Value::String(hash.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Stored at `~/.config/mathswe-ops/pins.json`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pins(BTreeMap<String, Pin>);
+
+impl Pins {
+    fn path() -> Result<PathBuf, String> {
+        tmp::config_dir()
+            .map(|dir| dir.join("pins.json"))
+            .ok_or_else(|| "Fail to find the user config directory".to_string())
+    }
+
+    pub fn load() -> Result<Self, String> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Pins::default());
+        }
+
+        let pins_json = fs::read_to_string(&path).map_err(|error| error.to_string())?;
+
+        serde_json::from_str(&pins_json)
+            .map_err(|error| format!("Fail to parse pins at {:?}.\nCause: {}", path, error))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path()?;
+        let pins_json = serde_json::to_string_pretty(self).map_err(|error| error.to_string())?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+
+        fs::write(path, pins_json).map_err(|error| error.to_string())
+    }
+
+    pub fn get(&self, id: &ImageId) -> Option<&Pin> {
+        self.0.get(&id.to_string())
+    }
+
+    pub fn set(&mut self, id: &ImageId, pin: Pin) {
+        self.0.insert(id.to_string(), pin);
+    }
+
+    pub fn remove(&mut self, id: &ImageId) -> Option<Pin> {
+        self.0.remove(&id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::image::pin::Pin;
+
+    #[test]
+    fn overrides_version_and_hash() {
+        let mut info = json!({ "version": "1.22.5", "hash_sha256": "abc" });
+        let pin = Pin { version: "1.21.0".to_string(), hash_sha256: Some("def".to_string()) };
+
+        pin.apply(&mut info).unwrap();
+
+        assert_eq!(json!({ "version": "1.21.0", "hash_sha256": "def" }), info);
+    }
+
+    #[test]
+    fn keeps_hash_without_pinned_hash() {
+        let mut info = json!({ "version": "1.22.5", "hash_sha256": "abc" });
+        let pin = Pin { version: "1.21.0".to_string(), hash_sha256: None };
+
+        pin.apply(&mut info).unwrap();
+
+        assert_eq!(json!({ "version": "1.21.0", "hash_sha256": "abc" }), info);
+    }
+
+    #[test]
+    fn fails_without_version_field() {
+        let mut info = json!({ "source": "distro" });
+        let pin = Pin { version: "6.1".to_string(), hash_sha256: None };
+
+        assert!(pin.apply(&mut info).is_err());
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Like the IDE settings in `.config/JetBrains/RustRover2024.1`.
+pub fn versioned_dirs(parent: &str, prefix: &str) -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else { return Vec::new() };
+    let Ok(entries) = fs::read_dir(home.join(parent)) else { return Vec::new() };
+
+    let mut dirs = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(prefix))
+        .map(|name| Path::new(parent).join(name))
+        .collect::<Vec<PathBuf>>();
+
+    dirs.sort();
+    dirs
+}
+
+pub fn purge(rel_paths: &[PathBuf]) -> Result<(), String> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| "Fail to read the user home directory".to_string())?;
+
+    let failed = rel_paths
+        .iter()
+        .map(|rel_path| home.join(rel_path))
+        .filter(|path| path.symlink_metadata().is_ok())
+        .filter_map(|path| {
+            info!("Purging {}...", path.display());
+
+            remove_path(&path)
+                .err()
+                .map(|error| format!("{}: {}", path.display(), error))
+        })
+        .collect::<Vec<String>>();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Fail to purge {}", failed.join(", ")))
+    }
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() && !path.is_symlink() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
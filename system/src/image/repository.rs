@@ -4,9 +4,9 @@
 
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
-use DesktopImageId::{CLion, DataGrip, Goland, IntelliJIdea, JetBrainsToolbox, PhpStorm, PyCharm, Rider, RubyMine, RustRover, VsCode, WebStorm};
-use ImageOperationError::OperationNotImplemented;
-use ServerImageId::{Git, Go, Gradle, Java, Miniconda, Node, Nvm, Rust, Sdkman};
+use DesktopImageId::{CLion, DataGrip, DataSpell, DevFonts, Fleet, Gimp, Goland, IntelliJIdea, JetBrainsToolbox, Obsidian, PhpStorm, PyCharm, Rider, RubyMine, RustRover, VsCode, WebStorm, Writerside};
+use ImageOperationError::{InfoError, OperationNotImplemented};
+use ServerImageId::{Dotnet, Elixir, Ffmpeg, Flutter, Gh, Ghcup, Git, GitlabRunner, Go, Gradle, Grafana, ImageMagick, Java, Julia, Kotlin, Miniconda, Mysql, Nginx, Node, Nvm, Php, Pipx, Poetry, Postgresql, Prometheus, Pyenv, R, Rabbitmq, Rbenv, Redis, Ruby, Rust, Sbt, Scala, Sdkman, Shell, Ssh, TexLive, Uv, Zig};
 
 use crate::image::desktop::jetbrains_ide::JetBrainsIdeImage;
 use crate::image::desktop::jetbrains_toolbox::JetBrainsToolboxImage;
@@ -14,18 +14,53 @@ use crate::image::desktop::vscode::VsCodeImage;
 use crate::image::desktop::zoom::ZoomImage;
 use crate::image::desktop::DesktopImageId;
 use crate::image::desktop::DesktopImageId::Zoom;
+use crate::image::desktop::dev_fonts::DevFontsImage;
+use crate::image::desktop::flatpak_app::FlatpakAppImage;
 use crate::image::server::go::GoImage;
 use crate::image::server::gradle::GradleImage;
 use crate::image::server::java::JavaImage;
+use crate::image::server::kotlin::KotlinImage;
 use crate::image::server::miniconda::MinicondaImage;
 use crate::image::server::node::NodeImage;
 use crate::image::server::nvm::NvmImage;
 use crate::image::server::rust::RustImage;
 use crate::image::server::sdkman::SdkmanImage;
 use crate::image::server::ServerImageId;
-use crate::image::{Config, ImageId, ImageInfoError, ImageInfoLoader, ImageLoadContext, ImageLoader, ImageOperationError, ImageOps, LoadImage, StrFind, ToImageId};
+use crate::image::{Config, ImageId, ImageInfoError, ImageInfoLoader, ImageLoadContext, InfoFileType, ImageLoader, ImageOperationError, ImageOps, LoadImage, StrFind, ToImageId};
 use crate::image::server::git::GitImage;
+use crate::image::server::shell::ShellImage;
+use crate::image::server::ssh::SshImage;
+use crate::image::server::scala::ScalaImage;
+use crate::image::server::sbt::SbtImage;
+use crate::image::server::ghcup::GhcupImage;
+use crate::image::server::elixir::ElixirImage;
+use crate::image::server::dotnet::DotnetImage;
+use crate::image::server::php::PhpImage;
+use crate::image::server::rbenv::RbenvImage;
+use crate::image::server::ruby::RubyImage;
+use crate::image::server::r::RImage;
+use crate::image::server::julia::JuliaImage;
+use crate::image::server::zig::ZigImage;
+use crate::image::server::flutter::FlutterImage;
+use crate::image::server::texlive::TexLiveImage;
+use crate::image::server::ffmpeg::FfmpegImage;
+use crate::image::server::imagemagick::ImageMagickImage;
+use crate::image::server::postgresql::PostgresqlImage;
+use crate::image::server::mysql::MysqlImage;
+use crate::image::server::redis::RedisImage;
+use crate::image::server::nginx::NginxImage;
+use crate::image::server::rabbitmq::RabbitmqImage;
+use crate::image::server::grafana::GrafanaImage;
+use crate::image::server::prometheus::PrometheusImage;
+use crate::image::server::gh::GhImage;
+use crate::image::server::gitlab_runner::GitlabRunnerImage;
+use crate::image::server::pyenv::PyenvImage;
+use crate::image::server::poetry::PoetryImage;
+use crate::image::server::pipx::PipxImage;
+use crate::image::server::uv::UvImage;
 use crate::os::Os;
+use crate::os::Os::{Linux, MacOs, Windows};
+use crate::os::OsArch::{Arm64, X64};
 
 struct RepositoryImageLoader<T> where T: Display + ToImageId {
     id: T,
@@ -45,38 +80,123 @@ impl ToImageId for RepositoryImageLoader<DesktopImageId> {
 
 impl LoadImage for RepositoryImageLoader<DesktopImageId> {
     fn load_image(&self, os: Os) -> Result<Box<dyn ImageOps>, ImageInfoError> {
+        check_os(&self.id, &os, self.supports(&os))?;
+
         let info_loader = ImageInfoLoader::from(&self.id, PathBuf::from("image"), PathBuf::from(""));
         let ctx = ImageLoadContext::new(&os, info_loader);
         let image = match self.id {
-            Zoom => ctx.load(ZoomImage::new)?,
-            VsCode => ctx.load(VsCodeImage::new)?,
-            JetBrainsToolbox => ctx.load(JetBrainsToolboxImage::new)?,
-            IntelliJIdea => ctx.load(JetBrainsIdeImage::intellij_idea())?,
-            WebStorm => ctx.load(JetBrainsIdeImage::webstorm())?,
-            RustRover => ctx.load(JetBrainsIdeImage::rustrover())?,
-            CLion => ctx.load(JetBrainsIdeImage::clion())?,
-            DataGrip => ctx.load(JetBrainsIdeImage::datagrip())?,
-            PyCharm => ctx.load(JetBrainsIdeImage::pycharm())?,
-            Goland => ctx.load(JetBrainsIdeImage::goland())?,
-            Rider => ctx.load(JetBrainsIdeImage::rider())?,
-            PhpStorm => ctx.load(JetBrainsIdeImage::phpstorm())?,
-            RubyMine => ctx.load(JetBrainsIdeImage::rubymine())?,
+            Zoom => ctx.try_load(ZoomImage::new)?,
+            VsCode => ctx.try_load(VsCodeImage::new)?,
+            JetBrainsToolbox => ctx.try_load(JetBrainsToolboxImage::new)?,
+            IntelliJIdea => ctx.try_load(JetBrainsIdeImage::intellij_idea())?,
+            WebStorm => ctx.try_load(JetBrainsIdeImage::webstorm())?,
+            RustRover => ctx.try_load(JetBrainsIdeImage::rustrover())?,
+            CLion => ctx.try_load(JetBrainsIdeImage::clion())?,
+            DataGrip => ctx.try_load(JetBrainsIdeImage::datagrip())?,
+            PyCharm => ctx.try_load(JetBrainsIdeImage::pycharm())?,
+            Goland => ctx.try_load(JetBrainsIdeImage::goland())?,
+            Rider => ctx.try_load(JetBrainsIdeImage::rider())?,
+            PhpStorm => ctx.try_load(JetBrainsIdeImage::phpstorm())?,
+            RubyMine => ctx.try_load(JetBrainsIdeImage::rubymine())?,
+            Fleet => ctx.try_load(JetBrainsIdeImage::fleet())?,
+            DataSpell => ctx.try_load(JetBrainsIdeImage::dataspell())?,
+            Writerside => ctx.try_load(JetBrainsIdeImage::writerside())?,
+            DevFonts => ctx.load(DevFontsImage::new)?,
+            Gimp => ctx.try_load(FlatpakAppImage::gimp())?,
+            Obsidian => ctx.try_load(FlatpakAppImage::obsidian())?,
         };
 
         Ok(image)
     }
 
-    fn load_config(&self, _: Os)
+    fn load_config(&self, os: Os)
         -> Result<Box<dyn Config>, ImageOperationError> {
-        Err(OperationNotImplemented(
-            self.id.to_image_id(),
-            "config".to_string(),
-        ))
+        check_os(&self.id, &os, self.supports(&os)).map_err(InfoError)?;
+
+        let info_loader = ImageInfoLoader::from(&self.id, PathBuf::from("image"), PathBuf::from(""));
+        let ctx = ImageLoadContext::new(&os, info_loader);
+
+        let config = match self.id {
+            VsCode => ctx
+                .try_load_concrete(VsCodeImage::new)
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            IntelliJIdea => ctx
+                .try_load_concrete(JetBrainsIdeImage::intellij_idea())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            WebStorm => ctx
+                .try_load_concrete(JetBrainsIdeImage::webstorm())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            RustRover => ctx
+                .try_load_concrete(JetBrainsIdeImage::rustrover())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            CLion => ctx
+                .try_load_concrete(JetBrainsIdeImage::clion())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            DataGrip => ctx
+                .try_load_concrete(JetBrainsIdeImage::datagrip())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            PyCharm => ctx
+                .try_load_concrete(JetBrainsIdeImage::pycharm())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            Goland => ctx
+                .try_load_concrete(JetBrainsIdeImage::goland())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            Rider => ctx
+                .try_load_concrete(JetBrainsIdeImage::rider())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            PhpStorm => ctx
+                .try_load_concrete(JetBrainsIdeImage::phpstorm())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            RubyMine => ctx
+                .try_load_concrete(JetBrainsIdeImage::rubymine())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            DataSpell => ctx
+                .try_load_concrete(JetBrainsIdeImage::dataspell())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            Writerside => ctx
+                .try_load_concrete(JetBrainsIdeImage::writerside())
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            _ => Err(OperationNotImplemented(
+                self.id.to_image_id(),
+                "config".to_string(),
+            ))?
+        };
+
+        Ok(config)
     }
 }
 
 impl ImageLoader for RepositoryImageLoader<DesktopImageId> {}
 
+impl RepositoryImageLoader<DesktopImageId> {
+    /// Fonts, Flathub apps, and winget installs also build for other OSes.
+    fn supports(&self, os: &Os) -> bool {
+        match os {
+            Linux(X64, _) => true,
+            Linux(arch, _) => match self.id {
+                DevFonts => true,
+                Gimp | Obsidian => *arch == Arm64,
+                _ => false,
+            },
+            MacOs(_) => false,
+            Windows(_) => matches!(self.id, Zoom | VsCode | JetBrainsToolbox),
+        }
+    }
+}
+
 impl Display for RepositoryImageLoader<ServerImageId> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", format!("Server Image ID: {}", self.id))
@@ -91,18 +211,51 @@ impl ToImageId for RepositoryImageLoader<ServerImageId> {
 
 impl LoadImage for RepositoryImageLoader<ServerImageId> {
     fn load_image(&self, os: Os) -> Result<Box<dyn ImageOps>, ImageInfoError> {
+        check_os(&self.id, &os, self.supports(&os))?;
+
         let info_loader = ImageInfoLoader::from(&self.id, PathBuf::from("image"), PathBuf::from(""));
         let ctx = ImageLoadContext::new(&os, info_loader);
         let image = match self.id {
-            Rust => ImageLoadContext::basic_image_from(os, RustImage::new),
-            Go => ctx.load(GoImage::new)?,
+            Rust => Box::new(RustImage::new(os)?),
+            Go => ctx.try_load(GoImage::new)?,
             Sdkman => ImageLoadContext::basic_image_from(os, SdkmanImage::new),
             Java => ctx.load(JavaImage::new)?,
             Gradle => ctx.load(GradleImage::new)?,
+            Kotlin => ctx.load(KotlinImage::new)?,
+            Scala => ctx.load(ScalaImage::new)?,
+            Sbt => ctx.load(SbtImage::new)?,
             Nvm => ctx.load(NvmImage::new)?,
             Node => ctx.load(NodeImage::new)?,
-            Miniconda => ctx.load(MinicondaImage::new)?,
+            Miniconda => ctx.try_load(MinicondaImage::new)?,
+            Ghcup => ctx.load(GhcupImage::new)?,
+            Elixir => ctx.load(ElixirImage::new)?,
+            Dotnet => ctx.load(DotnetImage::new)?,
+            Php => ctx.load(PhpImage::new)?,
+            Rbenv => ctx.load(RbenvImage::new)?,
+            Ruby => ctx.load(RubyImage::new)?,
+            R => ctx.load(RImage::new)?,
+            Julia => ctx.try_load(JuliaImage::new)?,
+            Zig => ctx.try_load(ZigImage::new)?,
+            Flutter => ctx.try_load(FlutterImage::new)?,
+            TexLive => ctx.try_load(TexLiveImage::new)?,
+            Ffmpeg => ctx.try_load(FfmpegImage::new)?,
+            ImageMagick => ctx.try_load(ImageMagickImage::new)?,
+            Postgresql => ctx.load(PostgresqlImage::new)?,
+            Mysql => ctx.load(MysqlImage::new)?,
+            Redis => ctx.load(RedisImage::new)?,
+            Nginx => ctx.load(NginxImage::new)?,
+            Rabbitmq => ctx.load(RabbitmqImage::new)?,
+            Grafana => ctx.load(GrafanaImage::new)?,
+            Prometheus => ctx.try_load(PrometheusImage::new)?,
+            Gh => ctx.load(GhImage::new)?,
+            GitlabRunner => ctx.load(GitlabRunnerImage::new)?,
+            Pyenv => ctx.load(PyenvImage::new)?,
+            Poetry => ctx.load(PoetryImage::new)?,
+            Pipx => ctx.load(PipxImage::new)?,
+            Uv => ctx.try_load(UvImage::new)?,
             Git => ImageLoadContext::basic_image_from(os, GitImage::new),
+            Shell => ImageLoadContext::basic_image_from(os, ShellImage::new),
+            Ssh => ImageLoadContext::basic_image_from(os, SshImage::new),
         };
 
         Ok(image)
@@ -110,16 +263,50 @@ impl LoadImage for RepositoryImageLoader<ServerImageId> {
 
     fn load_config(&self, os: Os)
         -> Result<Box<dyn Config>, ImageOperationError> {
+        check_os(&self.id, &os, self.supports(&os)).map_err(InfoError)?;
+
         let info_loader = ImageInfoLoader::from(&self.id, PathBuf::from("image"), PathBuf::from(""));
         let ctx = ImageLoadContext::new(&os, info_loader);
 
         let config = match self.id {
             Miniconda => ctx
-                .load_concrete(MinicondaImage::new)
+                .try_load_concrete(MinicondaImage::new)
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            Gradle => ctx
+                .load_concrete(GradleImage::new)
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            Mysql => ctx
+                .load_concrete(MysqlImage::new)
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            GitlabRunner => ctx
+                .load_concrete(GitlabRunnerImage::new)
                 .and_then(|image| ctx.load_to_image_config(image))?,
 
+            Pipx => ctx
+                .load_concrete(PipxImage::new)
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            Nvm => ctx
+                .load_concrete(NvmImage::new)
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            Node => ctx
+                .load_concrete(NodeImage::new)
+                .and_then(|image| ctx.load_to_image_config(image))?,
+
+            Sdkman => ctx.load_to_image_config(SdkmanImage::new(os))?,
+
+            Rust => ctx.load_to_image_config(RustImage::new(os).map_err(InfoError)?)?,
+
             Git => ctx.load_to_image_config(GitImage::new(os))?,
 
+            Shell => ctx.load_to_image_config(ShellImage::new(os))?,
+
+            Ssh => ctx.load_to_image_config(SshImage::new(os))?,
+
             _ => Err(OperationNotImplemented(
                 self.id.to_image_id(),
                 "config".to_string(),
@@ -132,6 +319,33 @@ impl LoadImage for RepositoryImageLoader<ServerImageId> {
 
 impl ImageLoader for RepositoryImageLoader<ServerImageId> {}
 
+impl RepositoryImageLoader<ServerImageId> {
+    /// ARM builds provision Raspberry Pis, and macOS ones Mac laptops.
+    fn supports(&self, os: &Os) -> bool {
+        match os {
+            Linux(X64, _) => true,
+            Linux(arch, _) => match self.id {
+                Rust | Go | Sdkman | Java | Gradle | Kotlin | Scala | Sbt | Nvm | Node | Rbenv | Ruby
+                | ImageMagick | Grafana | Prometheus | Gh | Pyenv | Poetry | Pipx | Uv | Git | Shell | Ssh => true,
+
+                // Their vendor APT repositories only publish arm64 packages
+                Postgresql | Redis | Nginx => *arch == Arm64,
+                _ => false,
+            },
+            MacOs(_) => matches!(self.id, Rust | Go | Sdkman | Nvm | Shell | Ssh),
+            Windows(_) => false,
+        }
+    }
+}
+
+fn check_os(id: &impl Display, os: &Os, supported: bool) -> Result<(), ImageInfoError> {
+    if supported {
+        Ok(())
+    } else {
+        Err(ImageInfoError::unsupported_os(id, os))
+    }
+}
+
 pub struct Repository;
 
 impl Repository {
@@ -145,6 +359,32 @@ impl Repository {
         }
     }
 
+    pub fn desktop_image_ids() -> Vec<ImageId> {
+        DesktopImageId::all()
+            .iter()
+            .map(ToImageId::to_image_id)
+            .collect()
+    }
+
+    pub fn server_image_ids() -> Vec<ImageId> {
+        ServerImageId::all()
+            .iter()
+            .map(ToImageId::to_image_id)
+            .collect()
+    }
+
+    /// Basic images have no information file.
+    pub fn info_path(id: &ImageId) -> PathBuf {
+        ImageInfoLoader::from(id, PathBuf::from("image"), PathBuf::from("")).path()
+    }
+
+    /// Only configurable images have one.
+    pub fn config_path(id: &ImageId) -> PathBuf {
+        ImageInfoLoader::from(id, PathBuf::from("image"), PathBuf::from(""))
+            .of(InfoFileType::Config)
+            .path()
+    }
+
     fn box_it<T>(id: T) -> Box<dyn ImageLoader>
     where
         T: Display + ToImageId + 'static,
@@ -153,3 +393,91 @@ impl Repository {
         Box::new(RepositoryImageLoader { id })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    use crate::image::repository::Repository;
+    use crate::os::{DEBIAN_ARM64, DEBIAN_ARMHF, MACOS_ARM64, UBUNTU_X64, WINDOWS_X64};
+
+    #[test]
+    fn lists_every_image_id_once() {
+        let ids = Repository::desktop_image_ids()
+            .into_iter()
+            .chain(Repository::server_image_ids())
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>();
+
+        let unique_ids = ids.iter().collect::<HashSet<&String>>();
+
+        assert_eq!(ids.len(), unique_ids.len());
+
+        for id in &ids {
+            let loader = Repository::image_loader_from(id)
+                .expect("Listed image ID must be in the repository");
+
+            assert_eq!(*id, loader.to_image_id().to_string());
+        }
+    }
+
+    #[test]
+    fn info_path_points_to_image_dir() {
+        let id = Repository::image_loader_from("jetbrains-toolbox")
+            .unwrap()
+            .to_image_id();
+
+        assert_eq!(
+            PathBuf::from("image/jetbrains-toolbox.json"),
+            Repository::info_path(&id),
+        );
+    }
+
+    #[test]
+    fn config_path_points_to_image_dir() {
+        let id = Repository::image_loader_from("git")
+            .unwrap()
+            .to_image_id();
+
+        assert_eq!(
+            PathBuf::from("image/git.config.json"),
+            Repository::config_path(&id),
+        );
+    }
+
+    #[test]
+    fn refuses_linux_only_images_on_macos() {
+        let load = |id: &str, os| Repository::image_loader_from(id).unwrap().load_image(os);
+
+        assert!(load("rust", MACOS_ARM64).is_ok());
+        assert!(load("sdkman", MACOS_ARM64).is_ok());
+        assert!(load("git", MACOS_ARM64).is_err());
+        assert!(load("vscode", MACOS_ARM64).is_err());
+        assert!(load("git", UBUNTU_X64).is_ok());
+    }
+
+    #[test]
+    fn loads_winget_images_on_windows() {
+        let load = |id: &str| Repository::image_loader_from(id).unwrap().load_image(WINDOWS_X64);
+
+        assert!(load("zoom").is_ok());
+        assert!(load("vscode").is_ok());
+        assert!(load("jetbrains-toolbox").is_ok());
+        assert!(load("intellij-idea").is_err());
+        assert!(load("rust").is_err());
+    }
+
+    #[test]
+    fn loads_arm_images_on_raspberry_pi() {
+        let load = |id: &str, os| Repository::image_loader_from(id).unwrap().load_image(os);
+
+        assert!(load("go", DEBIAN_ARMHF).is_ok());
+        assert!(load("uv", DEBIAN_ARMHF).is_ok());
+        assert!(load("prometheus", DEBIAN_ARM64).is_ok());
+        assert!(load("redis", DEBIAN_ARM64).is_ok());
+        assert!(load("redis", DEBIAN_ARMHF).is_err());
+        assert!(load("zig", DEBIAN_ARM64).is_err());
+        assert!(load("vscode", DEBIAN_ARM64).is_err());
+    }
+}
@@ -6,7 +6,7 @@ use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-use ServerImageId::{Git, Go, Gradle, Java, Miniconda, Node, Nvm, Rust, Sdkman};
+use ServerImageId::{Dotnet, Elixir, Ffmpeg, Flutter, Gh, Ghcup, Git, GitlabRunner, Go, Gradle, Grafana, ImageMagick, Java, Julia, Kotlin, Miniconda, Mysql, Nginx, Node, Nvm, Php, Pipx, Poetry, Postgresql, Prometheus, Pyenv, R, Rabbitmq, Rbenv, Redis, Ruby, Rust, Sbt, Scala, Sdkman, Shell, Ssh, TexLive, Uv, Zig};
 
 use crate::image::{Image, ImageId, StrFind, ToImageId};
 use crate::impl_image;
@@ -19,10 +19,88 @@ pub enum ServerImageId {
     Sdkman,
     Java,
     Gradle,
+    Kotlin,
+    Scala,
+    Sbt,
     Nvm,
     Node,
     Miniconda,
+    Ghcup,
+    Elixir,
+    Dotnet,
+    Php,
+    Rbenv,
+    Ruby,
+    R,
+    Julia,
+    Zig,
+    Flutter,
+    TexLive,
+    Ffmpeg,
+    ImageMagick,
+    Postgresql,
+    Mysql,
+    Redis,
+    Nginx,
+    Rabbitmq,
+    Grafana,
+    Prometheus,
+    Gh,
+    GitlabRunner,
+    Pyenv,
+    Poetry,
+    Pipx,
+    Uv,
     Git,
+    Shell,
+    Ssh,
+}
+
+impl ServerImageId {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Rust,
+            Go,
+            Sdkman,
+            Java,
+            Gradle,
+            Kotlin,
+            Scala,
+            Sbt,
+            Nvm,
+            Node,
+            Miniconda,
+            Ghcup,
+            Elixir,
+            Dotnet,
+            Php,
+            Rbenv,
+            Ruby,
+            R,
+            Julia,
+            Zig,
+            Flutter,
+            TexLive,
+            Ffmpeg,
+            ImageMagick,
+            Postgresql,
+            Mysql,
+            Redis,
+            Nginx,
+            Rabbitmq,
+            Grafana,
+            Prometheus,
+            Gh,
+            GitlabRunner,
+            Pyenv,
+            Poetry,
+            Pipx,
+            Uv,
+            Git,
+            Shell,
+            Ssh,
+        ]
+    }
 }
 
 impl Display for ServerImageId {
@@ -33,10 +111,41 @@ impl Display for ServerImageId {
             Sdkman => "sdkman",
             Java => "java",
             Gradle => "gradle",
+            Kotlin => "kotlin",
+            Scala => "scala",
+            Sbt => "sbt",
             Nvm => "nvm",
             Node => "node",
             Miniconda => "miniconda",
+            Ghcup => "ghcup",
+            Elixir => "elixir",
+            Dotnet => "dotnet",
+            Php => "php",
+            Rbenv => "rbenv",
+            Ruby => "ruby",
+            R => "r",
+            Julia => "julia",
+            Zig => "zig",
+            Flutter => "flutter",
+            TexLive => "texlive",
+            Ffmpeg => "ffmpeg",
+            ImageMagick => "imagemagick",
+            Postgresql => "postgresql",
+            Mysql => "mysql",
+            Redis => "redis",
+            Nginx => "nginx",
+            Rabbitmq => "rabbitmq",
+            Grafana => "grafana",
+            Prometheus => "prometheus",
+            Gh => "gh",
+            GitlabRunner => "gitlab-runner",
+            Pyenv => "pyenv",
+            Poetry => "poetry",
+            Pipx => "pipx",
+            Uv => "uv",
             Git => "git",
+            Shell => "shell",
+            Ssh => "ssh",
         };
 
         write!(f, "{}", msg)
@@ -51,10 +160,41 @@ impl StrFind for ServerImageId {
             "sdkman" => Some(Sdkman),
             "java" => Some(Java),
             "gradle" => Some(Gradle),
+            "kotlin" => Some(Kotlin),
+            "scala" => Some(Scala),
+            "sbt" => Some(Sbt),
             "nvm" => Some(Nvm),
             "node" => Some(Node),
             "miniconda" => Some(Miniconda),
+            "ghcup" => Some(Ghcup),
+            "elixir" => Some(Elixir),
+            "dotnet" => Some(Dotnet),
+            "php" => Some(Php),
+            "rbenv" => Some(Rbenv),
+            "ruby" => Some(Ruby),
+            "r" => Some(R),
+            "julia" => Some(Julia),
+            "zig" => Some(Zig),
+            "flutter" => Some(Flutter),
+            "texlive" => Some(TexLive),
+            "ffmpeg" => Some(Ffmpeg),
+            "imagemagick" => Some(ImageMagick),
+            "postgresql" => Some(Postgresql),
+            "mysql" => Some(Mysql),
+            "redis" => Some(Redis),
+            "nginx" => Some(Nginx),
+            "rabbitmq" => Some(Rabbitmq),
+            "grafana" => Some(Grafana),
+            "prometheus" => Some(Prometheus),
+            "gh" => Some(Gh),
+            "gitlab-runner" => Some(GitlabRunner),
+            "pyenv" => Some(Pyenv),
+            "poetry" => Some(Poetry),
+            "pipx" => Some(Pipx),
+            "uv" => Some(Uv),
             "git" => Some(Git),
+            "shell" => Some(Shell),
+            "ssh" => Some(Ssh),
             _ => None
         }
     }
@@ -80,33 +220,157 @@ pub struct ServerImage(ServerImageId, Package);
 
 impl_image!(ServerImage);
 
+mod apt {
+    use crate::cmd::{exec_cmd, print_output};
+
+    fn exec_bash(bash_cmd: &str) -> Result<(), String> {
+        let output = exec_cmd("bash", &["-c", bash_cmd])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    pub fn write_file(path: &str, contents: &str) -> Result<(), String> {
+        exec_bash(&format!("printf '%s' '{contents}' | sudo tee {path}"))
+    }
+
+    pub fn delete_file(path: &str) -> Result<(), String> {
+        let output = exec_cmd("sudo", &["rm", "-f", path])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    pub fn install(packages: &[&str]) -> Result<(), String> {
+        let args = ["apt-get", "--yes", "install"]
+            .into_iter()
+            .chain(packages.iter().copied())
+            .collect::<Vec<&str>>();
+
+        let output = exec_cmd("sudo", &args)
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    pub fn remove(packages: &[&str]) -> Result<(), String> {
+        let args = ["apt-get", "--yes", "remove"]
+            .into_iter()
+            .chain(packages.iter().copied())
+            .collect::<Vec<&str>>();
+
+        let output = exec_cmd("sudo", &args)
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        let output = exec_cmd("sudo", &["apt-get", "--yes", "autoremove"])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+}
+
+mod systemd {
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::os::warn_without_systemd;
+
+    fn exec_systemctl(args: &[&str]) -> Result<(), String> {
+        warn_without_systemd();
+
+        let args = ["systemctl"]
+            .into_iter()
+            .chain(args.iter().copied())
+            .collect::<Vec<&str>>();
+
+        let output = exec_cmd("sudo", &args)
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    pub fn enable(service: &str) -> Result<(), String> {
+        info!("Enabling service {service}...");
+
+        exec_systemctl(&["enable", "--now", service])
+    }
+
+    pub fn disable(service: &str) -> Result<(), String> {
+        info!("Disabling service {service}...");
+
+        exec_systemctl(&["disable", "--now", service])
+    }
+
+    fn unit_path(service: &str) -> String {
+        format!("/etc/systemd/system/{service}.service")
+    }
+
+    pub fn write_unit(service: &str, contents: &str) -> Result<(), String> {
+        info!("Writing service unit {service}...");
+
+        let bash_cmd = format!("printf '%s' '{contents}' | sudo tee {}", unit_path(service));
+        let output = exec_cmd("bash", &["-c", &bash_cmd])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        exec_systemctl(&["daemon-reload"])
+    }
+
+    pub fn remove_unit(service: &str) -> Result<(), String> {
+        info!("Removing service unit {service}...");
+
+        let output = exec_cmd("sudo", &["rm", "-f", &unit_path(service)])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
 
+        exec_systemctl(&["daemon-reload"])
+    }
+}
 
 pub mod rust {
+    use std::env;
+    use std::path::PathBuf;
+
     use reqwest::Url;
+    use serde::{Deserialize, Serialize};
 
-    use crate::cmd::exec_cmd;
+    use crate::cmd::{exec_cmd, print_output};
     use crate::download::{DownloadRequest, Integrity};
     use crate::image::server::ServerImage;
     use crate::image::server::ServerImageId::Rust;
-    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image::detection::Detection;
+    use crate::image::{Config, Image, ImageConfig, ImageInfoError, ImageOps, Install, ToImageConfig, Uninstall};
     use crate::image_ops_impl;
     use crate::os::Os;
-    use crate::os::Os::Linux;
+    use crate::os::Os::{Linux, MacOs, Windows};
     use crate::package::{Package, Software};
 
+    #[derive(Clone)]
     pub struct RustImage(ServerImage);
 
     impl RustImage {
-        pub fn new(os: Os) -> Self {
+        pub fn new(os: Os) -> Result<Self, ImageInfoError> {
             let id = Rust;
             let pkg_id = id.to_string();
             let fetch_url = match os {
-                Linux(_, _) => "https://sh.rustup.rs"
+                Linux(_, _) | MacOs(_) => "https://sh.rustup.rs",
+                Windows(_) => return Err(ImageInfoError::unsupported_os(&id, &os)),
             };
             let version = "latest";
 
-            RustImage(
+            Ok(RustImage(
                 ServerImage(
                     id,
                     Package::new(
@@ -115,7 +379,7 @@ pub mod rust {
                         Software::new("Rust Team", "Rust", version),
                         Url::parse("https://www.rust-lang.org/tools/install").unwrap(),
                         DownloadRequest::new(fetch_url, Integrity::None).unwrap(),
-                    )))
+                    ))))
 
             // More Rustup doc:
             // https://rust-lang.github.io/rustup/installation/other.html
@@ -124,13 +388,13 @@ pub mod rust {
 
     impl Install for RustImage {
         fn install(&self) -> Result<(), String> {
-            let bash_cmd = format!("curl --proto '=https' --tlsv1.2 -sSf {} | sh -s -- -y", self.0.package().fetch.url());
+            let bash_cmd = format!("{} | sh -s -- -y", self.0.package().fetch.curl_script());
             let output = exec_cmd("bash", &["-c", &bash_cmd])
                 .map_err(|output| output.to_string())?;
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
             Ok(())
         }
@@ -143,19 +407,113 @@ pub mod rust {
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for RustImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("rustc", &["--version"])
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct RustConfig {
+        default_toolchain: String,
+        components: Vec<String>,
+        targets: Vec<String>,
+    }
+
+    type RustImageConfig = ImageConfig<RustImage, RustConfig>;
+
+    impl ToImageConfig<RustConfig> for RustImage {
+        fn to_image_config(&self, config: RustConfig) -> RustImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for RustImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let RustConfig { default_toolchain, components, targets } = &self.1;
+
+            // The components and targets go to the default toolchain, so it's
+            // set first
+            if !default_toolchain.trim().is_empty() {
+                info!("Setting default toolchain {default_toolchain}...");
+
+                exec_rustup(&["default", default_toolchain])?;
+            }
+
+            if !components.is_empty() {
+                info!("Adding components {}...", components.join(", "));
+
+                exec_rustup(&rustup_args(&["component", "add"], components))?;
+            }
+
+            if !targets.is_empty() {
+                info!("Adding targets {}...", targets.join(", "));
+
+                exec_rustup(&rustup_args(&["target", "add"], targets))?;
+            }
 
             Ok(())
         }
     }
 
-    impl ImageOps for RustImage { image_ops_impl!(); }
+    /// `~/.cargo/bin` isn't in the `PATH` right after the install.
+    fn rustup() -> String {
+        let cargo_home = env::var("CARGO_HOME")
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+
+        cargo_home
+            .map(|cargo_home| cargo_home.join("bin").join("rustup"))
+            .filter(|rustup| rustup.is_file())
+            .map_or("rustup".to_string(), |rustup| rustup.to_string_lossy().to_string())
+    }
+
+    fn rustup_args<'a>(subcommand: &[&'a str], values: &'a [String]) -> Vec<&'a str> {
+        subcommand
+            .iter()
+            .copied()
+            .chain(values.iter().map(String::as_str))
+            .collect()
+    }
+
+    fn exec_rustup(args: &[&str]) -> Result<(), String> {
+        let output = exec_cmd(&rustup(), args)
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::image::server::rust::{rustup_args, RustConfig};
+
+        #[test]
+        fn adds_every_component_at_once() {
+            let config: RustConfig = serde_json::from_str(r#"{
+                "default_toolchain": "stable",
+                "components": ["clippy", "rustfmt"],
+                "targets": ["wasm32-unknown-unknown"]
+            }"#).unwrap();
+
+            assert_eq!(vec!["component", "add", "clippy", "rustfmt"], rustup_args(&["component", "add"], &config.components));
+            assert_eq!(vec!["target", "add", "wasm32-unknown-unknown"], rustup_args(&["target", "add"], &config.targets));
+        }
+    }
 }
 
 pub mod go {
     use std::env;
-    use std::fs::OpenOptions;
-    use std::io::Write;
     use std::path::Path;
 
     use reqwest::Url;
@@ -165,13 +523,18 @@ pub mod go {
     use crate::download::{DownloadRequest, Downloader, Integrity};
     use crate::image::server::ServerImage;
     use crate::image::server::ServerImageId::Go;
-    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
     use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
     use crate::os::Os;
-    use crate::os::Os::Linux;
+    use crate::os::Os::{Linux, MacOs, Windows};
+    use crate::os::OsArch::{Arm64, Armhf, X64};
     use crate::package::{Package, SemVer, Software};
     use crate::tmp::TmpWorkingDir;
 
+    const PROFILE_LINES: [&str; 2] = ["# Golang", r#"export PATH="$PATH:/usr/local/go/bin""#];
+
     #[derive(Debug, Serialize, Deserialize)]
     pub struct GoInfo {
         version: SemVer,
@@ -180,13 +543,18 @@ pub mod go {
     pub struct GoImage(ServerImage);
 
     impl GoImage {
-        pub fn new(os: Os, GoInfo { version }: GoInfo) -> Self {
+        pub fn new(os: Os, GoInfo { version }: GoInfo) -> Result<Self, ImageInfoError> {
             let id = Go;
             let fetch_url = match os {
-                Linux(_, _) => format!("https://go.dev/dl/go{}.linux-amd64.tar.gz", version),
+                Linux(X64, _) => format!("https://go.dev/dl/go{}.linux-amd64.tar.gz", version),
+                Linux(Arm64, _) => format!("https://go.dev/dl/go{}.linux-arm64.tar.gz", version),
+                Linux(Armhf, _) => format!("https://go.dev/dl/go{}.linux-armv6l.tar.gz", version),
+                MacOs(X64) => format!("https://go.dev/dl/go{}.darwin-amd64.tar.gz", version),
+                MacOs(Arm64) => format!("https://go.dev/dl/go{}.darwin-arm64.tar.gz", version),
+                MacOs(Armhf) | Windows(_) => return Err(ImageInfoError::unsupported_os(&id, &os)),
             };
 
-            GoImage(
+            Ok(GoImage(
                 ServerImage(
                     id.clone(),
                     Package::new(
@@ -195,7 +563,7 @@ pub mod go {
                         Software::new("Google, LLC", "Go", &version.to_string()),
                         Url::parse("https://go.dev/doc/install").unwrap(),
                         DownloadRequest::new(&fetch_url, Integrity::None).unwrap(),
-                    )))
+                    ))))
         }
     }
 
@@ -214,13 +582,13 @@ pub mod go {
             let downloader = Downloader::from(package.fetch.clone(), &tmp);
             let installer_file = downloader.path.clone();
 
-            println!("Downloading Go...");
+            info!("Downloading Go...");
 
             downloader
                 .download_blocking()
                 .map_err(|error| error.to_string())?;
 
-            println!("Unpacking Go...");
+            info!("Unpacking Go...");
 
             let output = exec_cmd(
                 "sudo",
@@ -228,30 +596,24 @@ pub mod go {
             ).map_err(|error| error.to_string())?;
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
-            println!("Updating environment variable...");
+            info!("Updating environment variable...");
 
+            let profile = package.os.login_profile()?;
             let home = env::var("HOME").unwrap();
-            let mut prof = OpenOptions::new()
-                .write(true)
-                .append(true)
-                .open(Path::new(&home).join(".profile"))
-                .map_err(|error| error.to_string())?;
 
-            writeln!(prof, "# Golang").map_err(|error| error.to_string())?;
-            writeln!(prof, r#"export PATH="$PATH:/usr/local/go/bin""#).map_err(|error| error.to_string())?;
-            writeln!(prof, "").map_err(|error| error.to_string())?;
+            append_lines(&Path::new(&home).join(profile), &PROFILE_LINES)?;
 
             let output = exec_cmd(
                 "bash",
-                &["-c", "source ~/.profile && go version"],
+                &["-c", &format!("source ~/{profile} && go version")],
             ).map_err(|error| error.to_string())?;
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
-            println!("Go installed.");
+            info!("Go installed.");
 
             Ok(())
         }
@@ -259,35 +621,39 @@ pub mod go {
 
     impl Uninstall for GoImage {
         fn uninstall(&self) -> Result<(), String> {
-            println!("Removing Go files...");
+            info!("Removing Go files...");
 
             remove_go_dir()?;
 
-            println!("Cleaning environment variable...");
+            info!("Cleaning environment variable...");
 
-            // It deletes the lines from ~/.profile
+            // It deletes the lines from ~/.profile, or ~/.zprofile on macOS
             // # Golang
             // export PATH="$PATH:/usr/local/go/bin"
-            //
+            let profile = self.0.package().os.login_profile()?;
             let prof = env::var("HOME")
-                .map(|home| Path::new(&home).join(".profile"))
-                .map_err(|output| output.to_string())?;
-
-            let clean_profile_pattern = r#"/# Golang/d; /export PATH="\$PATH:\/usr\/local\/go\/bin"/d"#;
-            let output = exec_cmd("sed", &["-i", clean_profile_pattern, prof.to_str().unwrap()])
+                .map(|home| Path::new(&home).join(profile))
                 .map_err(|output| output.to_string())?;
 
-            let stdout = String::from_utf8_lossy(&output.stdout);
-
-            println!("{}", stdout);
+            remove_lines(&prof, &PROFILE_LINES)?;
 
-            println!("Go uninstalled.");
+            info!("Go uninstalled.");
 
             Ok(())
         }
     }
 
-    impl ImageOps for GoImage { image_ops_impl!(); }
+    impl ImageOps for GoImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("go", &["version"])
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
 
     fn remove_go_dir() -> Result<(), String> {
         let go_install_dir = "/usr/local/go";
@@ -296,7 +662,7 @@ pub mod go {
 
         let stdout = String::from_utf8_lossy(&output.stdout);
 
-        println!("{}", stdout);
+        verbose!("{}", stdout);
 
         Ok(())
     }
@@ -304,19 +670,30 @@ pub mod go {
 
 pub mod sdkman {
     use std::path::Path;
+    use std::process::Output;
     use std::{env, fs};
 
     use reqwest::Url;
+    use serde::{Deserialize, Serialize};
 
     use crate::cmd::exec_cmd;
     use crate::download::{DownloadRequest, Integrity};
     use crate::image::server::ServerImage;
     use crate::image::server::ServerImageId::Sdkman;
-    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image::detection::Detection;
+    use crate::image::{Config, Image, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
     use crate::image_ops_impl;
+    use crate::os::linux::remove_lines;
     use crate::os::Os;
     use crate::package::{Package, Software};
 
+    const RC_LINES: [&str; 3] = [
+        "#THIS MUST BE AT THE END OF THE FILE FOR SDKMAN TO WORK!!!",
+        r#"export SDKMAN_DIR="$HOME/.sdkman""#,
+        r#"[[ -s "$HOME/.sdkman/bin/sdkman-init.sh" ]] && source "$HOME/.sdkman/bin/sdkman-init.sh""#,
+    ];
+
+    #[derive(Clone)]
     pub struct SdkmanImage(ServerImage);
 
     impl SdkmanImage {
@@ -343,22 +720,22 @@ pub mod sdkman {
 
     impl Install for SdkmanImage {
         fn install(&self) -> Result<(), String> {
-            println!("Fetching SDKMAN!");
+            info!("Fetching SDKMAN!");
 
-            let bash_cmd = format!("curl --proto '=https' --tlsv1.2 -sSf {} | bash", self.0.package().fetch.url());
+            let bash_cmd = format!("{} | bash", self.0.package().fetch.curl_script());
             let output = exec_cmd("bash", &["-c", &bash_cmd])
                 .map_err(|output| output.to_string())?;
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
             // sdk is not a program but a bash function declared in
             // sdkman-init.sh, so that script must be sourced first before
             // calling the command.
             // .bashrc should work as well to load the sdk function into the
             // bash session.
-            println!("Initializing SDKMAN!");
+            info!("Initializing SDKMAN!");
 
             let bash_cmd = "source ~/.sdkman/bin/sdkman-init.sh && sdk version";
             let output = exec_cmd("bash", &["-c", &bash_cmd])
@@ -366,13 +743,13 @@ pub mod sdkman {
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
             if !output.stderr.is_empty() {
-                println!("Source .bashrc (error): {}", String::from_utf8_lossy(&output.stderr));
+                verbose!("Source .bashrc (error): {}", String::from_utf8_lossy(&output.stderr));
             }
 
-            println!("SDKMAN! installed.");
+            info!("SDKMAN! installed.");
 
             Ok(())
         }
@@ -384,38 +761,132 @@ pub mod sdkman {
                 .map(|home| Path::new(&home).join(".sdkman"))
                 .map_err(|output| output.to_string())?;
 
-            println!("Removing SDKMAN! files...");
+            info!("Removing SDKMAN! files...");
 
             fs::remove_dir_all(sdkman_dir)
                 .map_err(|output| output.to_string())?;
 
-            println!("Removing environment variables...");
-
-            // It deletes the lines from ~/.bashrc
-            // #THIS MUST BE AT THE END OF THE FILE FOR SDKMAN TO WORK!!!
-            // export SDKMAN_DIR="$HOME/.sdkman"
-            // [[ -s "$HOME/.sdkman/bin/sdkman-init.sh" ]] && source "$HOME/.sdkman/bin/sdkman-init.sh"
-            //
+            info!("Removing environment variables...");
 
-            let prof = env::var("HOME")
-                .map(|home| Path::new(&home).join(".bashrc"))
+            // It deletes the lines from ~/.bashrc, or also ~/.zshrc and
+            // ~/.bash_profile on macOS
+            let home = env::var("HOME")
                 .map_err(|output| output.to_string())?;
 
-            let clean_profile_pattern = r#"/#THIS MUST BE AT THE END OF THE FILE FOR SDKMAN TO WORK!!!/d; /export SDKMAN_DIR="\$HOME\/.sdkman"/d; /\[\[ -s "\$HOME\/.sdkman\/bin\/sdkman-init.sh" \]\] && source "\$HOME\/.sdkman\/bin\/sdkman-init.sh"/d"#;
-            let output = exec_cmd("sed", &["-i", clean_profile_pattern, prof.to_str().unwrap()])
-                .map_err(|output| output.to_string())?;
+            for rc in self.0.package().os.shell_rc_files()? {
+                remove_lines(&Path::new(&home).join(rc), &RC_LINES)?;
+            }
 
-            let stdout = String::from_utf8_lossy(&output.stdout);
+            info!("SDKMAN! uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for SdkmanImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::home_dir(".sdkman")
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct SdkmanConfig {
+        auto_answer: bool,
+        selfupdate: bool,
+        offline_mode: bool,
+    }
+
+    type SdkmanImageConfig = ImageConfig<SdkmanImage, SdkmanConfig>;
+
+    impl ToImageConfig<SdkmanConfig> for SdkmanImage {
+        fn to_image_config(&self, config: SdkmanConfig) -> SdkmanImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for SdkmanImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let SdkmanConfig { auto_answer, selfupdate, offline_mode } = self.1;
+
+            // sdkman-init.sh sources the config file, so the offline mode
+            // goes as the variable `sdk offline` toggles for the session
+            let options = [
+                ("sdkman_auto_answer", auto_answer),
+                ("sdkman_selfupdate_feature", selfupdate),
+                ("SDKMAN_OFFLINE_MODE", offline_mode),
+            ];
+
+            let config_file = env::var("HOME")
+                .map(|home| Path::new(&home).join(".sdkman/etc/config"))
+                .map_err(|error| error.to_string())?;
+
+            info!("Writing SDKMAN! config options...");
 
-            println!("{}", stdout);
+            let contents = fs::read_to_string(&config_file)
+                .map_err(|error| format!("Fail to read {}: {error}", config_file.display()))?;
 
-            println!("SDKMAN! uninstalled.");
+            fs::write(&config_file, set_options(&contents, &options))
+                .map_err(|error| format!("Fail to write {}: {error}", config_file.display()))?;
+
+            info!("SDKMAN! configured.");
 
             Ok(())
         }
     }
 
-    impl ImageOps for SdkmanImage { image_ops_impl!(); }
+    fn set_options(contents: &str, options: &[(&str, bool)]) -> String {
+        let option_key = |line: &str| line
+            .split_once('=')
+            .map(|(key, _)| key.trim().to_string());
+
+        let option_of = |line: &str| option_key(line)
+            .and_then(|key| options.iter().find(|(option, _)| *option == key));
+
+        let mut lines = contents
+            .lines()
+            .map(|line| match option_of(line) {
+                Some((key, value)) => format!("{key}={value}"),
+                None => line.to_string(),
+            })
+            .collect::<Vec<String>>();
+
+        for (key, value) in options {
+            if !contents.lines().any(|line| option_key(line).as_deref() == Some(key)) {
+                lines.push(format!("{key}={value}"));
+            }
+        }
+
+        lines.iter().map(|line| format!("{line}\n")).collect()
+    }
+
+    pub fn exec_sdk_cmd(sdk_cmd: &str) -> Result<Output, String> {
+        let bash_cmd = format!("source ~/.sdkman/bin/sdkman-init.sh && {}", sdk_cmd);
+
+        exec_cmd("bash", &["-c", &bash_cmd])
+            .map_err(|error| error.to_string())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::image::server::sdkman::set_options;
+
+        #[test]
+        fn sets_config_options() {
+            let contents = "sdkman_auto_answer=false\nsdkman_colour_enable=true\nsdkman_selfupdate_feature=true\n";
+            let options = [
+                ("sdkman_auto_answer", true),
+                ("sdkman_selfupdate_feature", false),
+                ("SDKMAN_OFFLINE_MODE", true),
+            ];
+
+            assert_eq!(
+                "sdkman_auto_answer=true\nsdkman_colour_enable=true\nsdkman_selfupdate_feature=false\nSDKMAN_OFFLINE_MODE=true\n",
+                set_options(contents, &options),
+            );
+        }
+    }
 }
 
 pub mod java {
@@ -426,6 +897,7 @@ pub mod java {
     use crate::image::server::ServerImage;
     use crate::image::server::ServerImageId::Java;
     use crate::image::Image;
+    use crate::image::detection::Detection;
     use crate::image::{ImageOps, Install, Uninstall};
     use crate::image_ops_impl;
     use crate::os::Os;
@@ -457,7 +929,7 @@ pub mod java {
 
     impl Install for JavaImage {
         fn install(&self) -> Result<(), String> {
-            println!("Installing Java via SDKMAN!");
+            info!("Installing Java via SDKMAN!");
 
             let sdk_cmd = format!("sdk install java {}", self.0.package().software.version);
             let bash_cmd = format!("source ~/.sdkman/bin/sdkman-init.sh && {}", sdk_cmd);
@@ -466,9 +938,9 @@ pub mod java {
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
-            println!("Java installed");
+            info!("Java installed");
 
             Ok(())
         }
@@ -476,7 +948,7 @@ pub mod java {
 
     impl Uninstall for JavaImage {
         fn uninstall(&self) -> Result<(), String> {
-            println!("Uninstalling Java via SDKMAN!");
+            info!("Uninstalling Java via SDKMAN!");
 
             let sdk_cmd = format!("sdk uninstall java {} --force", self.0.package().software.version);
             let bash_cmd = format!("source ~/.sdkman/bin/sdkman-init.sh && {}", sdk_cmd);
@@ -485,18 +957,28 @@ pub mod java {
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
-            println!("Java uninstalled");
+            info!("Java uninstalled");
 
             Ok(())
         }
     }
 
-    impl ImageOps for JavaImage { image_ops_impl!(); }
+    impl ImageOps for JavaImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("java", &["-version"])
+        }
+    }
 }
 
 pub mod gradle {
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+
     use reqwest::Url;
     use serde::{Deserialize, Serialize};
 
@@ -504,7 +986,7 @@ pub mod gradle {
     use crate::image::server::ServerImage;
     use crate::image::server::ServerImageId::Gradle;
     use crate::image::Image;
-    use crate::image::{ImageOps, Install, Uninstall};
+    use crate::image::{Config, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
     use crate::image_ops_impl;
     use crate::os::Os;
     use crate::package::{Package, SemVer, Software};
@@ -514,6 +996,7 @@ pub mod gradle {
         version: SemVer,
     }
 
+    #[derive(Clone)]
     pub struct GradleImage(ServerImage, SemVer);
 
     impl GradleImage {
@@ -548,7 +1031,7 @@ pub mod gradle {
 
     impl Install for GradleImage {
         fn install(&self) -> Result<(), String> {
-            println!("Installing Gradle via SDKMAN!");
+            info!("Installing Gradle via SDKMAN!");
 
             let version = self.get_normalized_version();
             let sdk_cmd = format!("sdk install gradle {version}");
@@ -558,9 +1041,9 @@ pub mod gradle {
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
-            println!("Gradle installed");
+            info!("Gradle installed");
 
             Ok(())
         }
@@ -568,7 +1051,7 @@ pub mod gradle {
 
     impl Uninstall for GradleImage {
         fn uninstall(&self) -> Result<(), String> {
-            println!("Uninstalling Gradle via SDKMAN!");
+            info!("Uninstalling Gradle via SDKMAN!");
 
             let version = self.get_normalized_version();
             let sdk_cmd = format!("sdk uninstall gradle {version} --force");
@@ -578,420 +1061,1029 @@ pub mod gradle {
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
-            println!("Gradle uninstalled");
+            info!("Gradle uninstalled");
 
             Ok(())
         }
     }
 
-    impl ImageOps for GradleImage { image_ops_impl!(); }
-}
-
-pub mod nvm {
-    use std::path::Path;
-    use std::{env, fs};
-
-    use reqwest::Url;
-    use serde::{Deserialize, Serialize};
+    impl ImageOps for GradleImage {
+        image_ops_impl!();
 
-    use crate::cmd::exec_cmd;
-    use crate::download::{DownloadRequest, Integrity};
-    use crate::image::server::ServerImage;
-    use crate::image::server::ServerImageId::Nvm;
-    use crate::image::{Image, ImageOps, Install, Uninstall};
-    use crate::image_ops_impl;
-    use crate::os::Os;
-    use crate::package::{Package, SemVer, Software};
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".gradle")]
+        }
+    }
 
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct NvmInfo {
-        version: SemVer,
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct GradleProxy {
+        host: String,
+        port: u16,
+        non_proxy_hosts: Vec<String>,
     }
 
-    pub struct NvmImage(ServerImage);
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct GradleConfig {
+        jvm_args: String,
+        proxy: Option<GradleProxy>,
+        properties: BTreeMap<String, String>,
+    }
 
-    impl NvmImage {
-        pub fn new(os: Os, NvmInfo { version }: NvmInfo) -> Self {
-            let id = Nvm;
-            let pkg_id = id.to_string();
-            let fetch_url = format!("https://raw.githubusercontent.com/nvm-sh/nvm/v{}/install.sh", version);
+    impl GradleConfig {
+        fn to_properties(&self) -> Vec<(String, String)> {
+            let mut properties = vec![];
 
-            NvmImage(
-                ServerImage(
-                    id,
-                    Package::new(
-                        &pkg_id.as_str(),
-                        os,
-                        Software::new("nvm.sh", "NVM (Node Version Manager)", &version.to_string()),
-                        Url::parse("https://github.com/nvm-sh/nvm").unwrap(),
-                        DownloadRequest::new(&fetch_url, Integrity::None).unwrap(),
-                    ),
-                )
-            )
-        }
-    }
+            if !self.jvm_args.trim().is_empty() {
+                properties.push(("org.gradle.jvmargs".to_string(), self.jvm_args.clone()));
+            }
 
-    impl Install for NvmImage {
-        fn install(&self) -> Result<(), String> {
-            println!("Fetching and installing NVM.");
+            if let Some(GradleProxy { host, port, non_proxy_hosts }) = &self.proxy {
+                for scheme in ["http", "https"] {
+                    properties.push((format!("systemProp.{scheme}.proxyHost"), host.clone()));
+                    properties.push((format!("systemProp.{scheme}.proxyPort"), port.to_string()));
+                }
 
-            let bash_cmd = format!("curl --proto '=https' --tlsv1.2 -sSf -o- {} | bash", self.0.package().fetch.url());
-            let output = exec_cmd("bash", &["-c", &bash_cmd])
-                .map_err(|output| output.to_string())?;
+                // The JVM reads the HTTP one for HTTPS too
+                if !non_proxy_hosts.is_empty() {
+                    properties.push(("systemProp.http.nonProxyHosts".to_string(), non_proxy_hosts.join("|")));
+                }
+            }
 
-            let stdout = String::from_utf8_lossy(&output.stdout);
+            properties.extend(self.properties.clone());
 
-            println!("{}", stdout);
+            properties
+        }
+    }
 
-            println!("NVM installed.");
+    type GradleImageConfig = ImageConfig<GradleImage, GradleConfig>;
 
-            Ok(())
+    impl ToImageConfig<GradleConfig> for GradleImage {
+        fn to_image_config(&self, config: GradleConfig) -> GradleImageConfig {
+            ImageConfig(self.clone(), config)
         }
     }
 
-    impl Uninstall for NvmImage {
-        fn uninstall(&self) -> Result<(), String> {
-            let nvm_dir = env::var("HOME")
-                .map(|home| Path::new(&home).join(".nvm"))
-                .map_err(|output| output.to_string())?;
+    impl Config for GradleImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let properties = self.1.to_properties();
 
-            println!("Unloading NVM...");
+            properties
+                .iter()
+                .try_for_each(|(key, value)| check_property(key, value))?;
 
-            let nvm_cmd = "source ~/.nvm/nvm.sh && nvm unload";
+            let gradle_dir = env::var("HOME")
+                .map(|home| Path::new(&home).join(".gradle"))
+                .map_err(|error| error.to_string())?;
 
-            let output = exec_cmd("bash", &["-c", nvm_cmd])
-                .map_err(|output| output.to_string())?;
+            let properties_file = gradle_dir.join("gradle.properties");
 
-            let stdout = String::from_utf8_lossy(&output.stdout);
+            info!("Writing Gradle properties...");
 
-            println!("{}", stdout);
+            // The file may not exist before the first build
+            let contents = fs::read_to_string(&properties_file).unwrap_or_default();
 
-            println!("Deleting NVM files...");
+            fs::create_dir_all(&gradle_dir)
+                .and_then(|_| fs::write(&properties_file, set_properties(&contents, &properties)))
+                .map_err(|error| format!("Fail to write {}: {error}", properties_file.display()))?;
 
-            fs::remove_dir_all(nvm_dir)
-                .map_err(|output| output.to_string())?;
+            info!("Gradle configured.");
 
-            println!("Removing environment variables...");
+            Ok(())
+        }
+    }
 
-            // It deletes the lines from ~/.bashrc
-            // export NVM_DIR="$HOME/.nvm"
-            // [ -s "$NVM_DIR/nvm.sh" ] && \. "$NVM_DIR/nvm.sh"  # This loads nvm
-            // [ -s "$NVM_DIR/bash_completion" ] && \. "$NVM_DIR/bash_completion"  # This loads nvm bash_completion
+    fn check_property(key: &str, value: &str) -> Result<(), String> {
+        let valid = !key.is_empty()
+            && !key.chars().any(|c| c.is_whitespace() || matches!(c, '=' | ':'))
+            && !value.contains(['\n', '\r']);
 
-            let prof = env::var("HOME")
-                .map(|home| Path::new(&home).join(".bashrc"))
-                .map_err(|output| output.to_string())?;
-
-            let clean_profile_pattern = r#"
-                /export NVM_DIR="\$HOME\/.nvm"/d;
-                /\[ -s "\$NVM_DIR\/nvm.sh" \] && \\. "\$NVM_DIR\/nvm.sh"/d;
-                /\[ -s "\$NVM_DIR\/bash_completion" \] && \\. "\$NVM_DIR\/bash_completion"/d
-            "#.trim();
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Invalid Gradle property {key}"))
+        }
+    }
 
-            let output = exec_cmd("sed", &["-i", clean_profile_pattern, prof.to_str().unwrap()])
-                .map_err(|output| output.to_string())?;
+    fn set_properties(contents: &str, properties: &[(String, String)]) -> String {
+        let property_key = |line: &str| line
+            .split_once('=')
+            .map(|(key, _)| key.trim().to_string());
+
+        let property_of = |line: &str| property_key(line)
+            .and_then(|key| properties.iter().find(|(property, _)| *property == key));
+
+        let mut lines = contents
+            .lines()
+            .map(|line| match property_of(line) {
+                Some((key, value)) => format!("{key}={value}"),
+                None => line.to_string(),
+            })
+            .collect::<Vec<String>>();
+
+        for (key, value) in properties {
+            if !contents.lines().any(|line| property_key(line).as_ref() == Some(key)) {
+                lines.push(format!("{key}={value}"));
+            }
+        }
 
-            let stdout = String::from_utf8_lossy(&output.stdout);
+        lines.iter().map(|line| format!("{line}\n")).collect()
+    }
 
-            println!("{}", stdout);
+    #[cfg(test)]
+    mod tests {
+        use std::collections::BTreeMap;
+
+        use crate::image::server::gradle::{check_property, set_properties, GradleConfig, GradleProxy};
+
+        #[test]
+        fn writes_gradle_properties() {
+            let config = GradleConfig {
+                jvm_args: "-Xmx2g".to_string(),
+                proxy: Some(GradleProxy {
+                    host: "proxy.example.com".to_string(),
+                    port: 3128,
+                    non_proxy_hosts: vec!["localhost".to_string(), "*.internal".to_string()],
+                }),
+                properties: BTreeMap::from([("mavenMirrorUrl".to_string(), "https://repo.example.com/maven2".to_string())]),
+            };
 
-            println!("NVM uninstalled.");
+            let contents = "org.gradle.jvmargs=-Xmx512m\norg.gradle.parallel=true\n";
+
+            assert_eq!(
+                "org.gradle.jvmargs=-Xmx2g
+org.gradle.parallel=true
+systemProp.http.proxyHost=proxy.example.com
+systemProp.http.proxyPort=3128
+systemProp.https.proxyHost=proxy.example.com
+systemProp.https.proxyPort=3128
+systemProp.http.nonProxyHosts=localhost|*.internal
+mavenMirrorUrl=https://repo.example.com/maven2
+",
+                set_properties(contents, &config.to_properties()),
+            );
+        }
 
-            Ok(())
+        #[test]
+        fn checks_gradle_property() {
+            assert!(check_property("org.gradle.jvmargs", "-Xmx2g -Dfile.encoding=UTF-8").is_ok());
+            assert!(check_property("", "value").is_err());
+            assert!(check_property("a=b", "value").is_err());
+            assert!(check_property("key", "value\nother=value").is_err());
         }
     }
-
-    impl ImageOps for NvmImage { image_ops_impl!(); }
 }
 
-pub mod node {
+pub mod kotlin {
     use reqwest::Url;
     use serde::{Deserialize, Serialize};
 
-    use crate::cmd::exec_cmd;
+    use crate::image::server::sdkman::exec_sdk_cmd;
     use crate::image::server::ServerImage;
-    use crate::image::server::ServerImageId::Node;
+    use crate::image::server::ServerImageId::Kotlin;
     use crate::image::Image;
+    use crate::image::detection::Detection;
     use crate::image::{ImageOps, Install, Uninstall};
     use crate::image_ops_impl;
     use crate::os::Os;
     use crate::package::{Package, SemVer, Software};
 
     #[derive(Debug, Serialize, Deserialize)]
-    pub struct NodeInfo {
-        version: SemVer, // TODO supports latest version too
+    pub struct KotlinInfo {
+        version: SemVer,
     }
 
-    pub struct NodeImage(ServerImage);
+    pub struct KotlinImage(ServerImage);
 
-    impl NodeImage {
-        pub fn new(os: Os, NodeInfo { version }: NodeInfo) -> Self {
-            let id = Node;
+    impl KotlinImage {
+        pub fn new(os: Os, KotlinInfo { version }: KotlinInfo) -> Self {
+            let id = Kotlin;
             let pkg_name = id.to_string();
 
-            NodeImage(ServerImage(
-                id,
-                Package::new_managed(
-                    &pkg_name,
-                    os,
-                    Software::new("OpenJS Foundation", "Node.js", &version.to_string()),
-                    Url::parse("https://nodejs.org/en").unwrap(),
+            KotlinImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("JetBrains s.r.o.", "Kotlin", &version.to_string()),
+                        Url::parse("https://kotlinlang.org/docs/command-line.html").unwrap(),
+                    ),
                 ),
-            ))
+            )
         }
     }
 
-    impl Install for NodeImage {
+    impl Install for KotlinImage {
         fn install(&self) -> Result<(), String> {
-            println!("Installing Node via NVM.");
+            info!("Installing Kotlin via SDKMAN!");
 
-            let nvm_cmd = format!("nvm install {}", self.0.package().software.version);
-            let bash_cmd = format!("source ~/.nvm/nvm.sh && {}", nvm_cmd);
-            let output = exec_cmd("bash", &["-c", &bash_cmd])
-                .map_err(|error| error.to_string())?;
+            let sdk_cmd = format!("sdk install kotlin {}", self.0.package().software.version);
+            let output = exec_sdk_cmd(&sdk_cmd)?;
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
+            verbose!("{}", stdout);
 
-            println!("Node installed");
+            info!("Kotlin installed");
 
             Ok(())
         }
     }
 
-    impl Uninstall for NodeImage {
+    impl Uninstall for KotlinImage {
         fn uninstall(&self) -> Result<(), String> {
-            println!("Uninstalling Node via NVM.");
+            info!("Uninstalling Kotlin via SDKMAN!");
 
-            let nvm_cmd = format!("nvm uninstall {}", self.0.package().software.version);
-            let bash_cmd = format!("source ~/.nvm/nvm.sh && {}", nvm_cmd);
-            let output = exec_cmd("bash", &["-c", &bash_cmd])
-                .map_err(|error| error.to_string())?;
+            let sdk_cmd = format!("sdk uninstall kotlin {} --force", self.0.package().software.version);
+            let output = exec_sdk_cmd(&sdk_cmd)?;
 
             let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("{}", stdout);
-
-            println!("Node uninstalled");
+            verbose!("{}", stdout);
 
-            // TODO Consider fail: Cannot uninstall currently-active node version
+            info!("Kotlin uninstalled");
 
             Ok(())
         }
     }
 
-    impl ImageOps for NodeImage { image_ops_impl!(); }
-}
+    impl ImageOps for KotlinImage {
+        image_ops_impl!();
 
-pub mod miniconda {
-    use std::path::Path;
-    use std::process::Output;
-    use std::{env, fs};
+        fn detection(&self) -> Detection {
+            Detection::binary("kotlinc", &["-version"])
+        }
+    }
+}
 
+pub mod scala {
     use reqwest::Url;
     use serde::{Deserialize, Serialize};
 
-    use Os::Linux;
-
-    use crate::cmd::{exec_cmd, print_output};
-    use crate::download::hashing::Hash;
-    use crate::download::hashing::HashAlgorithm::Sha256;
-    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::sdkman::exec_sdk_cmd;
     use crate::image::server::ServerImage;
-    use crate::image::server::ServerImageId::Miniconda;
-    use crate::image::{Config, Image, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::image::server::ServerImageId::Scala;
+    use crate::image::Image;
+    use crate::image::{ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
     use crate::os::Os;
-    use crate::os::OsArch::X64;
     use crate::package::{Package, SemVer, Software};
-    use crate::tmp::TmpWorkingDir;
-    use crate::{cmd, image_ops_impl};
 
-    #[derive(Clone, Debug, Serialize, Deserialize)]
-    pub struct MinicondaInfo {
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct ScalaInfo {
         version: SemVer,
-        hash_sha256: String,
-        python_version: SemVer,
     }
 
-    impl MinicondaInfo {
-        fn url_version(&self) -> String {
-            let SemVer(py_major, py_minor, _) = self.clone().python_version;
-            let py_ver = format!("py{py_major}{py_minor}");
-            let conda_ver = self.clone().version;
-
-            format!("{py_ver}_{conda_ver}")
-        }
-    }
-
-    #[derive(Clone)]
-    pub struct MinicondaImage(ServerImage);
+    pub struct ScalaImage(ServerImage);
 
-    impl MinicondaImage {
-        pub fn new(os: Os, info: MinicondaInfo) -> Self {
-            let MinicondaInfo { version, hash_sha256, .. } = info.clone();
-            let id = Miniconda;
-            let pkg_id = "conda";
-            let url_version = info.url_version();
-            let fetch_url = match os {
-                Linux(X64, _) => format!("https://repo.anaconda.com/miniconda/Miniconda3-{url_version}-0-Linux-x86_64.sh")
-            };
-            let hash = Hash::new(Sha256, hash_sha256);
+    impl ScalaImage {
+        pub fn new(os: Os, ScalaInfo { version }: ScalaInfo) -> Self {
+            let id = Scala;
+            let pkg_name = id.to_string();
 
-            MinicondaImage(
+            ScalaImage(
                 ServerImage(
                     id,
-                    Package::new(
-                        pkg_id,
+                    Package::new_managed(
+                        &pkg_name,
                         os,
-                        Software::new("Anaconda, Inc", "Miniconda", &version.to_string()),
-                        Url::parse("https://docs.anaconda.com/miniconda/miniconda-install").unwrap(),
-                        DownloadRequest::new(&fetch_url, Integrity::Hash(hash)).unwrap(),
+                        Software::new("EPFL", "Scala", &version.to_string()),
+                        Url::parse("https://www.scala-lang.org/download").unwrap(),
                     ),
-                )
+                ),
             )
         }
     }
 
-    impl Install for MinicondaImage {
+    impl Install for ScalaImage {
         fn install(&self) -> Result<(), String> {
-            let tmp = TmpWorkingDir::new()
-                .map_err(|error| error.to_string())?;
+            info!("Installing Scala via SDKMAN!");
 
-            let package = self.0.package();
-            let downloader = Downloader::from(package.fetch.clone(), &tmp);
-            let installer_file = downloader.path.clone();
+            let sdk_cmd = format!("sdk install scala {}", self.0.package().software.version);
+            let output = exec_sdk_cmd(&sdk_cmd)?;
 
-            println!("Downloading Miniconda installer...");
+            let stdout = String::from_utf8_lossy(&output.stdout);
 
-            downloader
-                .download_blocking()
-                .map_err(|error| error.to_string())?;
+            verbose!("{}", stdout);
 
-            println!("Installing Miniconda...");
+            info!("Scala installed");
 
-            let miniconda_dir = env::var("HOME")
-                .map(|home| Path::new(&home).join("miniconda3"))
-                .map_err(|output| output.to_string())?;
+            Ok(())
+        }
+    }
 
-            let output = exec_cmd(
-                "bash",
-                &[
-                    installer_file.to_str().unwrap(),
-                    "-b",
-                    "-u",
-                    "-p",
-                    miniconda_dir.to_str().unwrap()
-                ],
-            ).map_err(|error| error.to_string())?;
+    impl Uninstall for ScalaImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Uninstalling Scala via SDKMAN!");
 
-            print_output(output);
+            let sdk_cmd = format!("sdk uninstall scala {} --force", self.0.package().software.version);
+            let output = exec_sdk_cmd(&sdk_cmd)?;
 
-            println!("Miniconda installed.");
+            let stdout = String::from_utf8_lossy(&output.stdout);
 
-            println!("Initializing miniconda.");
+            verbose!("{}", stdout);
 
-            let conda = miniconda_dir.join("bin").join("conda");
-            let output = exec_cmd(
-                conda.to_str().unwrap(),
-                &["init", "bash"],
-            ).map_err(|error| error.to_string())?;
+            info!("Scala uninstalled");
 
-            print_output(output);
+            Ok(())
+        }
+    }
 
-            let conda = miniconda_dir.join("bin").join("conda");
-            let output = exec_cmd(
-                conda.to_str().unwrap(),
-                &["init", "zsh"],
-            ).map_err(|error| error.to_string())?;
+    impl ImageOps for ScalaImage { image_ops_impl!(); }
+}
 
-            print_output(output);
+pub mod sbt {
+    use std::path::PathBuf;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
 
-            println!("Miniconda installed and initialized.");
+    use crate::image::server::sdkman::exec_sdk_cmd;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Sbt;
+    use crate::image::Image;
+    use crate::image::detection::Detection;
+    use crate::image::{ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
 
-            Ok(())
-        }
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct SbtInfo {
+        version: SemVer,
     }
 
-    impl Uninstall for MinicondaImage {
-        fn uninstall(&self) -> Result<(), String> {
-            let miniconda_dir = env::var("HOME")
-                .map(|home| Path::new(&home).join("miniconda3"))
-                .map_err(|output| output.to_string())?;
+    pub struct SbtImage(ServerImage);
 
-            let print_optional_step = |output: cmd::Result<Output>| match output {
-                Ok(o) => {
-                    print_output(o);
-                }
-                Err(error) => {
-                    eprintln!("Fail to remove conda initialization scripts (optional step): {}", error);
-                }
-            };
+    impl SbtImage {
+        pub fn new(os: Os, SbtInfo { version }: SbtInfo) -> Self {
+            let id = Sbt;
+            let pkg_name = id.to_string();
 
-            println!("Removing conda initialization scripts (optional step)...");
+            SbtImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("Scala Center", "sbt", &version.to_string()),
+                        Url::parse("https://www.scala-sbt.org/download").unwrap(),
+                    ),
+                ),
+            )
+        }
+    }
 
-            let output = exec_cmd(
-                "conda",
-                &["init", "--reverse", "--all"],
-            );
+    impl Install for SbtImage {
+        fn install(&self) -> Result<(), String> {
+            info!("Installing sbt via SDKMAN!");
 
-            print_optional_step(output);
+            let sdk_cmd = format!("sdk install sbt {}", self.0.package().software.version);
+            let output = exec_sdk_cmd(&sdk_cmd)?;
 
-            println!("Removing Miniconda files...");
+            let stdout = String::from_utf8_lossy(&output.stdout);
 
-            fs::remove_dir_all(miniconda_dir)
-                .map_err(|output| output.to_string())?;
+            verbose!("{}", stdout);
 
-            println!("Miniconda uninstalled.");
+            info!("sbt installed");
 
             Ok(())
         }
     }
 
-    impl ImageOps for MinicondaImage { image_ops_impl!(); }
+    impl Uninstall for SbtImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Uninstalling sbt via SDKMAN!");
 
-    #[derive(Clone, Debug, Serialize, Deserialize)]
-    pub struct MinicondaConfig {
-        env_name: String,
-        packages: Vec<String>,
+            let sdk_cmd = format!("sdk uninstall sbt {} --force", self.0.package().software.version);
+            let output = exec_sdk_cmd(&sdk_cmd)?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            verbose!("{}", stdout);
+
+            info!("sbt uninstalled");
+
+            Ok(())
+        }
     }
 
-    type MinicondaImageConfig = ImageConfig<MinicondaImage, MinicondaConfig>;
+    impl ImageOps for SbtImage {
+        image_ops_impl!();
 
-    impl ToImageConfig<MinicondaConfig> for MinicondaImage {
-        fn to_image_config(&self, config: MinicondaConfig) -> MinicondaImageConfig {
-            ImageConfig(self.clone(), config)
+        fn detection(&self) -> Detection {
+            Detection::binary("sbt", &[])
+        }
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".sbt"), PathBuf::from(".ivy2")]
         }
     }
+}
 
-    impl Config for MinicondaImageConfig {
-        fn config(&self) -> Result<(), String> {
-            let MinicondaConfig { env_name, packages } = self.1.clone();
+pub mod nvm {
+    use std::path::Path;
+    use std::{env, fs};
 
-            println!(
-                "Creating Miniconda environment `{}` with packages {:?}...",
-                env_name,
-                packages,
-            );
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
 
-            let create_env_args = ["create", "-n", &env_name, "--yes"]
-                .iter()
-                .map(|&s| s)
-                .chain(packages.iter().map(String::as_str))
-                .collect::<Vec<&str>>();
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::{DownloadRequest, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Nvm;
+    use crate::image::detection::Detection;
+    use crate::image::{Config, Image, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
 
-            let output = exec_cmd("conda", &create_env_args)
-                .map_err(|error| error.to_string())?;
+    const RC_LINES: [&str; 3] = [
+        r#"export NVM_DIR="$HOME/.nvm""#,
+        r#"[ -s "$NVM_DIR/nvm.sh" ] && \. "$NVM_DIR/nvm.sh"  # This loads nvm"#,
+        r#"[ -s "$NVM_DIR/bash_completion" ] && \. "$NVM_DIR/bash_completion"  # This loads nvm bash_completion"#,
+    ];
+
+    /// Runs `nvm use` when entering a directory with a `.nvmrc` file.
+    const BASH_AUTO_USE_LINES: [&str; 3] = [
+        "# NVM auto-use .nvmrc",
+        r#"_nvm_auto_use() { if [ "$PWD" != "$_NVM_AUTO_USE_DIR" ]; then _NVM_AUTO_USE_DIR="$PWD"; [ -f .nvmrc ] && nvm use --silent; fi; }"#,
+        r#"PROMPT_COMMAND="_nvm_auto_use${PROMPT_COMMAND:+;$PROMPT_COMMAND}""#,
+    ];
+
+    /// zsh has a hook for directory changes, so it doesn't need the prompt.
+    const ZSH_AUTO_USE_LINES: [&str; 4] = [
+        "# NVM auto-use .nvmrc",
+        "autoload -U add-zsh-hook",
+        "_nvm_auto_use() { [ -f .nvmrc ] && nvm use --silent; }",
+        "add-zsh-hook chpwd _nvm_auto_use",
+    ];
 
-            print_output(output);
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct NvmInfo {
+        version: SemVer,
+    }
 
-            println!("Installing Jupyter kernel for `{env_name}`...");
+    #[derive(Clone)]
+    pub struct NvmImage(ServerImage);
 
-            let output = exec_cmd(
-                "conda",
-                &[
+    impl NvmImage {
+        pub fn new(os: Os, NvmInfo { version }: NvmInfo) -> Self {
+            let id = Nvm;
+            let pkg_id = id.to_string();
+            let fetch_url = format!("https://raw.githubusercontent.com/nvm-sh/nvm/v{}/install.sh", version);
+
+            NvmImage(
+                ServerImage(
+                    id,
+                    Package::new(
+                        &pkg_id.as_str(),
+                        os,
+                        Software::new("nvm.sh", "NVM (Node Version Manager)", &version.to_string()),
+                        Url::parse("https://github.com/nvm-sh/nvm").unwrap(),
+                        DownloadRequest::new(&fetch_url, Integrity::None).unwrap(),
+                    ),
+                )
+            )
+        }
+    }
+
+    impl Install for NvmImage {
+        fn install(&self) -> Result<(), String> {
+            info!("Fetching and installing NVM.");
+
+            let bash_cmd = format!("{} | bash", self.0.package().fetch.curl_script());
+            let output = exec_cmd("bash", &["-c", &bash_cmd])
+                .map_err(|output| output.to_string())?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            verbose!("{}", stdout);
+
+            info!("NVM installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for NvmImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let nvm_dir = env::var("HOME")
+                .map(|home| Path::new(&home).join(".nvm"))
+                .map_err(|output| output.to_string())?;
+
+            info!("Unloading NVM...");
+
+            let nvm_cmd = "source ~/.nvm/nvm.sh && nvm unload";
+
+            let output = exec_cmd("bash", &["-c", nvm_cmd])
+                .map_err(|output| output.to_string())?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            verbose!("{}", stdout);
+
+            info!("Deleting NVM files...");
+
+            fs::remove_dir_all(nvm_dir)
+                .map_err(|output| output.to_string())?;
+
+            info!("Removing environment variables...");
+
+            // It deletes the lines from ~/.bashrc, or the init file of the
+            // user's shell on macOS, like ~/.zshrc
+            let home = env::var("HOME")
+                .map_err(|output| output.to_string())?;
+
+            for rc in self.0.package().os.shell_rc_files()? {
+                let rc = Path::new(&home).join(rc);
+
+                remove_lines(&rc, &RC_LINES)?;
+                remove_auto_use(&rc)?;
+            }
+
+            info!("NVM uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for NvmImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::home_dir(".nvm")
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct NvmConfig {
+        node_versions: Vec<String>,
+        default_version: String,
+        auto_use: bool,
+    }
+
+    type NvmImageConfig = ImageConfig<NvmImage, NvmConfig>;
+
+    impl ToImageConfig<NvmConfig> for NvmImage {
+        fn to_image_config(&self, config: NvmConfig) -> NvmImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for NvmImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let NvmConfig { node_versions, default_version, auto_use } = &self.1;
+
+            for version in node_versions {
+                info!("Installing Node {version}...");
+
+                exec_nvm("install", version)?;
+            }
+
+            if !default_version.trim().is_empty() {
+                info!("Setting default Node version {default_version}...");
+
+                exec_nvm("alias default", default_version)?;
+            }
+
+            let home = env::var("HOME")
+                .map_err(|error| error.to_string())?;
+
+            // The hook is removed first, so it's never added twice
+            for rc in self.0.0.package().os.shell_rc_files()? {
+                let rc_path = Path::new(&home).join(rc);
+
+                remove_auto_use(&rc_path)?;
+
+                if *auto_use {
+                    info!("Adding .nvmrc auto-use hook to ~/{rc}...");
+
+                    append_lines(&rc_path, auto_use_lines(rc))?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    fn auto_use_lines(rc: &str) -> &'static [&'static str] {
+        if rc.ends_with(".zshrc") {
+            &ZSH_AUTO_USE_LINES
+        } else {
+            &BASH_AUTO_USE_LINES
+        }
+    }
+
+    fn remove_auto_use(rc_path: &Path) -> Result<(), String> {
+        remove_lines(rc_path, &BASH_AUTO_USE_LINES)?;
+        remove_lines(rc_path, &ZSH_AUTO_USE_LINES)
+    }
+
+    /// The version goes as a positional parameter to keep it verbatim.
+    fn exec_nvm(subcommand: &str, version: &str) -> Result<(), String> {
+        let bash_cmd = format!("source ~/.nvm/nvm.sh && nvm {subcommand} \"$1\"");
+        let output = exec_cmd("bash", &["-c", &bash_cmd, "bash", version])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::image::server::nvm::{auto_use_lines, BASH_AUTO_USE_LINES, ZSH_AUTO_USE_LINES};
+
+        #[test]
+        fn hooks_auto_use_per_shell() {
+            assert_eq!(&BASH_AUTO_USE_LINES, auto_use_lines(".bashrc"));
+            assert_eq!(&BASH_AUTO_USE_LINES, auto_use_lines(".bash_profile"));
+            assert_eq!(&ZSH_AUTO_USE_LINES, auto_use_lines(".zshrc"));
+        }
+    }
+}
+
+pub mod node {
+    use std::path::PathBuf;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Node;
+    use crate::image::Image;
+    use crate::image::{Config, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct NodeInfo {
+        version: SemVer, // TODO supports latest version too
+    }
+
+    #[derive(Clone)]
+    pub struct NodeImage(ServerImage);
+
+    impl NodeImage {
+        pub fn new(os: Os, NodeInfo { version }: NodeInfo) -> Self {
+            let id = Node;
+            let pkg_name = id.to_string();
+
+            NodeImage(ServerImage(
+                id,
+                Package::new_managed(
+                    &pkg_name,
+                    os,
+                    Software::new("OpenJS Foundation", "Node.js", &version.to_string()),
+                    Url::parse("https://nodejs.org/en").unwrap(),
+                ),
+            ))
+        }
+    }
+
+    impl Install for NodeImage {
+        fn install(&self) -> Result<(), String> {
+            info!("Installing Node via NVM.");
+
+            let nvm_cmd = format!("nvm install {}", self.0.package().software.version);
+            let bash_cmd = format!("source ~/.nvm/nvm.sh && {}", nvm_cmd);
+            let output = exec_cmd("bash", &["-c", &bash_cmd])
+                .map_err(|error| error.to_string())?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            verbose!("{}", stdout);
+
+            info!("Node installed");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for NodeImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Uninstalling Node via NVM.");
+
+            let nvm_cmd = format!("nvm uninstall {}", self.0.package().software.version);
+            let bash_cmd = format!("source ~/.nvm/nvm.sh && {}", nvm_cmd);
+            let output = exec_cmd("bash", &["-c", &bash_cmd])
+                .map_err(|error| error.to_string())?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            verbose!("{}", stdout);
+
+            info!("Node uninstalled");
+
+            // TODO Consider fail: Cannot uninstall currently-active node version
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for NodeImage {
+        image_ops_impl!();
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".npm")]
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct NodeConfig {
+        packages: Vec<String>,
+        registry: Option<String>,
+    }
+
+    type NodeImageConfig = ImageConfig<NodeImage, NodeConfig>;
+
+    impl ToImageConfig<NodeConfig> for NodeImage {
+        fn to_image_config(&self, config: NodeConfig) -> NodeImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for NodeImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let NodeConfig { packages, registry } = &self.1;
+
+            if packages.is_empty() {
+                return Ok(());
+            }
+
+            let version = self.0.0.package().software.version;
+            let args = npm_install_args(packages, registry.as_deref())?;
+            let args = args.iter().map(String::as_str).collect::<Vec<&str>>();
+
+            info!("Installing global packages {} for Node {version}...", packages.join(", "));
+
+            // The packages go as positional parameters to keep them verbatim
+            let bash_cmd = format!("source ~/.nvm/nvm.sh && nvm exec {version} npm install --global \"$@\"");
+            let output = exec_cmd("bash", &[&["-c", &bash_cmd, "bash"], args.as_slice()].concat())
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            Ok(())
+        }
+    }
+
+    fn npm_install_args(packages: &[String], registry: Option<&str>) -> Result<Vec<String>, String> {
+        let registry_arg = registry
+            .map(|registry| Url::parse(registry)
+                .map(|url| format!("--registry={url}"))
+                .map_err(|error| format!("Invalid npm registry {registry}: {error}"))
+            )
+            .transpose()?;
+
+        Ok(registry_arg.into_iter().chain(packages.iter().cloned()).collect())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::image::server::node::npm_install_args;
+
+        #[test]
+        fn overrides_registry() {
+            let packages = vec!["typescript".to_string(), "pnpm@9".to_string()];
+
+            assert_eq!(Ok(packages.clone()), npm_install_args(&packages, None));
+            assert_eq!(
+                Ok(vec!["--registry=https://npm.example.com/".to_string(), "typescript".to_string(), "pnpm@9".to_string()]),
+                npm_install_args(&packages, Some("https://npm.example.com")),
+            );
+            assert!(npm_install_args(&packages, Some("not a url")).is_err());
+        }
+    }
+}
+
+pub mod miniconda {
+    use std::path::PathBuf;
+    use std::path::Path;
+    use std::process::Output;
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use Os::Linux;
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::hashing::Hash;
+    use crate::download::hashing::HashAlgorithm::Sha256;
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Miniconda;
+    use crate::image::detection::Detection;
+    use crate::image::{Config, Image, ImageConfig, ImageInfoError, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::os::Os;
+    use crate::os::OsArch::X64;
+    use crate::package::{Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+    use crate::{cmd, image_ops_impl};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct MinicondaInfo {
+        version: SemVer,
+        hash_sha256: String,
+        python_version: SemVer,
+    }
+
+    impl MinicondaInfo {
+        fn url_version(&self) -> String {
+            let SemVer(py_major, py_minor, _) = self.clone().python_version;
+            let py_ver = format!("py{py_major}{py_minor}");
+            let conda_ver = self.clone().version;
+
+            format!("{py_ver}_{conda_ver}")
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct MinicondaImage(ServerImage);
+
+    impl MinicondaImage {
+        pub fn new(os: Os, info: MinicondaInfo) -> Result<Self, ImageInfoError> {
+            let MinicondaInfo { version, hash_sha256, .. } = info.clone();
+            let id = Miniconda;
+            let pkg_id = "conda";
+            let url_version = info.url_version();
+            let fetch_url = match os {
+                Linux(X64, _) => format!("https://repo.anaconda.com/miniconda/Miniconda3-{url_version}-0-Linux-x86_64.sh"),
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+            };
+            let hash = Hash::new(Sha256, hash_sha256);
+
+            Ok(MinicondaImage(
+                ServerImage(
+                    id,
+                    Package::new(
+                        pkg_id,
+                        os,
+                        Software::new("Anaconda, Inc", "Miniconda", &version.to_string()),
+                        Url::parse("https://docs.anaconda.com/miniconda/miniconda-install").unwrap(),
+                        DownloadRequest::new(&fetch_url, Integrity::Hash(hash)).unwrap(),
+                    ),
+                )
+            ))
+        }
+    }
+
+    impl Install for MinicondaImage {
+        fn install(&self) -> Result<(), String> {
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let package = self.0.package();
+            let downloader = Downloader::from(package.fetch.clone(), &tmp);
+            let installer_file = downloader.path.clone();
+
+            info!("Downloading Miniconda installer...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Installing Miniconda...");
+
+            let miniconda_dir = env::var("HOME")
+                .map(|home| Path::new(&home).join("miniconda3"))
+                .map_err(|output| output.to_string())?;
+
+            let output = exec_cmd(
+                "bash",
+                &[
+                    installer_file.to_str().unwrap(),
+                    "-b",
+                    "-u",
+                    "-p",
+                    miniconda_dir.to_str().unwrap()
+                ],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Miniconda installed.");
+
+            info!("Initializing miniconda.");
+
+            let conda = miniconda_dir.join("bin").join("conda");
+            let output = exec_cmd(
+                conda.to_str().unwrap(),
+                &["init", "bash"],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            let conda = miniconda_dir.join("bin").join("conda");
+            let output = exec_cmd(
+                conda.to_str().unwrap(),
+                &["init", "zsh"],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Miniconda installed and initialized.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for MinicondaImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let miniconda_dir = env::var("HOME")
+                .map(|home| Path::new(&home).join("miniconda3"))
+                .map_err(|output| output.to_string())?;
+
+            let print_optional_step = |output: cmd::Result<Output>| match output {
+                Ok(o) => {
+                    print_output(o);
+                }
+                Err(error) => {
+                    eprintln!("Fail to remove conda initialization scripts (optional step): {}", error);
+                }
+            };
+
+            info!("Removing conda initialization scripts (optional step)...");
+
+            let output = exec_cmd(
+                "conda",
+                &["init", "--reverse", "--all"],
+            );
+
+            print_optional_step(output);
+
+            info!("Removing Miniconda files...");
+
+            fs::remove_dir_all(miniconda_dir)
+                .map_err(|output| output.to_string())?;
+
+            info!("Miniconda uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for MinicondaImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::home_dir("miniconda3")
+        }
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".conda"), PathBuf::from(".condarc")]
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct MinicondaConfig {
+        env_name: String,
+        packages: Vec<String>,
+    }
+
+    type MinicondaImageConfig = ImageConfig<MinicondaImage, MinicondaConfig>;
+
+    impl ToImageConfig<MinicondaConfig> for MinicondaImage {
+        fn to_image_config(&self, config: MinicondaConfig) -> MinicondaImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for MinicondaImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let MinicondaConfig { env_name, packages } = self.1.clone();
+
+            info!(
+                "Creating Miniconda environment `{}` with packages {:?}...",
+                env_name,
+                packages,
+            );
+
+            let create_env_args = ["create", "-n", &env_name, "--yes"]
+                .iter()
+                .map(|&s| s)
+                .chain(packages.iter().map(String::as_str))
+                .collect::<Vec<&str>>();
+
+            let output = exec_cmd("conda", &create_env_args)
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Installing Jupyter kernel for `{env_name}`...");
+
+            let output = exec_cmd(
+                "conda",
+                &[
                     "run",
                     "-n",
                     &env_name,
@@ -1005,11 +2097,3629 @@ pub mod miniconda {
                 ],
             ).map_err(|error| error.to_string())?;
 
-            print_output(output);
+            print_output(output);
+
+            Ok(())
+        }
+    }
+}
+
+pub mod ghcup {
+    use std::env;
+    use std::path::{Path, PathBuf};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::{DownloadRequest, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Ghcup;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, has_line, remove_lines};
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, SemVerRev, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct GhcupInfo {
+        ghc_version: SemVer,
+        cabal_version: SemVerRev,
+    }
+
+    pub struct GhcupImage(ServerImage, GhcupInfo);
+
+    /// The image manages this line instead of the GHCup bootstrap script.
+    const GHCUP_ENV_LINE: &str = r#"[ -f "$HOME/.ghcup/env" ] && . "$HOME/.ghcup/env" # ghcup-env"#;
+
+    impl GhcupImage {
+        pub fn new(os: Os, info: GhcupInfo) -> Self {
+            let id = Ghcup;
+            let pkg_id = id.to_string();
+            let fetch_url = "https://get-ghcup.haskell.org";
+
+            GhcupImage(
+                ServerImage(
+                    id,
+                    Package::new(
+                        &pkg_id,
+                        os,
+                        Software::new("Haskell.org", "GHCup", &info.ghc_version.to_string()),
+                        Url::parse("https://www.haskell.org/ghcup/install").unwrap(),
+                        DownloadRequest::new(fetch_url, Integrity::None).unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+    }
+
+    fn shell_init_files() -> Result<Vec<PathBuf>, String> {
+        let home = env::var("HOME")
+            .map(PathBuf::from)
+            .map_err(|error| error.to_string())?;
+
+        let zshrc = home.join(".zshrc");
+        let mut files = vec![home.join(".bashrc")];
+
+        if zshrc.exists() {
+            files.push(zshrc);
+        }
+
+        Ok(files)
+    }
+
+    impl Install for GhcupImage {
+        fn install(&self) -> Result<(), String> {
+            let GhcupInfo { ghc_version, cabal_version } = &self.1;
+
+            info!("Fetching and running GHCup bootstrap (GHC {ghc_version}, cabal {cabal_version})...");
+
+            let bash_cmd = format!(
+                "{} | \
+                BOOTSTRAP_HASKELL_NONINTERACTIVE=1 \
+                BOOTSTRAP_HASKELL_GHC_VERSION={ghc_version} \
+                BOOTSTRAP_HASKELL_CABAL_VERSION={cabal_version} \
+                BOOTSTRAP_HASKELL_INSTALL_NO_STACK=1 \
+                sh",
+                self.0.package().fetch.curl_script(),
+            );
+            let output = exec_cmd("bash", &["-c", &bash_cmd])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Updating shell init files...");
+
+            // Reinstalls find the line already there
+            for init_file in shell_init_files()? {
+                if !has_line(&init_file, GHCUP_ENV_LINE) {
+                    append_lines(&init_file, &[GHCUP_ENV_LINE])?;
+                }
+            }
+
+            let output = exec_cmd(
+                "bash",
+                &["-c", r#"source "$HOME/.ghcup/env" && ghc --version && cabal --version"#],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("GHCup installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for GhcupImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing GHCup files...");
+
+            let ghcup_bin = env::var("HOME")
+                .map(|home| Path::new(&home).join(".ghcup").join("bin").join("ghcup"))
+                .map_err(|error| error.to_string())?;
+
+            let output = exec_cmd(ghcup_bin.to_str().unwrap(), &["nuke"])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Cleaning shell init files...");
+
+            for init_file in shell_init_files()? {
+                remove_lines(&init_file, &[GHCUP_ENV_LINE])?;
+            }
+
+            info!("GHCup uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for GhcupImage { image_ops_impl!(); }
+}
+
+pub mod elixir {
+    use std::path::PathBuf;
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Elixir;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct ElixirInfo {
+        asdf_version: SemVer,
+        erlang_version: SemVer,
+        elixir_version: SemVer,
+    }
+
+    impl ElixirInfo {
+        /// Elixir builds are tied to an OTP major release, e.g.,
+        /// `1.17.2-otp-27`.
+        fn asdf_elixir_version(&self) -> String {
+            let SemVer(otp_major, ..) = self.erlang_version;
+
+            format!("{}-otp-{otp_major}", self.elixir_version)
+        }
+    }
+
+    const ERLANG_BUILD_DEPS: [&str; 8] = [
+        "build-essential",
+        "autoconf",
+        "m4",
+        "libncurses-dev",
+        "libssl-dev",
+        "libxml2-utils",
+        "xsltproc",
+        "unzip",
+    ];
+
+    const ASDF_ENV_LINES: [&str; 2] = [
+        "# asdf (Erlang/Elixir)",
+        r#". "$HOME/.asdf/asdf.sh""#,
+    ];
+
+    pub struct ElixirImage(ServerImage, ElixirInfo);
+
+    impl ElixirImage {
+        pub fn new(os: Os, info: ElixirInfo) -> Self {
+            let id = Elixir;
+            let pkg_name = id.to_string();
+
+            ElixirImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("The Elixir Team", "Elixir", &info.asdf_elixir_version()),
+                        Url::parse("https://elixir-lang.org/install.html").unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+    }
+
+    fn home_dir() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(PathBuf::from)
+            .map_err(|error| error.to_string())
+    }
+
+    fn exec_asdf_cmd(asdf_cmd: &str) -> Result<(), String> {
+        let bash_cmd = format!(r#"source "$HOME/.asdf/asdf.sh" && {asdf_cmd}"#);
+        let output = exec_cmd("bash", &["-c", &bash_cmd])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    impl Install for ElixirImage {
+        fn install(&self) -> Result<(), String> {
+            let info = &self.1;
+            let home = home_dir()?;
+            let asdf_dir = home.join(".asdf");
+
+            info!("Installing Erlang build dependencies via APT...");
+
+            let apt_args = ["apt-get", "--yes", "install"]
+                .into_iter()
+                .chain(ERLANG_BUILD_DEPS)
+                .collect::<Vec<&str>>();
+
+            let output = exec_cmd("sudo", &apt_args)
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            if !asdf_dir.exists() {
+                info!("Cloning asdf v{}...", info.asdf_version);
+
+                let branch = format!("v{}", info.asdf_version);
+                let output = exec_cmd(
+                    "git",
+                    &[
+                        "clone",
+                        "https://github.com/asdf-vm/asdf.git",
+                        asdf_dir.to_str().unwrap(),
+                        "--branch",
+                        &branch,
+                    ],
+                ).map_err(|error| error.to_string())?;
+
+                print_output(output);
+
+                append_lines(&home.join(".bashrc"), &ASDF_ENV_LINES)?;
+            }
+
+            info!("Installing Erlang/OTP {}...", info.erlang_version);
+
+            exec_asdf_cmd("asdf plugin add erlang || true")?;
+            exec_asdf_cmd(&format!("asdf install erlang {}", info.erlang_version))?;
+            exec_asdf_cmd(&format!("asdf global erlang {}", info.erlang_version))?;
+
+            info!("Installing Elixir {}...", info.asdf_elixir_version());
+
+            exec_asdf_cmd("asdf plugin add elixir || true")?;
+            exec_asdf_cmd(&format!("asdf install elixir {}", info.asdf_elixir_version()))?;
+            exec_asdf_cmd(&format!("asdf global elixir {}", info.asdf_elixir_version()))?;
+
+            exec_asdf_cmd("elixir --version")?;
+
+            info!("Erlang/OTP and Elixir installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for ElixirImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let info = &self.1;
+            let home = home_dir()?;
+            let elixir_version = info.asdf_elixir_version();
+            let erlang_version = info.erlang_version.to_string();
+
+            info!("Uninstalling Elixir and Erlang/OTP via asdf...");
+
+            exec_asdf_cmd(&format!("asdf uninstall elixir {elixir_version}"))?;
+            exec_asdf_cmd(&format!("asdf uninstall erlang {erlang_version}"))?;
+            exec_asdf_cmd("asdf plugin remove elixir")?;
+            exec_asdf_cmd("asdf plugin remove erlang")?;
+
+            let tool_versions = [
+                format!("elixir {elixir_version}"),
+                format!("erlang {erlang_version}"),
+            ];
+            let tool_versions = tool_versions.iter().map(String::as_str).collect::<Vec<&str>>();
+
+            remove_lines(&home.join(".tool-versions"), &tool_versions)?;
+
+            // Only remove asdf itself if no other tool depends on it
+            let plugins_dir = home.join(".asdf").join("plugins");
+            let has_other_plugins = fs::read_dir(&plugins_dir)
+                .map(|mut entries| entries.next().is_some())
+                .unwrap_or(false);
+
+            if has_other_plugins {
+                info!("Keeping asdf since other plugins are installed.");
+            } else {
+                info!("Removing asdf files...");
+
+                fs::remove_dir_all(home.join(".asdf"))
+                    .map_err(|error| error.to_string())?;
+
+                info!("Removing environment variables...");
+
+                remove_lines(&home.join(".bashrc"), &ASDF_ENV_LINES)?;
+            }
+
+            info!("Erlang/OTP and Elixir uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for ElixirImage { image_ops_impl!(); }
+}
+
+pub mod dotnet {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Dotnet;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{MajorMinor, Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct DotnetInfo {
+        channel: MajorMinor,
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "microsoft-prod";
+
+    /// APT must prefer the Microsoft packages over the Ubuntu ones.
+    const APT_PREFERENCES: &str = "Package: dotnet* aspnet* netstandard*\n\
+    Pin: origin \"packages.microsoft.com\"\n\
+    Pin-Priority: 999\n";
+
+    const APT_PREFERENCES_PATH: &str = "/etc/apt/preferences.d/microsoft-dotnet.pref";
+
+    pub struct DotnetImage(ServerImage, GpgKey);
+
+    impl DotnetImage {
+        pub fn new(os: Os, DotnetInfo { channel, key_fingerprint }: DotnetInfo) -> Self {
+            let id = Dotnet;
+            let pkg_name = format!("dotnet-sdk-{channel}");
+            let key_url = Url::parse("https://packages.microsoft.com/keys/microsoft.asc").unwrap();
+
+            DotnetImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("Microsoft Corporation", ".NET SDK", &channel.to_string()),
+                        Url::parse("https://learn.microsoft.com/en-us/dotnet/core/install/linux-ubuntu").unwrap(),
+                    ),
+                ),
+                GpgKey::new(key_url, key_fingerprint),
+            )
+        }
+    }
+
+    impl Install for DotnetImage {
+        fn install(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            apt_repo::add(
+                REPO_NAME,
+                &self.1,
+                &format!(
+                    "https://packages.microsoft.com/{}/{}/prod {} main",
+                    package.os.distro_id(),
+                    apt_repo::RELEASE,
+                    apt_repo::CODENAME,
+                ),
+            )?;
+
+            info!("Preferring Microsoft .NET packages...");
+
+            apt::write_file(APT_PREFERENCES_PATH, APT_PREFERENCES)?;
+
+            info!("Installing {}...", package.name);
+
+            apt::install(&[&package.name])?;
+
+            info!(".NET SDK installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for DotnetImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            info!("Uninstalling {}...", package.name);
+
+            apt::remove(&[&package.name])?;
+            apt::delete_file(APT_PREFERENCES_PATH)?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!(".NET SDK uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for DotnetImage { image_ops_impl!(); }
+}
+
+pub mod php {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::gpg::GpgKey;
+    use crate::download::hashing::HashAlgorithm::Sha384;
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Php;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::LinuxType::Ubuntu;
+    use crate::os::Os::Linux;
+    use crate::os::Os;
+    use crate::package::{MajorMinor, Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct PhpInfo {
+        version: MajorMinor,
+        composer_version: SemVer,
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "ondrej-php";
+
+    const COMPOSER_BIN: &str = "/usr/local/bin/composer";
+
+    pub struct PhpImage(ServerImage, PhpInfo);
+
+    impl PhpImage {
+        pub fn new(os: Os, info: PhpInfo) -> Self {
+            let id = Php;
+            let pkg_name = format!("php{}", info.version);
+
+            PhpImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("The PHP Group", "PHP", &info.version.to_string()),
+                        Url::parse("https://launchpad.net/~ondrej/+archive/ubuntu/php").unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+
+        fn apt_packages(&self) -> Vec<String> {
+            let php = self.0.package().name;
+
+            ["cli", "common", "curl", "mbstring", "xml", "zip"]
+                .iter()
+                .map(|ext| format!("{php}-{ext}"))
+                .collect()
+        }
+
+        fn repo_key(&self) -> GpgKey {
+            let key_url = Url::parse(&format!(
+                "https://keyserver.ubuntu.com/pks/lookup?op=get&search=0x{}",
+                self.1.key_fingerprint.replace(' ', ""),
+            )).unwrap();
+
+            GpgKey::new(key_url, self.1.key_fingerprint.clone())
+        }
+
+        /// Composer publishes the SHA-384 of its installer apart from it.
+        fn composer_installer_request(&self) -> DownloadRequest {
+            let integrity = Integrity::RemoteHash {
+                algorithm: Sha384,
+                url: Url::parse("https://composer.github.io/installer.sig").unwrap(),
+            };
+
+            self.0.package().download_request(
+                DownloadRequest::new("https://getcomposer.org/installer", integrity).unwrap()
+            )
+        }
+
+        fn install_composer(&self) -> Result<(), String> {
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let req = self.composer_installer_request();
+            let installer_file = tmp.join("composer-setup.php".as_ref());
+            let downloader = Downloader::new(req, installer_file.clone());
+
+            info!("Downloading Composer installer...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Installing Composer {}...", self.1.composer_version);
+
+            let version_arg = format!("--version={}", self.1.composer_version);
+            let output = exec_cmd(
+                "sudo",
+                &[
+                    "php",
+                    installer_file.to_str().unwrap(),
+                    "--install-dir=/usr/local/bin",
+                    "--filename=composer",
+                    &version_arg,
+                ],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            Ok(())
+        }
+    }
+
+    impl Install for PhpImage {
+        fn install(&self) -> Result<(), String> {
+            if !matches!(self.0.package().os, Linux(_, Ubuntu)) {
+                return Err("PHP installs from the Ondřej Surý PPA, which only serves Ubuntu".to_string());
+            }
+
+            apt_repo::add(
+                REPO_NAME,
+                &self.repo_key(),
+                &format!("https://ppa.launchpadcontent.net/ondrej/php/ubuntu {} main", apt_repo::CODENAME),
+            )?;
+
+            info!("Installing PHP {}...", self.1.version);
+
+            let packages = self.apt_packages();
+            let packages = packages.iter().map(String::as_str).collect::<Vec<&str>>();
+
+            apt::install(&packages)?;
+
+            self.install_composer()?;
+
+            info!("PHP and Composer installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for PhpImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing Composer...");
+
+            let output = exec_cmd("sudo", &["rm", "-f", COMPOSER_BIN])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Uninstalling PHP {}...", self.1.version);
+
+            let packages = self.apt_packages();
+            let packages = packages.iter().map(String::as_str).collect::<Vec<&str>>();
+
+            apt::remove(&packages)?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("PHP and Composer uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for PhpImage {
+        image_ops_impl!();
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.composer_installer_request()])
+        }
+    }
+}
+
+pub mod rbenv {
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Rbenv;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct RbenvInfo {
+        version: SemVer,
+        ruby_build_version: u32,
+    }
+
+    const RUBY_BUILD_DEPS: [&str; 11] = [
+        "autoconf",
+        "patch",
+        "build-essential",
+        "rustc",
+        "libssl-dev",
+        "libyaml-dev",
+        "libreadline6-dev",
+        "zlib1g-dev",
+        "libgmp-dev",
+        "libncurses5-dev",
+        "libffi-dev",
+    ];
+
+    const RBENV_ENV_LINES: [&str; 2] = [
+        "# rbenv",
+        r#"eval "$(~/.rbenv/bin/rbenv init - bash)""#,
+    ];
+
+    pub struct RbenvImage(ServerImage, RbenvInfo);
+
+    impl RbenvImage {
+        pub fn new(os: Os, info: RbenvInfo) -> Self {
+            let id = Rbenv;
+            let pkg_name = id.to_string();
+
+            RbenvImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("rbenv", "rbenv", &info.version.to_string()),
+                        Url::parse("https://github.com/rbenv/rbenv").unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+    }
+
+    fn rbenv_dir() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(|home| PathBuf::from(home).join(".rbenv"))
+            .map_err(|error| error.to_string())
+    }
+
+    fn git_clone(repo: &str, tag: &str, dir: &Path) -> Result<(), String> {
+        let output = exec_cmd(
+            "git",
+            &["clone", "--depth", "1", "--branch", tag, repo, dir.to_str().unwrap()],
+        ).map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    impl Install for RbenvImage {
+        fn install(&self) -> Result<(), String> {
+            let RbenvInfo { version, ruby_build_version } = &self.1;
+            let rbenv_dir = rbenv_dir()?;
+
+            info!("Installing Ruby build dependencies via APT...");
+
+            apt::install(&RUBY_BUILD_DEPS)?;
+
+            info!("Cloning rbenv v{version}...");
+
+            git_clone(
+                "https://github.com/rbenv/rbenv.git",
+                &format!("v{version}"),
+                &rbenv_dir,
+            )?;
+
+            info!("Cloning ruby-build v{ruby_build_version}...");
+
+            git_clone(
+                "https://github.com/rbenv/ruby-build.git",
+                &format!("v{ruby_build_version}"),
+                &rbenv_dir.join("plugins").join("ruby-build"),
+            )?;
+
+            info!("Updating shell init file...");
+
+            let bashrc = env::var("HOME")
+                .map(|home| PathBuf::from(home).join(".bashrc"))
+                .map_err(|error| error.to_string())?;
+
+            append_lines(&bashrc, &RBENV_ENV_LINES)?;
+
+            info!("rbenv installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for RbenvImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing rbenv files...");
+
+            fs::remove_dir_all(rbenv_dir()?)
+                .map_err(|error| error.to_string())?;
+
+            info!("Removing environment variables...");
+
+            let bashrc = env::var("HOME")
+                .map(|home| PathBuf::from(home).join(".bashrc"))
+                .map_err(|error| error.to_string())?;
+
+            remove_lines(&bashrc, &RBENV_ENV_LINES)?;
+
+            info!("rbenv uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for RbenvImage { image_ops_impl!(); }
+
+    pub fn exec_rbenv_cmd(rbenv_cmd: &str) -> Result<(), String> {
+        let bash_cmd = format!(r#"eval "$(~/.rbenv/bin/rbenv init - bash)" && {rbenv_cmd}"#);
+        let output = exec_cmd("bash", &["-c", &bash_cmd])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+}
+
+pub mod ruby {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::image::server::rbenv::exec_rbenv_cmd;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Ruby;
+    use crate::image::Image;
+    use crate::image::{ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct RubyInfo {
+        version: SemVer,
+    }
+
+    pub struct RubyImage(ServerImage);
+
+    impl RubyImage {
+        pub fn new(os: Os, RubyInfo { version }: RubyInfo) -> Self {
+            let id = Ruby;
+            let pkg_name = id.to_string();
+
+            RubyImage(ServerImage(
+                id,
+                Package::new_managed(
+                    &pkg_name,
+                    os,
+                    Software::new("Ruby Association", "Ruby", &version.to_string()),
+                    Url::parse("https://www.ruby-lang.org/en/documentation/installation").unwrap(),
+                ),
+            ))
+        }
+    }
+
+    impl Install for RubyImage {
+        fn install(&self) -> Result<(), String> {
+            let version = self.0.package().software.version;
+
+            info!("Installing Ruby via rbenv (it builds from source, so it may take a while)...");
+
+            exec_rbenv_cmd(&format!("rbenv install --skip-existing {version}"))?;
+            exec_rbenv_cmd(&format!("rbenv global {version}"))?;
+            exec_rbenv_cmd("ruby --version")?;
+
+            info!("Ruby installed");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for RubyImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let version = self.0.package().software.version;
+
+            info!("Uninstalling Ruby via rbenv...");
+
+            exec_rbenv_cmd(&format!("rbenv uninstall --force {version}"))?;
+
+            info!("Ruby uninstalled");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for RubyImage { image_ops_impl!(); }
+}
+
+pub mod r {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::R;
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::LinuxType::Ubuntu;
+    use crate::os::Os::Linux;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct RInfo {
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "cran-r";
+
+    pub struct RImage(ServerImage, GpgKey);
+
+    impl RImage {
+        pub fn new(os: Os, RInfo { key_fingerprint }: RInfo) -> Self {
+            let id = R;
+            let pkg_name = "r-base";
+            let version = "latest";
+            let key_url = Url::parse("https://cloud.r-project.org/bin/linux/ubuntu/marutter_pubkey.asc").unwrap();
+
+            RImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        pkg_name,
+                        os,
+                        Software::new("The R Foundation", "R", version),
+                        Url::parse("https://cloud.r-project.org/bin/linux/ubuntu").unwrap(),
+                    ),
+                ),
+                GpgKey::new(key_url, key_fingerprint),
+            )
+        }
+    }
+
+    impl Install for RImage {
+        fn install(&self) -> Result<(), String> {
+            if !matches!(self.0.package().os, Linux(_, Ubuntu)) {
+                return Err("R installs from the CRAN Ubuntu repository, whose key doesn't sign the Debian one".to_string());
+            }
+
+            apt_repo::add(
+                REPO_NAME,
+                &self.1,
+                &format!("https://cloud.r-project.org/bin/linux/ubuntu {}-cran40/", apt_repo::CODENAME),
+            )?;
+
+            info!("Installing R...");
+
+            apt::install(&[&self.0.package().name])?;
+
+            info!("R installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for RImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Uninstalling R...");
+
+            apt::remove(&[&self.0.package().name])?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("R uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for RImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("R", &["--version"])
+        }
+    }
+}
+
+pub mod julia {
+    use std::path::PathBuf;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Julia;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::os::OsArch::X64;
+    use crate::package::{Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct JuliaInfo {
+        version: SemVer,
+    }
+
+    const JULIA_DIR: &str = "/opt/julia";
+
+    const JULIA_BIN_LINK: &str = "/usr/local/bin/julia";
+
+    pub struct JuliaImage(ServerImage);
+
+    impl JuliaImage {
+        pub fn new(os: Os, JuliaInfo { version }: JuliaInfo) -> Result<Self, ImageInfoError> {
+            let id = Julia;
+            let SemVer(major, minor, _) = version;
+            let fetch_url = match os {
+                Linux(X64, _) => format!("https://julialang-s3.julialang.org/bin/linux/x64/{major}.{minor}/julia-{version}-linux-x86_64.tar.gz"),
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+            };
+            let checksum_url = Url::parse(&format!("https://julialang-s3.julialang.org/bin/checksums/julia-{version}.sha256")).unwrap();
+
+            Ok(JuliaImage(
+                ServerImage(
+                    id.clone(),
+                    Package::new(
+                        &id.to_string(),
+                        os,
+                        Software::new("The Julia Project", "Julia", &version.to_string()),
+                        Url::parse("https://julialang.org/downloads/platform").unwrap(),
+                        DownloadRequest::new(&fetch_url, Integrity::RemoteSha256 { url: checksum_url }).unwrap(),
+                    ))))
+        }
+    }
+
+    impl Install for JuliaImage {
+        fn install(&self) -> Result<(), String> {
+            let package = self.0.package();
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let downloader = Downloader::from(package.fetch.clone(), &tmp);
+            let tar_file = downloader.path.clone();
+
+            info!("Downloading Julia...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Unpacking Julia into {JULIA_DIR}...");
+
+            remove_julia_files()?;
+
+            let output = exec_cmd("sudo", &["mkdir", "-p", JULIA_DIR])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            let output = exec_cmd(
+                "sudo",
+                &["tar", "-xzf", tar_file.to_str().unwrap(), "-C", JULIA_DIR, "--strip-components=1"],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Linking Julia binary...");
+
+            let julia_bin = format!("{JULIA_DIR}/bin/julia");
+            let output = exec_cmd("sudo", &["ln", "-sf", &julia_bin, JULIA_BIN_LINK])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            let output = exec_cmd(JULIA_BIN_LINK, &["--version"])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Julia installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for JuliaImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing Julia files...");
+
+            remove_julia_files()?;
+
+            info!("Julia uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for JuliaImage {
+        image_ops_impl!();
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".julia")]
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+
+    fn remove_julia_files() -> Result<(), String> {
+        let output = exec_cmd("sudo", &["rm", "-rf", JULIA_DIR, JULIA_BIN_LINK])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+}
+
+pub mod zig {
+    use std::path::PathBuf;
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::minisign::MinisignKey;
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Zig;
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::os::OsArch::X64;
+    use crate::package::{Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct ZigInfo {
+        version: SemVer,
+        minisign_public_key: MinisignKey,
+    }
+
+    const ZIG_PROFILE_LINES: [&str; 2] = [
+        "# Zig",
+        r#"export PATH="$PATH:$HOME/.local/opt/zig""#,
+    ];
+
+    pub struct ZigImage(ServerImage);
+
+    impl ZigImage {
+        pub fn new(os: Os, ZigInfo { version, minisign_public_key }: ZigInfo) -> Result<Self, ImageInfoError> {
+            let id = Zig;
+            let fetch_url = match os {
+                Linux(X64, _) => format!("https://ziglang.org/download/{version}/zig-linux-x86_64-{version}.tar.xz"),
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+            };
+            let signature_url = Url::parse(&format!("{fetch_url}.minisig")).unwrap();
+
+            Ok(ZigImage(
+                ServerImage(
+                    id.clone(),
+                    Package::new(
+                        &id.to_string(),
+                        os,
+                        Software::new("Zig Software Foundation", "Zig", &version.to_string()),
+                        Url::parse("https://ziglang.org/learn/getting-started").unwrap(),
+                        DownloadRequest::new(&fetch_url, Integrity::minisign(minisign_public_key, signature_url)).unwrap(),
+                    ))))
+        }
+    }
+
+    fn home() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(PathBuf::from)
+            .map_err(|error| error.to_string())
+    }
+
+    fn zig_dir() -> Result<PathBuf, String> {
+        home().map(|home| home.join(".local").join("opt").join("zig"))
+    }
+
+    impl Install for ZigImage {
+        fn install(&self) -> Result<(), String> {
+            let package = self.0.package();
+            let zig_dir = zig_dir()?;
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let downloader = Downloader::from(package.fetch.clone(), &tmp);
+            let tar_file = downloader.path.clone();
+
+            info!("Downloading Zig...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Unpacking Zig...");
+
+            if zig_dir.exists() {
+                fs::remove_dir_all(&zig_dir)
+                    .map_err(|error| error.to_string())?;
+            }
+
+            fs::create_dir_all(&zig_dir)
+                .map_err(|error| error.to_string())?;
+
+            let output = exec_cmd(
+                "tar",
+                &["-xJf", tar_file.to_str().unwrap(), "-C", zig_dir.to_str().unwrap(), "--strip-components=1"],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Updating environment variable...");
+
+            append_lines(&home()?.join(".profile"), &ZIG_PROFILE_LINES)?;
+
+            let output = exec_cmd(
+                "bash",
+                &["-c", "source ~/.profile && zig version"],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Zig installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for ZigImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing Zig files...");
+
+            fs::remove_dir_all(zig_dir()?)
+                .map_err(|error| error.to_string())?;
+
+            info!("Cleaning environment variable...");
+
+            remove_lines(&home()?.join(".profile"), &ZIG_PROFILE_LINES)?;
+
+            info!("Zig uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for ZigImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("zig", &["version"])
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+}
+
+pub mod flutter {
+    use std::path::PathBuf;
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Flutter;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::os::OsArch::X64;
+    use crate::package::{Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct FlutterInfo {
+        version: SemVer,
+    }
+
+    const RELEASES_URL: &str = "https://storage.googleapis.com/flutter_infra_release/releases/releases_linux.json";
+
+    const FLUTTER_PROFILE_LINES: [&str; 2] = [
+        "# Flutter",
+        r#"export PATH="$PATH:$HOME/development/flutter/bin""#,
+    ];
+
+    pub struct FlutterImage(ServerImage);
+
+    impl FlutterImage {
+        pub fn new(os: Os, FlutterInfo { version }: FlutterInfo) -> Result<Self, ImageInfoError> {
+            let id = Flutter;
+            let fetch_url = match os {
+                Linux(X64, _) => format!("https://storage.googleapis.com/flutter_infra_release/releases/stable/linux/flutter_linux_{version}-stable.tar.xz"),
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+            };
+            let integrity = Integrity::ReleasesIndex { url: Url::parse(RELEASES_URL).unwrap() };
+
+            Ok(FlutterImage(
+                ServerImage(
+                    id.clone(),
+                    Package::new(
+                        &id.to_string(),
+                        os,
+                        Software::new("Google", "Flutter", &version.to_string()),
+                        Url::parse("https://docs.flutter.dev/get-started/install/linux").unwrap(),
+                        DownloadRequest::new(&fetch_url, integrity).unwrap(),
+                    ))))
+        }
+    }
+
+    fn home() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(PathBuf::from)
+            .map_err(|error| error.to_string())
+    }
+
+    fn development_dir() -> Result<PathBuf, String> {
+        home().map(|home| home.join("development"))
+    }
+
+    impl Install for FlutterImage {
+        fn install(&self) -> Result<(), String> {
+            let development_dir = development_dir()?;
+            let flutter_dir = development_dir.join("flutter");
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let downloader = Downloader::from(self.0.package().fetch, &tmp);
+            let tar_file = downloader.path.clone();
+
+            info!("Downloading Flutter SDK...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Unpacking Flutter SDK...");
+
+            if flutter_dir.exists() {
+                fs::remove_dir_all(&flutter_dir)
+                    .map_err(|error| error.to_string())?;
+            }
+
+            fs::create_dir_all(&development_dir)
+                .map_err(|error| error.to_string())?;
+
+            let output = exec_cmd(
+                "tar",
+                &["-xJf", tar_file.to_str().unwrap(), "-C", development_dir.to_str().unwrap()],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Updating environment variable...");
+
+            append_lines(&home()?.join(".profile"), &FLUTTER_PROFILE_LINES)?;
+
+            info!("Downloading Flutter development binaries...");
+
+            let output = exec_cmd(
+                "bash",
+                &["-c", "source ~/.profile && flutter precache && flutter --version"],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Flutter SDK installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for FlutterImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing Flutter SDK files...");
+
+            fs::remove_dir_all(development_dir()?.join("flutter"))
+                .map_err(|error| error.to_string())?;
+
+            let pub_cache = home()?.join(".pub-cache");
+
+            if pub_cache.exists() {
+                fs::remove_dir_all(pub_cache)
+                    .map_err(|error| error.to_string())?;
+            }
+
+            info!("Cleaning environment variable...");
+
+            remove_lines(&home()?.join(".profile"), &FLUTTER_PROFILE_LINES)?;
+
+            info!("Flutter SDK uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for FlutterImage {
+        image_ops_impl!();
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+}
+
+pub mod texlive {
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::TexLive;
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::os::OsArch::X64;
+    use crate::package::{Package, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct TexLiveInfo {
+        scheme: String,
+        packages: Vec<String>,
+    }
+
+    const TEXLIVE_PROFILE_LINES: [&str; 2] = [
+        "# TeX Live",
+        r#"export PATH="$PATH:$HOME/.local/opt/texlive/bin/x86_64-linux""#,
+    ];
+
+    pub struct TexLiveImage(ServerImage, TexLiveInfo);
+
+    impl TexLiveImage {
+        pub fn new(os: Os, info: TexLiveInfo) -> Result<Self, ImageInfoError> {
+            let id = TexLive;
+            let fetch_url = match os {
+                Linux(X64, _) => "https://mirror.ctan.org/systems/texlive/tlnet/install-tl-unx.tar.gz",
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+            };
+
+            // The net installer is rebuilt daily and always installs the
+            // current release, while it verifies the downloaded packages
+            // against the TeX Live GPG signature by itself.
+            Ok(TexLiveImage(
+                ServerImage(
+                    id.clone(),
+                    Package::new(
+                        &id.to_string(),
+                        os,
+                        Software::new("TeX Users Group", "TeX Live", "latest"),
+                        Url::parse("https://tug.org/texlive/quickinstall.html").unwrap(),
+                        DownloadRequest::new(fetch_url, Integrity::None).unwrap(),
+                    )),
+                info,
+            ))
+        }
+    }
+
+    fn home() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(PathBuf::from)
+            .map_err(|error| error.to_string())
+    }
+
+    fn texlive_dir() -> Result<PathBuf, String> {
+        home().map(|home| home.join(".local").join("opt").join("texlive"))
+    }
+
+    fn installation_profile(scheme: &str, texlive_dir: &Path, home: &Path) -> String {
+        let texdir = texlive_dir.display();
+
+        format!(
+            "selected_scheme {scheme}
+TEXDIR {texdir}
+TEXMFLOCAL {texdir}/texmf-local
+TEXMFSYSCONFIG {texdir}/texmf-config
+TEXMFSYSVAR {texdir}/texmf-var
+TEXMFHOME {}
+instopt_adjustpath 0
+instopt_adjustrepo 1
+tlpdbopt_autobackup 0
+tlpdbopt_install_docfiles 0
+tlpdbopt_install_srcfiles 0
+",
+            home.join("texmf").display(),
+        )
+    }
+
+    impl Install for TexLiveImage {
+        fn install(&self) -> Result<(), String> {
+            let TexLiveInfo { scheme, packages } = &self.1;
+            let texlive_dir = texlive_dir()?;
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let tmp_path = tmp.path();
+            let downloader = Downloader::from(self.0.package().fetch, &tmp);
+            let tar_file = downloader.path.clone();
+
+            info!("Downloading TeX Live installer...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Extracting TeX Live installer...");
+
+            let installer_dir = tmp_path.join("install-tl");
+
+            fs::create_dir_all(&installer_dir)
+                .map_err(|error| error.to_string())?;
+
+            let output = exec_cmd(
+                "tar",
+                &[
+                    "-xzf",
+                    tar_file.to_str().unwrap(),
+                    "--directory",
+                    installer_dir.to_str().unwrap(),
+                    "--strip-components=1",
+                ],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            let profile_file = tmp_path.join("texlive.profile");
+            let profile = installation_profile(scheme, &texlive_dir, &home()?);
+
+            fs::write(&profile_file, profile)
+                .map_err(|error| error.to_string())?;
+
+            info!("Installing TeX Live ({scheme}), this may take a while...");
+
+            let output = exec_cmd(
+                "perl",
+                &[
+                    installer_dir.join("install-tl").to_str().unwrap(),
+                    &format!("--profile={}", profile_file.to_str().unwrap()),
+                ],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Updating environment variable...");
+
+            append_lines(&home()?.join(".profile"), &TEXLIVE_PROFILE_LINES)?;
+
+            if !packages.is_empty() {
+                info!("Installing TeX Live packages {}...", packages.join(", "));
+
+                let tlmgr_cmd = format!("source ~/.profile && tlmgr install {}", packages.join(" "));
+                let output = exec_cmd("bash", &["-c", &tlmgr_cmd])
+                    .map_err(|error| error.to_string())?;
+
+                print_output(output);
+            }
+
+            info!("TeX Live installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for TexLiveImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing TeX Live files...");
+
+            fs::remove_dir_all(texlive_dir()?)
+                .map_err(|error| error.to_string())?;
+
+            info!("Cleaning environment variable...");
+
+            remove_lines(&home()?.join(".profile"), &TEXLIVE_PROFILE_LINES)?;
+
+            info!("TeX Live uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for TexLiveImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::home_dir(".local/opt/texlive")
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+}
+
+pub mod ffmpeg {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::hashing::Hash;
+    use crate::download::hashing::HashAlgorithm::Sha256;
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Ffmpeg;
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::os::OsArch::X64;
+    use crate::package::{Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    /// Distribution package, or a pinned static build for a newer version.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(tag = "source", rename_all = "snake_case")]
+    pub enum FfmpegInfo {
+        Distro,
+        Static { version: SemVer, hash_sha256: String },
+    }
+
+    const BINARIES: [&str; 2] = ["ffmpeg", "ffprobe"];
+
+    const BIN_DIR: &str = "/usr/local/bin";
+
+    pub struct FfmpegImage(ServerImage, FfmpegInfo);
+
+    impl FfmpegImage {
+        pub fn new(os: Os, info: FfmpegInfo) -> Result<Self, ImageInfoError> {
+            let id = Ffmpeg;
+            let pkg_name = id.to_string();
+            let doc = Url::parse("https://ffmpeg.org/download.html").unwrap();
+            let package = match &info {
+                FfmpegInfo::Distro => Package::new_managed(
+                    &pkg_name,
+                    os,
+                    Software::new("FFmpeg team", "FFmpeg", "distro"),
+                    doc,
+                ),
+                FfmpegInfo::Static { version, hash_sha256 } => {
+                    let fetch_url = match os {
+                        Linux(X64, _) => format!("https://johnvansickle.com/ffmpeg/releases/ffmpeg-{version}-amd64-static.tar.xz"),
+                        _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+                    };
+                    let hash = Hash::new(Sha256, hash_sha256.clone());
+
+                    Package::new(
+                        &pkg_name,
+                        os,
+                        Software::new("FFmpeg team", "FFmpeg", &version.to_string()),
+                        doc,
+                        DownloadRequest::new(&fetch_url, Integrity::Hash(hash)).unwrap(),
+                    )
+                }
+            };
+
+            Ok(FfmpegImage(ServerImage(id, package), info))
+        }
+
+        fn install_static(&self) -> Result<(), String> {
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let tmp_path = tmp.path();
+            let downloader = Downloader::from(self.0.package().fetch, &tmp);
+            let tar_file = downloader.path.clone();
+
+            info!("Downloading FFmpeg static build...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Extracting FFmpeg static build...");
+
+            let output = exec_cmd(
+                "tar",
+                &[
+                    "-xJf",
+                    tar_file.to_str().unwrap(),
+                    "--directory",
+                    tmp_path.to_str().unwrap(),
+                    "--strip-components=1",
+                ],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Copying FFmpeg binaries to {BIN_DIR}...");
+
+            for bin in BINARIES {
+                let output = exec_cmd(
+                    "sudo",
+                    &["install", "-m", "0755", tmp_path.join(bin).to_str().unwrap(), BIN_DIR],
+                ).map_err(|error| error.to_string())?;
+
+                print_output(output);
+            }
+
+            Ok(())
+        }
+
+        fn uninstall_static(&self) -> Result<(), String> {
+            info!("Removing FFmpeg binaries from {BIN_DIR}...");
+
+            let bin_files = BINARIES
+                .iter()
+                .map(|bin| format!("{BIN_DIR}/{bin}"))
+                .collect::<Vec<_>>();
+
+            let args = ["rm", "-f"]
+                .into_iter()
+                .chain(bin_files.iter().map(String::as_str))
+                .collect::<Vec<&str>>();
+
+            let output = exec_cmd("sudo", &args)
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            Ok(())
+        }
+    }
+
+    impl Install for FfmpegImage {
+        fn install(&self) -> Result<(), String> {
+            match self.1 {
+                FfmpegInfo::Distro => {
+                    info!("Installing FFmpeg from the distribution repository...");
+
+                    apt::install(&["ffmpeg"])?;
+                }
+                FfmpegInfo::Static { .. } => self.install_static()?,
+            }
+
+            info!("FFmpeg installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for FfmpegImage {
+        fn uninstall(&self) -> Result<(), String> {
+            match self.1 {
+                FfmpegInfo::Distro => {
+                    info!("Uninstalling FFmpeg...");
+
+                    apt::remove(&["ffmpeg"])?;
+                }
+                FfmpegInfo::Static { .. } => self.uninstall_static()?,
+            }
+
+            info!("FFmpeg uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for FfmpegImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("ffmpeg", &["-version"])
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+}
+
+pub mod imagemagick {
+    use std::path::PathBuf;
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::hashing::Hash;
+    use crate::download::hashing::HashAlgorithm::Sha256;
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::ImageMagick;
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::package::{Package, SemVerVendor, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    /// Distribution package, or built from a pinned release for version 7.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(tag = "source", rename_all = "snake_case")]
+    pub enum ImageMagickInfo {
+        Distro,
+        Release { version: SemVerVendor, hash_sha256: String },
+    }
+
+    const BUILD_DEPS: [&str; 9] = [
+        "build-essential",
+        "pkg-config",
+        "libjpeg-dev",
+        "libpng-dev",
+        "libtiff-dev",
+        "libwebp-dev",
+        "libfreetype-dev",
+        "liblcms2-dev",
+        "libxml2-dev",
+    ];
+
+    pub struct ImageMagickImage(ServerImage, ImageMagickInfo);
+
+    impl ImageMagickImage {
+        pub fn new(os: Os, info: ImageMagickInfo) -> Result<Self, ImageInfoError> {
+            let id = ImageMagick;
+            let pkg_name = id.to_string();
+            let doc = Url::parse("https://imagemagick.org/script/install-source.php").unwrap();
+            let package = match &info {
+                ImageMagickInfo::Distro => Package::new_managed(
+                    &pkg_name,
+                    os,
+                    Software::new("ImageMagick Studio LLC", "ImageMagick", "distro"),
+                    doc,
+                ),
+                ImageMagickInfo::Release { version, hash_sha256 } => {
+                    let fetch_url = match os {
+                        Linux(_, _) => format!("https://imagemagick.org/archive/releases/ImageMagick-{version}.tar.xz"),
+                        _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+                    };
+                    let hash = Hash::new(Sha256, hash_sha256.clone());
+
+                    Package::new(
+                        &pkg_name,
+                        os,
+                        Software::new("ImageMagick Studio LLC", "ImageMagick", &version.to_string()),
+                        doc,
+                        DownloadRequest::new(&fetch_url, Integrity::Hash(hash)).unwrap(),
+                    )
+                }
+            };
+
+            Ok(ImageMagickImage(ServerImage(id, package), info))
+        }
+
+        fn install_release(&self) -> Result<(), String> {
+            let src_dir = src_dir()?;
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let downloader = Downloader::from(self.0.package().fetch, &tmp);
+            let tar_file = downloader.path.clone();
+
+            info!("Installing build dependencies...");
+
+            apt::install(&BUILD_DEPS)?;
+
+            info!("Downloading ImageMagick source...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Extracting ImageMagick source...");
+
+            if src_dir.exists() {
+                fs::remove_dir_all(&src_dir)
+                    .map_err(|error| error.to_string())?;
+            }
+
+            fs::create_dir_all(&src_dir)
+                .map_err(|error| error.to_string())?;
+
+            let output = exec_cmd(
+                "tar",
+                &[
+                    "-xJf",
+                    tar_file.to_str().unwrap(),
+                    "--directory",
+                    src_dir.to_str().unwrap(),
+                    "--strip-components=1",
+                ],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Building ImageMagick, this may take a while...");
+
+            exec_in_src_dir("./configure --prefix=/usr/local && make -j\"$(nproc)\"")?;
+
+            info!("Installing ImageMagick into /usr/local...");
+
+            exec_in_src_dir("sudo make install && sudo ldconfig /usr/local/lib")
+        }
+
+        fn uninstall_release(&self) -> Result<(), String> {
+            info!("Removing ImageMagick from /usr/local...");
+
+            exec_in_src_dir("sudo make uninstall")?;
+
+            info!("Removing ImageMagick source files...");
+
+            fs::remove_dir_all(src_dir()?)
+                .map_err(|error| error.to_string())
+        }
+    }
+
+    /// Kept after installing so `make uninstall` removes the installed files.
+    fn src_dir() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(|home| PathBuf::from(home).join(".local").join("src").join("ImageMagick"))
+            .map_err(|error| error.to_string())
+    }
+
+    fn exec_in_src_dir(bash_cmd: &str) -> Result<(), String> {
+        let src_dir = src_dir()?;
+        let bash_cmd = format!("cd {} && {bash_cmd}", src_dir.to_str().unwrap());
+        let output = exec_cmd("bash", &["-c", &bash_cmd])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    impl Install for ImageMagickImage {
+        fn install(&self) -> Result<(), String> {
+            match self.1 {
+                ImageMagickInfo::Distro => {
+                    info!("Installing ImageMagick from the distribution repository...");
+
+                    apt::install(&["imagemagick"])?;
+                }
+                ImageMagickInfo::Release { .. } => self.install_release()?,
+            }
+
+            info!("ImageMagick installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for ImageMagickImage {
+        fn uninstall(&self) -> Result<(), String> {
+            match self.1 {
+                ImageMagickInfo::Distro => {
+                    info!("Uninstalling ImageMagick...");
+
+                    apt::remove(&["imagemagick"])?;
+                }
+                ImageMagickInfo::Release { .. } => self.uninstall_release()?,
+            }
+
+            info!("ImageMagick uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for ImageMagickImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("convert", &["--version"])
+        }
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+}
+
+pub mod postgresql {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Postgresql;
+    use crate::image::server::{apt, systemd};
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct PostgresqlInfo {
+        major_version: u8,
+        key_fingerprint: String,
+        purge_data: bool,
+    }
+
+    const REPO_NAME: &str = "pgdg";
+
+    const CLUSTER_NAME: &str = "main";
+
+    pub struct PostgresqlImage(ServerImage, PostgresqlInfo);
+
+    impl PostgresqlImage {
+        pub fn new(os: Os, info: PostgresqlInfo) -> Self {
+            let id = Postgresql;
+            let pkg_name = format!("postgresql-{}", info.major_version);
+
+            PostgresqlImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("PostgreSQL Global Development Group", "PostgreSQL", &info.major_version.to_string()),
+                        Url::parse("https://www.postgresql.org/download/linux/ubuntu").unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+
+        fn key(&self) -> GpgKey {
+            let key_url = Url::parse("https://www.postgresql.org/media/keys/ACCC4CF8.asc").unwrap();
+
+            GpgKey::new(key_url, self.1.key_fingerprint.clone())
+        }
+    }
+
+    fn exec_bash(bash_cmd: &str) -> Result<(), String> {
+        let output = exec_cmd("bash", &["-c", bash_cmd])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    impl Install for PostgresqlImage {
+        fn install(&self) -> Result<(), String> {
+            let package = self.0.package();
+            let major = self.1.major_version;
+
+            apt_repo::add(
+                REPO_NAME,
+                &self.key(),
+                &format!("https://apt.postgresql.org/pub/repos/apt {}-pgdg main", apt_repo::CODENAME),
+            )?;
+
+            info!("Installing {}...", package.name);
+
+            apt::install(&[&package.name])?;
+
+            // The package creates the default cluster unless the system
+            // disables it in createcluster.conf
+            info!("Initializing cluster {major}/{CLUSTER_NAME}...");
+
+            exec_bash(&format!(
+                "pg_lsclusters --no-header | grep -q '^{major} *{CLUSTER_NAME} ' || \
+                sudo pg_createcluster {major} {CLUSTER_NAME} --start"
+            ))?;
+
+            systemd::enable("postgresql")?;
+
+            info!("PostgreSQL installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for PostgresqlImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let package = self.0.package();
+            let major = self.1.major_version;
+
+            if self.1.purge_data {
+                info!("Dropping cluster {major}/{CLUSTER_NAME} and its data...");
+
+                exec_bash(&format!("sudo pg_dropcluster --stop {major} {CLUSTER_NAME}"))?;
+            } else {
+                info!("Keeping cluster {major}/{CLUSTER_NAME} data in /var/lib/postgresql/{major}.");
+            }
+
+            systemd::disable("postgresql")?;
+
+            info!("Uninstalling {}...", package.name);
+
+            apt::remove(&[&package.name, &format!("postgresql-client-{major}")])?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("PostgreSQL uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for PostgresqlImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("psql", &["--version"])
+        }
+    }
+}
+
+pub mod mysql {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, exec_cmd_input, print_output};
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Mysql;
+    use crate::image::server::{apt, systemd};
+    use crate::image::{Config, Image, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct MysqlInfo {
+        release_series: String,
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "mysql";
+
+    const PACKAGES: [&str; 2] = ["mysql-server", "mysql-client"];
+
+    /// An empty root password makes root authenticate with `auth_socket`.
+    const ROOT_PASS_SELECTIONS: &str = "mysql-community-server mysql-community-server/root-pass password \n\
+    mysql-community-server mysql-community-server/re-root-pass password \n";
+
+    #[derive(Clone)]
+    pub struct MysqlImage(ServerImage, MysqlInfo);
+
+    impl MysqlImage {
+        pub fn new(os: Os, info: MysqlInfo) -> Self {
+            let id = Mysql;
+            let pkg_name = id.to_string();
+
+            MysqlImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("Oracle Corporation", "MySQL Community Server", &info.release_series),
+                        Url::parse("https://dev.mysql.com/doc/mysql-apt-repo-quick-guide/en").unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+
+        fn key(&self) -> GpgKey {
+            let key_url = Url::parse("https://repo.mysql.com/RPM-GPG-KEY-mysql-2023").unwrap();
+
+            GpgKey::new(key_url, self.1.key_fingerprint.clone())
+        }
+    }
+
+    fn exec_bash(bash_cmd: &str) -> Result<(), String> {
+        let output = exec_cmd("bash", &["-c", bash_cmd])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    impl Install for MysqlImage {
+        fn install(&self) -> Result<(), String> {
+            apt_repo::add(
+                REPO_NAME,
+                &self.key(),
+                &format!(
+                    "https://repo.mysql.com/apt/{} {} mysql-{}",
+                    self.0.package().os.distro_id(),
+                    apt_repo::CODENAME,
+                    self.1.release_series,
+                ),
+            )?;
+
+            info!("Preseeding MySQL root setup...");
+
+            exec_bash(&format!("printf '{ROOT_PASS_SELECTIONS}' | sudo debconf-set-selections"))?;
+
+            info!("Installing MySQL...");
+
+            exec_bash(&format!(
+                "sudo DEBIAN_FRONTEND=noninteractive apt-get --yes install {}",
+                PACKAGES.join(" "),
+            ))?;
+
+            systemd::enable("mysql")?;
+
+            info!("MySQL installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for MysqlImage {
+        fn uninstall(&self) -> Result<(), String> {
+            systemd::disable("mysql")?;
+
+            info!("Uninstalling MySQL, data in /var/lib/mysql is kept...");
+
+            apt::remove(&PACKAGES)?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("MySQL uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for MysqlImage { image_ops_impl!(); }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct MysqlConfig {
+        root_password: String,
+    }
+
+    type MysqlImageConfig = ImageConfig<MysqlImage, MysqlConfig>;
+
+    impl ToImageConfig<MysqlConfig> for MysqlImage {
+        fn to_image_config(&self, config: MysqlConfig) -> MysqlImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for MysqlImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let MysqlConfig { root_password } = &self.1;
+
+            // The root user logs in via auth_socket after installation, so
+            // sudo gives access to change its authentication
+            let sql = if root_password.is_empty() {
+                info!("Configuring MySQL root to authenticate with the system root user...");
+
+                "ALTER USER 'root'@'localhost' IDENTIFIED WITH auth_socket;".to_string()
+            } else {
+                info!("Configuring MySQL root password...");
+
+                let root_password = root_password.replace('\\', "\\\\").replace('\'', "''");
+
+                format!("ALTER USER 'root'@'localhost' IDENTIFIED WITH caching_sha2_password BY '{root_password}';")
+            };
+
+            let output = exec_cmd_input("sudo", &["mysql", "--user=root"], &sql)
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("MySQL configured.");
+
+            Ok(())
+        }
+    }
+}
+
+pub mod redis {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Redis;
+    use crate::image::server::{apt, systemd};
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct RedisInfo {
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "redis";
+
+    const SERVICE_NAME: &str = "redis-server";
+
+    pub struct RedisImage(ServerImage, GpgKey);
+
+    impl RedisImage {
+        pub fn new(os: Os, RedisInfo { key_fingerprint }: RedisInfo) -> Self {
+            let id = Redis;
+            let pkg_name = id.to_string();
+            let version = "latest";
+            let key_url = Url::parse("https://packages.redis.io/gpg").unwrap();
+
+            RedisImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("Redis Ltd.", "Redis", version),
+                        Url::parse("https://redis.io/docs/latest/operate/oss_and_stack/install/install-redis/install-redis-on-linux").unwrap(),
+                    ),
+                ),
+                GpgKey::new(key_url, key_fingerprint),
+            )
+        }
+    }
+
+    impl Install for RedisImage {
+        fn install(&self) -> Result<(), String> {
+            apt_repo::add(
+                REPO_NAME,
+                &self.1,
+                &format!("https://packages.redis.io/deb {} main", apt_repo::CODENAME),
+            )?;
+
+            info!("Installing Redis...");
+
+            apt::install(&[&self.0.package().name])?;
+
+            systemd::enable(SERVICE_NAME)?;
+
+            info!("Redis installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for RedisImage {
+        fn uninstall(&self) -> Result<(), String> {
+            systemd::disable(SERVICE_NAME)?;
+
+            info!("Uninstalling Redis...");
+
+            apt::remove(&[&self.0.package().name, "redis-server", "redis-tools"])?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("Redis uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for RedisImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("redis-server", &["--version"])
+        }
+    }
+}
+
+pub mod nginx {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Nginx;
+    use crate::image::server::{apt, systemd};
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct NginxInfo {
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "nginx";
+
+    /// APT must prefer the nginx.org packages over the Ubuntu ones.
+    const APT_PREFERENCES: &str = "Package: *\n\
+    Pin: origin nginx.org\n\
+    Pin: release o=nginx\n\
+    Pin-Priority: 900\n";
+
+    const APT_PREFERENCES_PATH: &str = "/etc/apt/preferences.d/99nginx";
+
+    pub struct NginxImage(ServerImage, GpgKey);
+
+    impl NginxImage {
+        pub fn new(os: Os, NginxInfo { key_fingerprint }: NginxInfo) -> Self {
+            let id = Nginx;
+            let pkg_name = id.to_string();
+            let version = "stable";
+            let key_url = Url::parse("https://nginx.org/keys/nginx_signing.key").unwrap();
+
+            NginxImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("F5, Inc.", "nginx", version),
+                        Url::parse("https://nginx.org/en/linux_packages.html").unwrap(),
+                    ),
+                ),
+                GpgKey::new(key_url, key_fingerprint),
+            )
+        }
+    }
+
+    impl Install for NginxImage {
+        fn install(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            apt_repo::add(
+                REPO_NAME,
+                &self.1,
+                &format!("https://nginx.org/packages/{} {} nginx", self.0.package().os.distro_id(), apt_repo::CODENAME),
+            )?;
+
+            info!("Preferring nginx.org packages...");
+
+            apt::write_file(APT_PREFERENCES_PATH, APT_PREFERENCES)?;
+
+            info!("Installing nginx...");
+
+            apt::install(&[&package.name])?;
+
+            systemd::enable(&package.name)?;
+
+            info!("nginx installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for NginxImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            systemd::disable(&package.name)?;
+
+            info!("Uninstalling nginx...");
+
+            apt::remove(&[&package.name])?;
+            apt::delete_file(APT_PREFERENCES_PATH)?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("nginx uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for NginxImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("nginx", &["-v"])
+        }
+    }
+}
+
+pub mod rabbitmq {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Rabbitmq;
+    use crate::image::server::{apt, systemd};
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    /// Versions are APT package versions, like `1:26.2.5.2-1` for Erlang.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct RabbitmqInfo {
+        erlang_version: String,
+        rabbitmq_version: String,
+        key_fingerprint: String,
+    }
+
+    const ERLANG_REPO_NAME: &str = "rabbitmq-erlang";
+
+    const RABBITMQ_REPO_NAME: &str = "rabbitmq-server";
+
+    const SERVICE_NAME: &str = "rabbitmq-server";
+
+    const ERLANG_PACKAGES: [&str; 17] = [
+        "erlang-base",
+        "erlang-asn1",
+        "erlang-crypto",
+        "erlang-eldap",
+        "erlang-ftp",
+        "erlang-inets",
+        "erlang-mnesia",
+        "erlang-os-mon",
+        "erlang-parsetools",
+        "erlang-public-key",
+        "erlang-runtime-tools",
+        "erlang-snmp",
+        "erlang-ssl",
+        "erlang-syntax-tools",
+        "erlang-tftp",
+        "erlang-tools",
+        "erlang-xmerl",
+    ];
+
+    pub struct RabbitmqImage(ServerImage, RabbitmqInfo);
+
+    impl RabbitmqImage {
+        pub fn new(os: Os, info: RabbitmqInfo) -> Self {
+            let id = Rabbitmq;
+            let pkg_name = "rabbitmq-server";
+
+            RabbitmqImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        pkg_name,
+                        os,
+                        Software::new("Broadcom Inc.", "RabbitMQ", &info.rabbitmq_version),
+                        Url::parse("https://www.rabbitmq.com/docs/install-debian").unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+
+        fn key(&self) -> GpgKey {
+            let key_url = Url::parse("https://keys.openpgp.org/vks/v1/by-fingerprint/0A9AF2115F4687BD29803A206B73A36E6026DFCA").unwrap();
+
+            GpgKey::new(key_url, self.1.key_fingerprint.clone())
+        }
+    }
+
+    impl Install for RabbitmqImage {
+        fn install(&self) -> Result<(), String> {
+            let RabbitmqInfo { erlang_version, rabbitmq_version, .. } = &self.1;
+            let distro = self.0.package().os.distro_id();
+
+            apt_repo::add(
+                ERLANG_REPO_NAME,
+                &self.key(),
+                &format!("https://deb1.rabbitmq.com/rabbitmq-erlang/{distro}/{0} {0} main", apt_repo::CODENAME),
+            )?;
+
+            apt_repo::add(
+                RABBITMQ_REPO_NAME,
+                &self.key(),
+                &format!("https://deb1.rabbitmq.com/rabbitmq-server/{distro}/{0} {0} main", apt_repo::CODENAME),
+            )?;
+
+            info!("Installing Erlang {erlang_version} and RabbitMQ {rabbitmq_version}...");
+
+            let pinned_packages = ERLANG_PACKAGES
+                .iter()
+                .map(|package| format!("{package}={erlang_version}"))
+                .chain([format!("{}={rabbitmq_version}", self.0.package().name)])
+                .collect::<Vec<String>>();
+
+            apt::install(&pinned_packages.iter().map(String::as_str).collect::<Vec<&str>>())?;
+
+            systemd::enable(SERVICE_NAME)?;
+
+            info!("RabbitMQ installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for RabbitmqImage {
+        fn uninstall(&self) -> Result<(), String> {
+            systemd::disable(SERVICE_NAME)?;
+
+            info!("Uninstalling RabbitMQ and Erlang...");
+
+            let package = self.0.package();
+            let packages = [package.name.as_str()]
+                .into_iter()
+                .chain(ERLANG_PACKAGES)
+                .collect::<Vec<&str>>();
+
+            apt::remove(&packages)?;
+            apt_repo::remove(RABBITMQ_REPO_NAME)?;
+            apt_repo::remove(ERLANG_REPO_NAME)?;
+
+            info!("RabbitMQ uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for RabbitmqImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::dpkg_package("rabbitmq-server")
+        }
+    }
+}
+
+pub mod grafana {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Grafana;
+    use crate::image::server::{apt, systemd};
+    use crate::image::detection::Detection;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct GrafanaInfo {
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "grafana";
+
+    const SERVICE_NAME: &str = "grafana-server";
+
+    pub struct GrafanaImage(ServerImage, GpgKey);
+
+    impl GrafanaImage {
+        pub fn new(os: Os, GrafanaInfo { key_fingerprint }: GrafanaInfo) -> Self {
+            let id = Grafana;
+            let pkg_name = id.to_string();
+            let version = "latest";
+            let key_url = Url::parse("https://apt.grafana.com/gpg.key").unwrap();
+
+            GrafanaImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("Grafana Labs", "Grafana OSS", version),
+                        Url::parse("https://grafana.com/docs/grafana/latest/setup-grafana/installation/debian").unwrap(),
+                    ),
+                ),
+                GpgKey::new(key_url, key_fingerprint),
+            )
+        }
+    }
+
+    impl Install for GrafanaImage {
+        fn install(&self) -> Result<(), String> {
+            apt_repo::add(
+                REPO_NAME,
+                &self.1,
+                "https://apt.grafana.com stable main",
+            )?;
+
+            info!("Installing Grafana...");
+
+            apt::install(&[&self.0.package().name])?;
+
+            systemd::enable(SERVICE_NAME)?;
+
+            info!("Grafana installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for GrafanaImage {
+        fn uninstall(&self) -> Result<(), String> {
+            systemd::disable(SERVICE_NAME)?;
+
+            info!("Uninstalling Grafana...");
+
+            apt::remove(&[&self.0.package().name])?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("Grafana uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for GrafanaImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::dpkg_package("grafana")
+        }
+    }
+}
+
+pub mod prometheus {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Prometheus;
+    use crate::image::server::systemd;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::os::OsArch::{Arm64, Armhf, X64};
+    use crate::package::{Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct PrometheusInfo {
+        version: SemVer,
+    }
+
+    const SERVICE_NAME: &str = "prometheus";
+
+    const BINARIES: [&str; 2] = ["prometheus", "promtool"];
+
+    const BIN_DIR: &str = "/usr/local/bin";
+
+    const CONFIG_DIR: &str = "/etc/prometheus";
+
+    const DATA_DIR: &str = "/var/lib/prometheus";
+
+    const SERVICE_UNIT: &str = "[Unit]
+Description=Prometheus
+Wants=network-online.target
+After=network-online.target
+
+[Service]
+User=prometheus
+Group=prometheus
+Type=simple
+ExecStart=/usr/local/bin/prometheus \\
+    --config.file=/etc/prometheus/prometheus.yml \\
+    --storage.tsdb.path=/var/lib/prometheus
+Restart=on-failure
+
+[Install]
+WantedBy=multi-user.target
+";
+
+    pub struct PrometheusImage(ServerImage);
+
+    impl PrometheusImage {
+        pub fn new(os: Os, PrometheusInfo { version }: PrometheusInfo) -> Result<Self, ImageInfoError> {
+            let id = Prometheus;
+            let fetch_url = match os {
+                Linux(X64, _) => format!("https://github.com/prometheus/prometheus/releases/download/v{version}/prometheus-{version}.linux-amd64.tar.gz"),
+                Linux(Arm64, _) => format!("https://github.com/prometheus/prometheus/releases/download/v{version}/prometheus-{version}.linux-arm64.tar.gz"),
+                Linux(Armhf, _) => format!("https://github.com/prometheus/prometheus/releases/download/v{version}/prometheus-{version}.linux-armv7.tar.gz"),
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+            };
+            let checksum_url = Url::parse(&format!("https://github.com/prometheus/prometheus/releases/download/v{version}/sha256sums.txt")).unwrap();
+
+            Ok(PrometheusImage(
+                ServerImage(
+                    id.clone(),
+                    Package::new(
+                        &id.to_string(),
+                        os,
+                        Software::new("Prometheus Authors", "Prometheus", &version.to_string()),
+                        Url::parse("https://prometheus.io/docs/prometheus/latest/installation").unwrap(),
+                        DownloadRequest::new(&fetch_url, Integrity::RemoteSha256 { url: checksum_url }).unwrap(),
+                    ))))
+        }
+    }
+
+    fn exec_sudo(args: &[&str]) -> Result<(), String> {
+        let output = exec_cmd("sudo", args)
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    impl Install for PrometheusImage {
+        fn install(&self) -> Result<(), String> {
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let tmp_path = tmp.path();
+            let downloader = Downloader::from(self.0.package().fetch, &tmp);
+            let tar_file = downloader.path.clone();
+
+            info!("Downloading Prometheus...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Extracting Prometheus...");
+
+            let output = exec_cmd(
+                "tar",
+                &[
+                    "-xzf",
+                    tar_file.to_str().unwrap(),
+                    "--directory",
+                    tmp_path.to_str().unwrap(),
+                    "--strip-components=1",
+                ],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Creating system user prometheus...");
+
+            let output = exec_cmd(
+                "bash",
+                &["-c", "id prometheus || sudo useradd --system --no-create-home --shell /usr/sbin/nologin prometheus"],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Copying Prometheus binaries to {BIN_DIR}...");
+
+            for bin in BINARIES {
+                exec_sudo(&["install", "-m", "0755", tmp_path.join(bin).to_str().unwrap(), BIN_DIR])?;
+            }
+
+            info!("Copying default configuration to {CONFIG_DIR}...");
+
+            exec_sudo(&["install", "-d", CONFIG_DIR])?;
+            exec_sudo(&["install", "-m", "0644", tmp_path.join("prometheus.yml").to_str().unwrap(), CONFIG_DIR])?;
+            exec_sudo(&["install", "-d", "-o", "prometheus", "-g", "prometheus", DATA_DIR])?;
+
+            systemd::write_unit(SERVICE_NAME, SERVICE_UNIT)?;
+            systemd::enable(SERVICE_NAME)?;
+
+            info!("Prometheus installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for PrometheusImage {
+        fn uninstall(&self) -> Result<(), String> {
+            systemd::disable(SERVICE_NAME)?;
+            systemd::remove_unit(SERVICE_NAME)?;
+
+            info!("Removing Prometheus files...");
+
+            let bin_files = BINARIES
+                .iter()
+                .map(|bin| format!("{BIN_DIR}/{bin}"))
+                .collect::<Vec<_>>();
+
+            let args = ["rm", "-rf", CONFIG_DIR, DATA_DIR]
+                .into_iter()
+                .chain(bin_files.iter().map(String::as_str))
+                .collect::<Vec<&str>>();
+
+            exec_sudo(&args)?;
+
+            info!("Removing system user prometheus...");
+
+            exec_sudo(&["userdel", "prometheus"])?;
+
+            info!("Prometheus uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for PrometheusImage {
+        image_ops_impl!();
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
+}
+
+pub mod gh {
+    use std::path::PathBuf;
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Gh;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct GhInfo {
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "github-cli";
+
+    pub struct GhImage(ServerImage, GpgKey);
+
+    impl GhImage {
+        pub fn new(os: Os, GhInfo { key_fingerprint }: GhInfo) -> Self {
+            let id = Gh;
+            let pkg_name = id.to_string();
+            let version = "latest";
+            let key_url = Url::parse("https://cli.github.com/packages/githubcli-archive-keyring.gpg").unwrap();
+
+            GhImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("GitHub, Inc.", "GitHub CLI", version),
+                        Url::parse("https://github.com/cli/cli/blob/trunk/docs/install_linux.md").unwrap(),
+                    ),
+                ),
+                GpgKey::new(key_url, key_fingerprint),
+            )
+        }
+    }
+
+    impl Install for GhImage {
+        fn install(&self) -> Result<(), String> {
+            apt_repo::add(
+                REPO_NAME,
+                &self.1,
+                "https://cli.github.com/packages stable main",
+            )?;
+
+            info!("Installing GitHub CLI...");
+
+            apt::install(&[&self.0.package().name])?;
+
+            info!("GitHub CLI installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for GhImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Uninstalling GitHub CLI...");
+
+            apt::remove(&[&self.0.package().name])?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("GitHub CLI uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for GhImage {
+        image_ops_impl!();
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".config/gh")]
+        }
+    }
+}
+
+pub mod gitlab_runner {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, exec_cmd_env, print_output};
+    use crate::download::gpg::GpgKey;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::GitlabRunner;
+    use crate::image::server::{apt, systemd};
+    use crate::image::{Config, Image, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::apt_repo;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct GitlabRunnerInfo {
+        key_fingerprint: String,
+    }
+
+    const REPO_NAME: &str = "gitlab-runner";
+
+    #[derive(Clone)]
+    pub struct GitlabRunnerImage(ServerImage, GpgKey);
+
+    impl GitlabRunnerImage {
+        pub fn new(os: Os, GitlabRunnerInfo { key_fingerprint }: GitlabRunnerInfo) -> Self {
+            let id = GitlabRunner;
+            let pkg_name = id.to_string();
+            let version = "latest";
+            let key_url = Url::parse("https://packages.gitlab.com/runner/gitlab-runner/gpgkey").unwrap();
+
+            GitlabRunnerImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("GitLab Inc.", "GitLab Runner", version),
+                        Url::parse("https://docs.gitlab.com/runner/install/linux-repository.html").unwrap(),
+                    ),
+                ),
+                GpgKey::new(key_url, key_fingerprint),
+            )
+        }
+    }
+
+    impl Install for GitlabRunnerImage {
+        fn install(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            apt_repo::add(
+                REPO_NAME,
+                &self.1,
+                &format!("https://packages.gitlab.com/runner/gitlab-runner/{}/ {} main", self.0.package().os.distro_id(), apt_repo::CODENAME),
+            )?;
+
+            info!("Installing GitLab Runner...");
+
+            apt::install(&[&package.name])?;
+
+            systemd::enable(&package.name)?;
+
+            info!("GitLab Runner installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for GitlabRunnerImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let package = self.0.package();
+
+            info!("Unregistering GitLab Runner runners...");
+
+            let output = exec_cmd("sudo", &["gitlab-runner", "unregister", "--all-runners"])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            systemd::disable(&package.name)?;
+
+            info!("Uninstalling GitLab Runner...");
+
+            apt::remove(&[&package.name])?;
+            apt_repo::remove(REPO_NAME)?;
+
+            info!("GitLab Runner uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for GitlabRunnerImage { image_ops_impl!(); }
+
+    /// The token is a runner authentication token (`glrt-`).
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct GitlabRunnerConfig {
+        url: String,
+        token: String,
+        executor: String,
+        docker_image: String,
+    }
+
+    type GitlabRunnerImageConfig = ImageConfig<GitlabRunnerImage, GitlabRunnerConfig>;
+
+    impl ToImageConfig<GitlabRunnerConfig> for GitlabRunnerImage {
+        fn to_image_config(&self, config: GitlabRunnerConfig) -> GitlabRunnerImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for GitlabRunnerImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let GitlabRunnerConfig { url, token, executor, docker_image } = &self.1;
+
+            if token.trim().is_empty() {
+                return Err("Value 'token' is empty. Provide a runner authentication token to register the runner.".to_string());
+            }
+
+            info!("Registering GitLab Runner at {url} with executor {executor}...");
+
+            // The token goes in the environment, so it stays out of ps
+            let mut args = vec![
+                "--preserve-env=CI_SERVER_TOKEN",
+                "gitlab-runner",
+                "register",
+                "--non-interactive",
+                "--url",
+                url,
+                "--executor",
+                executor,
+            ];
+
+            if !docker_image.is_empty() {
+                args.extend(["--docker-image", docker_image]);
+            }
+
+            let output = exec_cmd_env("sudo", &args, &[("CI_SERVER_TOKEN", token)])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("GitLab Runner registered.");
+
+            Ok(())
+        }
+    }
+}
+
+pub mod pyenv {
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Pyenv;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct PyenvInfo {
+        version: SemVer,
+        python_version: Option<SemVer>,
+    }
+
+    const PYTHON_BUILD_DEPS: [&str; 14] = [
+        "build-essential",
+        "libssl-dev",
+        "zlib1g-dev",
+        "libbz2-dev",
+        "libreadline-dev",
+        "libsqlite3-dev",
+        "curl",
+        "libncursesw5-dev",
+        "xz-utils",
+        "tk-dev",
+        "libxml2-dev",
+        "libxmlsec1-dev",
+        "libffi-dev",
+        "liblzma-dev",
+    ];
+
+    const PYENV_ENV_LINES: [&str; 4] = [
+        "# pyenv",
+        r#"export PYENV_ROOT="$HOME/.pyenv""#,
+        r#"[[ -d $PYENV_ROOT/bin ]] && export PATH="$PYENV_ROOT/bin:$PATH""#,
+        r#"eval "$(pyenv init -)""#,
+    ];
+
+    pub struct PyenvImage(ServerImage, PyenvInfo);
+
+    impl PyenvImage {
+        pub fn new(os: Os, info: PyenvInfo) -> Self {
+            let id = Pyenv;
+            let pkg_name = id.to_string();
+
+            PyenvImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("pyenv", "pyenv", &info.version.to_string()),
+                        Url::parse("https://github.com/pyenv/pyenv").unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+    }
+
+    fn home() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(PathBuf::from)
+            .map_err(|error| error.to_string())
+    }
+
+    fn git_clone(repo: &str, tag: &str, dir: &Path) -> Result<(), String> {
+        let output = exec_cmd(
+            "git",
+            &["clone", "--depth", "1", "--branch", tag, repo, dir.to_str().unwrap()],
+        ).map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    fn exec_pyenv_cmd(pyenv_cmd: &str) -> Result<(), String> {
+        let init = PYENV_ENV_LINES[1..].join(" && ");
+        let bash_cmd = format!("{init} && {pyenv_cmd}");
+        let output = exec_cmd("bash", &["-c", &bash_cmd])
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    impl Install for PyenvImage {
+        fn install(&self) -> Result<(), String> {
+            let PyenvInfo { version, python_version } = &self.1;
+
+            info!("Installing Python build dependencies via APT...");
+
+            apt::install(&PYTHON_BUILD_DEPS)?;
+
+            info!("Cloning pyenv v{version}...");
+
+            git_clone(
+                "https://github.com/pyenv/pyenv.git",
+                &format!("v{version}"),
+                &home()?.join(".pyenv"),
+            )?;
+
+            info!("Updating shell init file...");
+
+            append_lines(&home()?.join(".bashrc"), &PYENV_ENV_LINES)?;
+
+            if let Some(python_version) = python_version {
+                info!("Installing Python {python_version} via pyenv (it builds from source, so it may take a while)...");
+
+                exec_pyenv_cmd(&format!("pyenv install --skip-existing {python_version}"))?;
+                exec_pyenv_cmd(&format!("pyenv global {python_version}"))?;
+                exec_pyenv_cmd("python --version")?;
+            }
+
+            info!("pyenv installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for PyenvImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Removing pyenv files and its Python versions...");
+
+            fs::remove_dir_all(home()?.join(".pyenv"))
+                .map_err(|error| error.to_string())?;
+
+            info!("Removing environment variables...");
+
+            remove_lines(&home()?.join(".bashrc"), &PYENV_ENV_LINES)?;
+
+            info!("pyenv uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for PyenvImage { image_ops_impl!(); }
+}
+
+pub mod poetry {
+    use std::env;
+    use std::path::PathBuf;
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::{DownloadRequest, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Poetry;
+    use crate::image::{Image, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{append_lines, remove_lines};
+    use crate::os::Os;
+    use crate::package::{Package, SemVer, Software};
+
+    /// The installer isn't versioned, so `version` pins the Poetry it installs.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct PoetryInfo {
+        version: SemVer,
+    }
+
+    const POETRY_PROFILE_LINES: [&str; 2] = [
+        "# Poetry",
+        r#"export PATH="$HOME/.local/bin:$PATH""#,
+    ];
+
+    pub struct PoetryImage(ServerImage);
+
+    impl PoetryImage {
+        pub fn new(os: Os, PoetryInfo { version }: PoetryInfo) -> Self {
+            let id = Poetry;
+
+            PoetryImage(
+                ServerImage(
+                    id.clone(),
+                    Package::new(
+                        &id.to_string(),
+                        os,
+                        Software::new("Python Poetry", "Poetry", &version.to_string()),
+                        Url::parse("https://python-poetry.org/docs/#installing-with-the-official-installer").unwrap(),
+                        DownloadRequest::new("https://install.python-poetry.org", Integrity::None).unwrap(),
+                    )))
+        }
+
+        fn exec_installer(&self, args: &[&str]) -> Result<(), String> {
+            let bash_cmd = format!(
+                "{} | python3 - {}",
+                self.0.package().fetch.curl_script(),
+                args.join(" "),
+            );
+
+            let output = exec_cmd("bash", &["-c", &bash_cmd])
+                .map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            Ok(())
+        }
+    }
+
+    fn profile() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(|home| PathBuf::from(home).join(".profile"))
+            .map_err(|error| error.to_string())
+    }
+
+    impl Install for PoetryImage {
+        fn install(&self) -> Result<(), String> {
+            let version = self.0.package().software.version;
+
+            info!("Installing Poetry {version}...");
+
+            self.exec_installer(&["--version", &version])?;
+
+            info!("Updating environment variable...");
+
+            append_lines(&profile()?, &POETRY_PROFILE_LINES)?;
+
+            info!("Poetry installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for PoetryImage {
+        fn uninstall(&self) -> Result<(), String> {
+            info!("Uninstalling Poetry...");
+
+            self.exec_installer(&["--uninstall"])?;
+
+            info!("Cleaning environment variable...");
+
+            remove_lines(&profile()?, &POETRY_PROFILE_LINES)?;
+
+            info!("Poetry uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for PoetryImage {
+        image_ops_impl!();
+
+        fn purge_paths(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".config/pypoetry"), PathBuf::from(".cache/pypoetry")]
+        }
+    }
+}
+
+pub mod pipx {
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::image::server::apt;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Pipx;
+    use crate::image::{Config, Image, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    /// Distribution package, or from PyPI via pip for a newer version.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(tag = "source", rename_all = "snake_case")]
+    pub enum PipxInfo {
+        Apt,
+        Pip,
+    }
+
+    #[derive(Clone)]
+    pub struct PipxImage(ServerImage, PipxInfo);
+
+    impl PipxImage {
+        pub fn new(os: Os, info: PipxInfo) -> Self {
+            let id = Pipx;
+            let pkg_name = id.to_string();
+            let version = "latest";
+
+            PipxImage(
+                ServerImage(
+                    id,
+                    Package::new_managed(
+                        &pkg_name,
+                        os,
+                        Software::new("pypa", "pipx", version),
+                        Url::parse("https://pipx.pypa.io/stable/installation").unwrap(),
+                    ),
+                ),
+                info,
+            )
+        }
+    }
+
+    /// As a Python module, it runs before `~/.local/bin` is in the PATH.
+    fn exec_pipx(args: &[&str]) -> Result<(), String> {
+        let args = ["-m", "pipx"]
+            .into_iter()
+            .chain(args.iter().copied())
+            .collect::<Vec<&str>>();
+
+        let output = exec_cmd("python3", &args)
+            .map_err(|error| error.to_string())?;
+
+        print_output(output);
+
+        Ok(())
+    }
+
+    impl Install for PipxImage {
+        fn install(&self) -> Result<(), String> {
+            match self.1 {
+                PipxInfo::Apt => {
+                    info!("Installing pipx via APT...");
+
+                    apt::install(&["pipx"])?;
+                }
+                PipxInfo::Pip => {
+                    info!("Installing pipx via pip...");
+
+                    let output = exec_cmd("python3", &["-m", "pip", "install", "--user", "pipx"])
+                        .map_err(|error| error.to_string())?;
+
+                    print_output(output);
+                }
+            }
+
+            info!("Ensuring pipx apps are in the PATH...");
+
+            exec_pipx(&["ensurepath"])?;
+
+            info!("pipx installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for PipxImage {
+        fn uninstall(&self) -> Result<(), String> {
+            match self.1 {
+                PipxInfo::Apt => {
+                    info!("Uninstalling pipx via APT...");
+
+                    apt::remove(&["pipx"])?;
+                }
+                PipxInfo::Pip => {
+                    info!("Uninstalling pipx via pip...");
+
+                    let output = exec_cmd("python3", &["-m", "pip", "uninstall", "--yes", "pipx"])
+                        .map_err(|error| error.to_string())?;
+
+                    print_output(output);
+                }
+            }
+
+            // ensurepath only adds ~/.local/bin to the PATH, which other tools
+            // use too, so it's kept
+            info!("pipx uninstalled, apps installed with it are kept in ~/.local/bin.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for PipxImage { image_ops_impl!(); }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct PipxConfig {
+        packages: Vec<String>,
+    }
+
+    type PipxImageConfig = ImageConfig<PipxImage, PipxConfig>;
+
+    impl ToImageConfig<PipxConfig> for PipxImage {
+        fn to_image_config(&self, config: PipxConfig) -> PipxImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for PipxImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let PipxConfig { packages } = &self.1;
+
+            for package in packages {
+                info!("Installing {package} via pipx...");
+
+                exec_pipx(&["install", package])?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+pub mod uv {
+    use std::path::PathBuf;
+    use std::{env, fs};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::download::{DownloadRequest, Downloader, Integrity};
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Uv;
+    use crate::image::{Image, ImageInfoError, ImageOps, Install, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::Os;
+    use crate::os::Os::Linux;
+    use crate::os::OsArch::{Arm64, Armhf, X64};
+    use crate::package::{Package, SemVer, Software};
+    use crate::tmp::TmpWorkingDir;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct UvInfo {
+        version: SemVer,
+    }
+
+    const BINARIES: [&str; 2] = ["uv", "uvx"];
+
+    pub struct UvImage(ServerImage);
+
+    impl UvImage {
+        pub fn new(os: Os, UvInfo { version }: UvInfo) -> Result<Self, ImageInfoError> {
+            let id = Uv;
+            let fetch_url = match os {
+                Linux(X64, _) => format!("https://github.com/astral-sh/uv/releases/download/{version}/uv-x86_64-unknown-linux-gnu.tar.gz"),
+                Linux(Arm64, _) => format!("https://github.com/astral-sh/uv/releases/download/{version}/uv-aarch64-unknown-linux-gnu.tar.gz"),
+                Linux(Armhf, _) => format!("https://github.com/astral-sh/uv/releases/download/{version}/uv-armv7-unknown-linux-gnueabihf.tar.gz"),
+                _ => return Err(ImageInfoError::unsupported_os(&id, &os)),
+            };
+            let checksum_url = Url::parse(&format!("{fetch_url}.sha256")).unwrap();
+
+            Ok(UvImage(
+                ServerImage(
+                    id.clone(),
+                    Package::new(
+                        &id.to_string(),
+                        os,
+                        Software::new("Astral Software Inc.", "uv", &version.to_string()),
+                        Url::parse("https://docs.astral.sh/uv/getting-started/installation").unwrap(),
+                        DownloadRequest::new(&fetch_url, Integrity::RemoteSha256 { url: checksum_url }).unwrap(),
+                    ))))
+        }
+    }
+
+    fn bin_dir() -> Result<PathBuf, String> {
+        env::var("HOME")
+            .map(|home| PathBuf::from(home).join(".local").join("bin"))
+            .map_err(|error| error.to_string())
+    }
+
+    impl Install for UvImage {
+        fn install(&self) -> Result<(), String> {
+            let bin_dir = bin_dir()?;
+            let tmp = TmpWorkingDir::new()
+                .map_err(|error| error.to_string())?;
+
+            let tmp_path = tmp.path();
+            let downloader = Downloader::from(self.0.package().fetch, &tmp);
+            let tar_file = downloader.path.clone();
+
+            info!("Downloading uv...");
+
+            downloader
+                .download_blocking()
+                .map_err(|error| error.to_string())?;
+
+            info!("Extracting uv...");
+
+            let output = exec_cmd(
+                "tar",
+                &[
+                    "-xzf",
+                    tar_file.to_str().unwrap(),
+                    "--directory",
+                    tmp_path.to_str().unwrap(),
+                    "--strip-components=1",
+                ],
+            ).map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("Copying uv binaries to {}...", bin_dir.display());
+
+            fs::create_dir_all(&bin_dir)
+                .map_err(|error| error.to_string())?;
+
+            for bin in BINARIES {
+                let output = exec_cmd(
+                    "install",
+                    &["-m", "0755", tmp_path.join(bin).to_str().unwrap(), bin_dir.to_str().unwrap()],
+                ).map_err(|error| error.to_string())?;
+
+                print_output(output);
+            }
+
+            info!("uv installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for UvImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let bin_dir = bin_dir()?;
+
+            info!("Removing uv binaries...");
+
+            for bin in BINARIES {
+                let bin_file = bin_dir.join(bin);
+
+                if bin_file.exists() {
+                    fs::remove_file(bin_file)
+                        .map_err(|error| error.to_string())?;
+                }
+            }
+
+            info!("uv uninstalled.");
 
             Ok(())
         }
     }
+
+    impl ImageOps for UvImage {
+        image_ops_impl!();
+
+        fn artifacts(&self) -> Result<Vec<DownloadRequest>, String> {
+            Ok(vec![self.0.package().fetch])
+        }
+    }
 }
 
 pub mod git {
@@ -1023,6 +5733,7 @@ pub mod git {
     use crate::{image_ops_impl, os};
     use reqwest::Url;
     use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
     use std::fs;
     use std::process::Output;
 
@@ -1049,14 +5760,14 @@ pub mod git {
 
     impl Install for GitImage {
         fn install(&self) -> Result<(), String> {
-            println!("Installing Git via APT...");
+            info!("Installing Git via APT...");
 
             let output = exec_cmd("sudo", &["apt-get", "install", "git"])
                 .map_err(|error| error.to_string())?;
 
             print_output(output);
 
-            println!("Git installed.");
+            info!("Git installed.");
 
             Ok(())
         }
@@ -1064,7 +5775,7 @@ pub mod git {
 
     impl Uninstall for GitImage {
         fn uninstall(&self) -> Result<(), String> {
-            println!("Uninstalling Git via APT...");
+            info!("Uninstalling Git via APT...");
 
             let output = exec_cmd(
                 "sudo",
@@ -1073,7 +5784,7 @@ pub mod git {
 
             print_output(output);
 
-            println!("Git uninstalled.");
+            info!("Git uninstalled.");
 
             Ok(())
         }
@@ -1084,6 +5795,9 @@ pub mod git {
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct Core {
         excludes_file: String,
+
+        #[serde(default)]
+        editor: String,
     }
 
     #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1098,11 +5812,23 @@ pub mod git {
         gpg_sign: bool,
     }
 
+    #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+    pub struct Init {
+        default_branch: String,
+    }
+
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct GitConfig {
         core: Core,
         user: User,
         commit: Commit,
+
+        #[serde(default)]
+        init: Init,
+
+        #[serde(default)]
+        alias: BTreeMap<String, String>,
+
         git_ignore: Vec<String>,
     }
 
@@ -1116,9 +5842,9 @@ pub mod git {
 
     impl Config for GitImageConfig {
         fn config(&self) -> Result<(), String> {
-            let GitConfig { core, user, commit, git_ignore } = self.1.clone();
+            let GitConfig { core, user, commit, init, alias, git_ignore } = self.1.clone();
 
-            println!("Configuring Git Core...");
+            info!("Configuring Git Core...");
 
             let output = exec_git_config_global(
                 "core.excludesFile",
@@ -1127,7 +5853,13 @@ pub mod git {
 
             print_output(output);
 
-            println!("Copying Git ignore...");
+            if !core.editor.trim().is_empty() {
+                let output = exec_git_config_global("core.editor", &core.editor)?;
+
+                print_output(output);
+            }
+
+            info!("Copying Git ignore...");
 
             let new_line = |acc, cur| format!("{acc}\n{cur}");
             let git_ignore_contents = git_ignore
@@ -1136,7 +5868,7 @@ pub mod git {
 
             write_git_ignore_file(core.excludes_file, git_ignore_contents)?;
 
-            println!("Configuring Git User...");
+            info!("Configuring Git User...");
 
             let output = exec_git_config_global(
                 "user.name",
@@ -1152,7 +5884,7 @@ pub mod git {
 
             print_output(output);
 
-            println!("Configuring GPG...");
+            info!("Configuring GPG...");
 
             let output = exec_git_config_global_unset("gpg.format")?;
 
@@ -1172,10 +5904,39 @@ pub mod git {
 
             print_output(output);
 
+            if !init.default_branch.trim().is_empty() {
+                info!("Configuring default branch...");
+
+                let output = exec_git_config_global("init.defaultBranch", &init.default_branch)?;
+
+                print_output(output);
+            }
+
+            if !alias.is_empty() {
+                info!("Configuring Git aliases...");
+            }
+
+            for (name, command) in &alias {
+                let output = exec_git_config_global(&alias_key(name)?, command)?;
+
+                print_output(output);
+            }
+
             Ok(())
         }
     }
 
+    fn alias_key(name: &str) -> Result<String, String> {
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+        if valid {
+            Ok(format!("alias.{name}"))
+        } else {
+            Err(format!("Invalid Git alias name {name}"))
+        }
+    }
+
     fn exec_git_config_global(
         arg1: &str,
         arg2: &str,
@@ -1225,4 +5986,522 @@ pub mod git {
                 error,
             ))
     }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::image::server::git::{alias_key, GitConfig};
+
+        #[test]
+        fn reads_config_without_optional_sections() {
+            let config: GitConfig = serde_json::from_str(r#"{
+                "core": { "excludes_file": "" },
+                "user": { "name": "", "email": "", "signing_key": "" },
+                "commit": { "gpg_sign": false },
+                "git_ignore": []
+            }"#).unwrap();
+
+            assert!(config.core.editor.is_empty());
+            assert!(config.init.default_branch.is_empty());
+            assert!(config.alias.is_empty());
+        }
+
+        #[test]
+        fn builds_alias_key() {
+            assert_eq!(Ok("alias.co".to_string()), alias_key("co"));
+            assert_eq!(Ok("alias.last-log".to_string()), alias_key("last-log"));
+            assert!(alias_key("").is_err());
+            assert!(alias_key("co.x").is_err());
+            assert!(alias_key("-co").is_err());
+        }
+    }
+}
+
+pub mod shell {
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::image::detection::Detection;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Shell;
+    use crate::image::{Config, Image, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{write_block, BlockMarkers};
+    use crate::os::Os;
+    use crate::package::{Package, Software};
+
+    const BLOCK_MARKERS: BlockMarkers = ("# >>> mathswe-ops shell >>>", "# <<< mathswe-ops shell <<<");
+
+    #[derive(Clone)]
+    pub struct ShellImage(ServerImage);
+
+    impl ShellImage {
+        pub fn new(os: Os) -> Self {
+            let id = Shell;
+            let pkg_name = id.to_string();
+            let version = "latest";
+
+            ShellImage(ServerImage(
+                id,
+                Package::new_managed(
+                    &pkg_name,
+                    os,
+                    Software::new("MathSwe", "Shell Environment", version),
+                    Url::parse("https://www.gnu.org/software/bash/manual/bash.html#Bash-Startup-Files").unwrap(),
+                ),
+            ))
+        }
+
+        /// The existing rc files of the OS and `~/.zshrc`, or the main one.
+        fn rc_files(&self, home: &Path) -> Result<Vec<PathBuf>, String> {
+            let os_rc_files = self.0.package().os.shell_rc_files()?;
+            let mut rc_files = os_rc_files
+                .iter()
+                .chain(Some(&".zshrc").filter(|zshrc| !os_rc_files.contains(zshrc)))
+                .map(|rc| home.join(rc))
+                .filter(|rc| rc.exists())
+                .collect::<Vec<PathBuf>>();
+
+            if rc_files.is_empty() {
+                rc_files.extend(os_rc_files.first().map(|rc| home.join(rc)));
+            }
+
+            Ok(rc_files)
+        }
+    }
+
+    impl Install for ShellImage {
+        fn install(&self) -> Result<(), String> {
+            info!("Shell environment has nothing to install, its config manages the shell rc files.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for ShellImage {
+        fn uninstall(&self) -> Result<(), String> {
+            let home = dirs::home_dir()
+                .ok_or("Fail to read the home directory")?;
+
+            for rc in self.rc_files(&home)?.iter().filter(|rc| rc.exists()) {
+                info!("Removing shell environment from {}...", rc.display());
+
+                write_block(rc, BLOCK_MARKERS, None)?;
+            }
+
+            info!("Shell environment uninstalled.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for ShellImage {
+        image_ops_impl!();
+
+        fn detection(&self) -> Detection {
+            Detection::binary("bash", &["--version"])
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct ShellConfig {
+        aliases: BTreeMap<String, String>,
+        path: Vec<String>,
+        env: BTreeMap<String, String>,
+    }
+
+    type ShellImageConfig = ImageConfig<ShellImage, ShellConfig>;
+
+    impl ToImageConfig<ShellConfig> for ShellImage {
+        fn to_image_config(&self, config: ShellConfig) -> ShellImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for ShellImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let block = block_lines(&self.1)?;
+            let home = dirs::home_dir()
+                .ok_or("Fail to read the home directory")?;
+
+            for rc in self.0.rc_files(&home)? {
+                info!("Writing shell environment to {}...", rc.display());
+
+                write_block(&rc, BLOCK_MARKERS, Some(&block))?;
+            }
+
+            info!("Shell environment configured. Open a new shell to load it.");
+
+            Ok(())
+        }
+    }
+
+    fn check_name(name: &str, is_variable: bool) -> Result<(), String> {
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || (!is_variable && first != '-'))
+            && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || (!is_variable && matches!(c, '-' | '.')));
+
+        match (valid, is_variable) {
+            (true, _) => Ok(()),
+            (false, true) => Err(format!("Invalid environment variable name {name}")),
+            (false, false) => Err(format!("Invalid alias name {name}")),
+        }
+    }
+
+    fn single_quote(value: &str) -> String {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+
+    /// Double quotes expand variables, like `$HOME`.
+    fn double_quote(value: &str) -> String {
+        let escaped = value
+            .replace('\\', r"\\")
+            .replace('"', "\\\"")
+            .replace('`', "\\`");
+
+        format!("\"{escaped}\"")
+    }
+
+    fn block_lines(ShellConfig { aliases, path, env }: &ShellConfig) -> Result<Vec<String>, String> {
+        let mut lines = vec![];
+
+        if !path.is_empty() {
+            if let Some(dir) = path.iter().find(|dir| dir.is_empty() || dir.contains([':', '\n'])) {
+                return Err(format!("Invalid PATH directory {dir}"));
+            }
+
+            lines.push(format!("export PATH={}", double_quote(&format!("{}:$PATH", path.join(":")))));
+        }
+
+        for (name, value) in env {
+            check_name(name, true)?;
+
+            lines.push(format!("export {name}={}", double_quote(value)));
+        }
+
+        for (name, value) in aliases {
+            check_name(name, false)?;
+
+            lines.push(format!("alias {name}={}", single_quote(value)));
+        }
+
+        Ok(lines)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::BTreeMap;
+
+        use crate::image::server::shell::{block_lines, check_name, ShellConfig};
+
+        #[test]
+        fn writes_shell_block() {
+            let config = ShellConfig {
+                aliases: BTreeMap::from([("ll".to_string(), "ls -alF".to_string()), ("say".to_string(), "echo 'hi'".to_string())]),
+                path: vec!["$HOME/.local/bin".to_string(), "/opt/tools/bin".to_string()],
+                env: BTreeMap::from([("EDITOR".to_string(), "vim".to_string()), ("GREETING".to_string(), r#"say "hi""#.to_string())]),
+            };
+
+            assert_eq!(
+                vec![
+                    r#"export PATH="$HOME/.local/bin:/opt/tools/bin:$PATH""#,
+                    r#"export EDITOR="vim""#,
+                    r#"export GREETING="say \"hi\"""#,
+                    "alias ll='ls -alF'",
+                    r"alias say='echo '\''hi'\'''",
+                ],
+                block_lines(&config).unwrap(),
+            );
+        }
+
+        #[test]
+        fn checks_shell_names() {
+            assert!(check_name("JAVA_HOME", true).is_ok());
+            assert!(check_name("_private", true).is_ok());
+            assert!(check_name("1ABC", true).is_err());
+            assert!(check_name("MY-VAR", true).is_err());
+            assert!(check_name("g.st", false).is_ok());
+            assert!(check_name("-x", false).is_err());
+            assert!(check_name("rm;ls", false).is_err());
+        }
+    }
+}
+
+pub mod ssh {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+
+    use reqwest::Url;
+    use serde::{Deserialize, Serialize};
+
+    use crate::cmd::{exec_cmd, print_output};
+    use crate::image::detection::Detection;
+    use crate::image::server::ServerImage;
+    use crate::image::server::ServerImageId::Ssh;
+    use crate::image::{Config, Image, ImageConfig, ImageOps, Install, ToImageConfig, Uninstall};
+    use crate::image_ops_impl;
+    use crate::os::linux::{write_block, BlockMarkers};
+    use crate::os::Os;
+    use crate::os::PkgType::{Brew, Deb, Pacman, Rpm};
+    use crate::package::{Package, Software};
+
+    const BLOCK_MARKERS: BlockMarkers = ("# >>> mathswe-ops ssh >>>", "# <<< mathswe-ops ssh <<<");
+
+    const KEY_FILE: &str = "id_ed25519";
+
+    #[derive(Clone)]
+    pub struct SshImage(ServerImage);
+
+    impl SshImage {
+        pub fn new(os: Os) -> Self {
+            let id = Ssh;
+            let pkg_name = id.to_string();
+            let version = "latest";
+
+            SshImage(ServerImage(
+                id,
+                Package::new_managed(
+                    &pkg_name,
+                    os,
+                    Software::new("OpenBSD", "OpenSSH", version),
+                    Url::parse("https://www.openssh.com").unwrap(),
+                ),
+            ))
+        }
+    }
+
+    fn ssh_dir() -> Result<PathBuf, String> {
+        dirs::home_dir()
+            .map(|home| home.join(".ssh"))
+            .ok_or_else(|| "Fail to read the home directory".to_string())
+    }
+
+    fn set_mode(path: &Path, mode: u32) -> Result<(), String> {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .map_err(|error| format!("Fail to set the permissions of {}: {error}", path.display()))
+    }
+
+    impl Install for SshImage {
+        fn install(&self) -> Result<(), String> {
+            if exec_cmd("bash", &["-c", "command -v ssh-keygen"]).is_ok() {
+                info!("OpenSSH client already installed.");
+
+                return Ok(());
+            }
+
+            info!("Installing OpenSSH client...");
+
+            let os = self.0.package().os;
+            let output = match os.pkg_type() {
+                Deb => exec_cmd("sudo", &["apt-get", "--yes", "install", "openssh-client"]),
+                Rpm => exec_cmd("sudo", &["dnf", "--assumeyes", "install", "openssh-clients"]),
+                Pacman => exec_cmd("sudo", &["pacman", "--sync", "--noconfirm", "openssh"]),
+                Brew => exec_cmd("brew", &["install", "openssh"]),
+                pkg_type => return Err(format!("OpenSSH client doesn't install with {pkg_type:?} packages")),
+            }.map_err(|error| error.to_string())?;
+
+            print_output(output);
+
+            info!("OpenSSH client installed.");
+
+            Ok(())
+        }
+    }
+
+    impl Uninstall for SshImage {
+        /// It removes the managed hosts only since Git depends on the client.
+        fn uninstall(&self) -> Result<(), String> {
+            let config_file = ssh_dir()?.join("config");
+
+            if config_file.exists() {
+                info!("Removing SSH hosts from {}...", config_file.display());
+
+                write_block(&config_file, BLOCK_MARKERS, None)?;
+            }
+
+            info!("SSH hosts uninstalled. The OpenSSH client and keys remain.");
+
+            Ok(())
+        }
+    }
+
+    impl ImageOps for SshImage {
+        image_ops_impl!();
+
+        // ssh -V prints its version to the standard error
+        fn detection(&self) -> Detection {
+            Detection::binary("ssh-keygen", &[])
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct SshHost {
+        host: String,
+        host_name: Option<String>,
+        user: Option<String>,
+        port: Option<u16>,
+        identity_file: Option<String>,
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct SshConfig {
+        key_comment: String,
+        hosts: Vec<SshHost>,
+    }
+
+    type SshImageConfig = ImageConfig<SshImage, SshConfig>;
+
+    impl ToImageConfig<SshConfig> for SshImage {
+        fn to_image_config(&self, config: SshConfig) -> SshImageConfig {
+            ImageConfig(self.clone(), config)
+        }
+    }
+
+    impl Config for SshImageConfig {
+        fn config(&self) -> Result<(), String> {
+            let SshConfig { key_comment, hosts } = &self.1;
+            let block = host_lines(hosts)?;
+            let ssh_dir = ssh_dir()?;
+            let key_file = ssh_dir.join(KEY_FILE);
+            let config_file = ssh_dir.join("config");
+
+            fs::create_dir_all(&ssh_dir)
+                .map_err(|error| error.to_string())?;
+
+            set_mode(&ssh_dir, 0o700)?;
+
+            if key_file.exists() {
+                info!("SSH key {} already exists.", key_file.display());
+            } else {
+                info!("Generating SSH key {}...", key_file.display());
+
+                // Without a passphrase, so unattended provisioning can
+                // finish, which `ssh-keygen -p` can add later
+                let output = exec_cmd(
+                    "ssh-keygen",
+                    &["-t", "ed25519", "-q", "-N", "", "-C", key_comment, "-f", &key_file.to_string_lossy()],
+                ).map_err(|error| error.to_string())?;
+
+                print_output(output);
+            }
+
+            set_mode(&key_file, 0o600)?;
+
+            info!("Writing SSH hosts to {}...", config_file.display());
+
+            write_block(&config_file, BLOCK_MARKERS, Some(&block))?;
+            set_mode(&config_file, 0o600)?;
+
+            let public_key_file = key_file.with_extension("pub");
+            let public_key = fs::read_to_string(&public_key_file)
+                .map_err(|error| format!("Fail to read {}: {error}", public_key_file.display()))?;
+
+            set_mode(&public_key_file, 0o644)?;
+
+            info!("SSH configured. Upload the public key to the hosts you connect to:");
+
+            println!("{}", public_key.trim());
+
+            Ok(())
+        }
+    }
+
+    fn check_value(keyword: &str, value: &str, allows_spaces: bool) -> Result<(), String> {
+        let valid = !value.trim().is_empty()
+            && !value.chars().any(|c| c.is_control() || (!allows_spaces && c.is_whitespace()));
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Invalid SSH {keyword} {value}"))
+        }
+    }
+
+    fn host_lines(hosts: &[SshHost]) -> Result<Vec<String>, String> {
+        let mut lines = vec![];
+
+        for SshHost { host, host_name, user, port, identity_file } in hosts {
+            check_value("Host", host, true)?;
+
+            lines.push(format!("Host {}", host.trim()));
+
+            let options = [
+                ("HostName", host_name.clone()),
+                ("User", user.clone()),
+                ("Port", port.map(|port| port.to_string())),
+                ("IdentityFile", identity_file.clone()),
+                ("IdentitiesOnly", identity_file.as_ref().map(|_| "yes".to_string())),
+            ];
+
+            for (keyword, value) in options {
+                if let Some(value) = value {
+                    check_value(keyword, &value, false)?;
+
+                    lines.push(format!("    {keyword} {value}"));
+                }
+            }
+        }
+
+        Ok(lines)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::image::server::ssh::{host_lines, SshHost};
+
+        #[test]
+        fn writes_host_blocks() {
+            let hosts = vec![
+                SshHost {
+                    host: "github.com".to_string(),
+                    host_name: None,
+                    user: Some("git".to_string()),
+                    port: None,
+                    identity_file: Some("~/.ssh/id_ed25519".to_string()),
+                },
+                SshHost {
+                    host: "build *.internal".to_string(),
+                    host_name: Some("build.example.com".to_string()),
+                    user: None,
+                    port: Some(2222),
+                    identity_file: None,
+                },
+            ];
+
+            assert_eq!(
+                vec![
+                    "Host github.com",
+                    "    User git",
+                    "    IdentityFile ~/.ssh/id_ed25519",
+                    "    IdentitiesOnly yes",
+                    "Host build *.internal",
+                    "    HostName build.example.com",
+                    "    Port 2222",
+                ],
+                host_lines(&hosts).unwrap(),
+            );
+        }
+
+        #[test]
+        fn checks_host_values() {
+            let host = |host: &str, user: &str| SshHost {
+                host: host.to_string(),
+                host_name: None,
+                user: Some(user.to_string()),
+                port: None,
+                identity_file: None,
+            };
+
+            assert!(host_lines(&[host("", "git")]).is_err());
+            assert!(host_lines(&[host("github.com\nHost *", "git")]).is_err());
+            assert!(host_lines(&[host("github.com", "git ProxyCommand")]).is_err());
+        }
+    }
 }
@@ -4,8 +4,14 @@
 
 
 use clap::{Parser};
+use std::process;
+
 use crate::main::cli::SystemCli;
+use crate::main::exit_code::ExitCode;
+use crate::output::set_verbosity;
 
+#[macro_use]
+mod output;
 mod tmp;
 mod download;
 mod resources;
@@ -20,14 +26,54 @@ mod main {
     pub mod batch;
     pub mod exec;
     pub mod cli;
+    pub mod list;
+    pub mod status;
+    pub mod info;
+    pub mod search;
+    pub mod doctor;
+    pub mod manifest;
+    pub mod profile;
+    pub mod journal;
+    pub mod history;
+    pub mod clean;
+    pub mod verify;
+    pub mod pin;
+    pub mod interactive;
+    pub mod export;
+    pub mod apply;
+    pub mod self_update;
+    pub mod exit_code;
+    pub mod schedule;
+    pub mod resume;
+    pub mod config_edit;
+    pub mod fetch;
+    pub mod lock;
+    pub mod container;
 }
 
-fn main() {
+fn main() -> process::ExitCode {
     let cli = SystemCli::parse();
-    let exec = cli.operation.execute();
 
-    match exec {
-        Ok(_) => println!("Execution successful"),
-        Err(err) => eprintln!("{}", format!("Fail to execute: {}", err))
-    }
+    set_verbosity(cli.verbosity());
+
+    let exec = cli.execute();
+
+    // Query commands print their own output, which may be machine-readable
+    let is_batch = cli.operation.to_operation().is_some();
+
+    let code = match exec {
+        Ok(_) => {
+            if is_batch {
+                info!("Execution successful");
+            }
+
+            ExitCode::Success
+        }
+        Err(err) => {
+            eprintln!("{}", format!("Fail to execute: {}", err));
+            err.code
+        }
+    };
+
+    code.into()
 }
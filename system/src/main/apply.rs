@@ -0,0 +1,146 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use crate::image::detection::InstallStatus::NotInstalled;
+use crate::image::repository::Repository;
+use crate::main::batch::BatchOperation;
+use crate::main::exit_code::{CliError, ExitCode};
+use crate::main::exec::{OperationContext, OperationExecution};
+use crate::main::manifest::Manifest;
+use crate::main::system::Operation;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    Install(String),
+    Upgrade(String),
+    Uninstall(String),
+    Keep(String),
+}
+
+/// Installed repository images missing from the manifest get uninstalled.
+pub struct Plan(Vec<Change>);
+
+impl Plan {
+    pub fn from(ctx: &OperationContext, manifest: &Manifest) -> Result<Self, String> {
+        let desired = manifest.image_ids();
+        let mut changes = Vec::new();
+
+        for id_raw in &desired {
+            let image = ctx.load_image(id_raw)?;
+            let status = image.detection().detect()?;
+            let version = image.image().package().software.version;
+
+            let change = match status.has_version(&version) {
+                _ if status == NotInstalled => Change::Install(id_raw.clone()),
+                Some(false) => Change::Upgrade(id_raw.clone()),
+                _ => Change::Keep(id_raw.clone()),
+            };
+
+            changes.push(change);
+        }
+
+        let undesired = Repository::desktop_image_ids()
+            .into_iter()
+            .chain(Repository::server_image_ids())
+            .map(|id| id.to_string())
+            .filter(|id_raw| !desired.contains(id_raw));
+
+        for id_raw in undesired {
+            let installed = ctx
+                .load_image(&id_raw)
+                .and_then(|image| image.detection().detect())
+                .is_ok_and(|status| status != NotInstalled);
+
+            if installed {
+                changes.push(Change::Uninstall(id_raw));
+            }
+        }
+
+        Ok(Plan(changes))
+    }
+
+    pub fn print(&self) {
+        for change in &self.0 {
+            match change {
+                Change::Install(id) => println!("  + install {id}"),
+                Change::Upgrade(id) => println!("  ~ upgrade {id}"),
+                Change::Uninstall(id) => println!("  - uninstall {id}"),
+                Change::Keep(id) => println!("    keep {id}"),
+            }
+        }
+    }
+
+    fn ids_of(&self, matches: impl Fn(&Change) -> Option<&String>) -> Vec<String> {
+        self.0.iter().filter_map(matches).cloned().collect()
+    }
+
+    pub fn is_converged(&self) -> bool {
+        self.0.iter().all(|change| matches!(change, Change::Keep(_)))
+    }
+
+    /// Uninstalls first to free what the new images may need.
+    pub fn apply(
+        &self,
+        exec: &OperationExecution,
+        manifest: &Manifest,
+        parallel: usize,
+    ) -> Result<(), CliError> {
+        let uninstalls = self.ids_of(|change| match change {
+            Change::Uninstall(id) => Some(id),
+            _ => None,
+        });
+
+        let installs = self.ids_of(|change| match change {
+            Change::Install(id) => Some(id),
+            _ => None,
+        });
+
+        let upgrades = self.ids_of(|change| match change {
+            Change::Upgrade(id) => Some(id),
+            _ => None,
+        });
+
+        let batch = |operation: Operation| BatchOperation { operation, parallel };
+        let mut results = Vec::new();
+
+        if !uninstalls.is_empty() {
+            results.push(batch(Operation::Uninstall).execute(&uninstalls, |id_raw| exec.uninstall(id_raw, &false)));
+        }
+
+        if !installs.is_empty() {
+            exec.prefetch_installs(&installs);
+            results.push(batch(Operation::Install).execute(&installs, |id_raw| {
+                exec.install(id_raw, &manifest.config_of(id_raw, false))
+            }));
+        }
+
+        if !upgrades.is_empty() {
+            exec.prefetch_upgrades(&upgrades);
+            results.push(batch(Operation::Upgrade).execute(&upgrades, |id_raw| exec.upgrade(id_raw)));
+        }
+
+        let errors = results
+            .into_iter()
+            .filter_map(Result::err)
+            .collect::<Vec<CliError>>();
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        let code = if errors.iter().all(|error| error.code == ExitCode::AllFailed) {
+            ExitCode::AllFailed
+        } else {
+            ExitCode::PartialFailure
+        };
+
+        let msg = errors
+            .iter()
+            .map(|error| error.msg.clone())
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        Err(CliError::new(code, msg))
+    }
+}
@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
-use std::iter::Map;
-use std::slice::Iter;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
 use Operation::Config;
 use crate::image::ImageId;
+use crate::main::exit_code::{check_image_ids, CliError, ExitCode};
 use crate::main::system::Operation;
-use crate::main::system::Operation::{Install, Reinstall, Uninstall};
+use crate::main::system::Operation::{Install, Reinstall, Uninstall, Upgrade};
 
 pub struct BatchReport {
     ok_num: i32,
@@ -16,7 +18,7 @@ pub struct BatchReport {
 
 impl BatchReport {
     pub fn from(
-        result: Map<Iter<'_, String>, impl Fn(&String) -> Result<ImageId, String>>
+        result: impl Iterator<Item=Result<ImageId, String>>
     ) -> Self {
         let empty_report = (0, Vec::new());
 
@@ -44,37 +46,78 @@ impl BatchReport {
 
 pub struct BatchOperation {
     pub operation: Operation,
+
+    /// Package manager commands still run one at a time.
+    pub parallel: usize,
 }
 
 impl BatchOperation {
     pub fn execute(
         &self,
         images: &Vec<String>,
-        exec: impl Fn(&String) -> Result<ImageId, String>,
-    ) -> Result<(), String> {
-        let result = images
-            .iter()
-            .map(exec);
-
-        let report = BatchReport::from(result);
+        exec: impl Fn(&String) -> Result<ImageId, String> + Sync,
+    ) -> Result<(), CliError> {
+        check_image_ids(images)?;
+
+        let report = if self.parallel > 1 {
+            BatchReport::from(Self::execute_parallel(images, exec, self.parallel).into_iter())
+        } else {
+            BatchReport::from(images.iter().map(exec))
+        };
 
         self.print_batch_report(report)
     }
 
+    /// Returns the results in the order of the images.
+    fn execute_parallel(
+        images: &[String],
+        exec: impl Fn(&String) -> Result<ImageId, String> + Sync,
+        workers: usize,
+    ) -> Vec<Result<ImageId, String>> {
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::new());
+
+        thread::scope(|scope| {
+            for _ in 0..workers.min(images.len()) {
+                scope.spawn(|| {
+                    loop {
+                        let index = next.fetch_add(1, Ordering::SeqCst);
+                        let Some(id_raw) = images.get(index) else { break };
+                        let result = exec(id_raw);
+
+                        results
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner())
+                            .push((index, result));
+                    }
+                });
+            }
+        });
+
+        let mut results = results
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
     pub fn print_batch_report(
         &self,
         BatchReport { ok_num, failed }: BatchReport,
-    ) -> Result<(), String> {
+    ) -> Result<(), CliError> {
         let report = (ok_num, failed);
 
         match report.clone() {
             (ok_num, err_ids) if err_ids.is_empty() => {
-                println!("{}", self.batch_report_success_msg(ok_num));
+                info!("{}", self.batch_report_success_msg(ok_num));
                 Ok(())
             }
-            (_, err_ids) => {
-                println!("{}", self.batch_report_fail_msg(err_ids));
-                Err(self.batch_report_msg(report))
+            (ok_num, err_ids) => {
+                let code = if ok_num == 0 { ExitCode::AllFailed } else { ExitCode::PartialFailure };
+
+                info!("{}", self.batch_report_fail_msg(err_ids));
+                Err(CliError::new(code, self.batch_report_msg(report)))
             }
         }
     }
@@ -84,6 +127,7 @@ impl BatchOperation {
             Install => format!("{} images successfully installed; {} images failed to install.", ok_num, err_ids.len()),
             Uninstall => format!("{} images successfully uninstalled; {} images failed to uninstall.", ok_num, err_ids.len()),
             Reinstall => format!("{} images successfully reinstalled; {} images failed to reinstall.", ok_num, err_ids.len()),
+            Upgrade => format!("{} images successfully upgraded or up to date; {} images failed to upgrade.", ok_num, err_ids.len()),
             Config => format!("{} images successfully configured; {} images failed to configure.", ok_num, err_ids.len()),
         }
     }
@@ -95,6 +139,7 @@ impl BatchOperation {
             Install => format!("✅ Install {} image{}.", ok_num, plural),
             Uninstall => format!("✅ Uninstall {} image{}.", ok_num, plural),
             Reinstall => format!("✅ Reinstall {} image{}.", ok_num, plural),
+            Upgrade => format!("✅ Upgrade {} image{}.", ok_num, plural),
             Config => format!("✅ Config {} image{}.", ok_num, plural),
         }
     }
@@ -106,7 +151,39 @@ impl BatchOperation {
             Install => format!("❌ Fail to install {} image{}: {:?}", err_ids.len(), plural, err_ids),
             Uninstall => format!("❌ Fail to uninstall {} image{}: {:?}", err_ids.len(), plural, err_ids),
             Reinstall => format!("❌ Fail to reinstall {} image{}: {:?}", err_ids.len(), plural, err_ids),
+            Upgrade => format!("❌ Fail to upgrade {} image{}: {:?}", err_ids.len(), plural, err_ids),
             Config => format!("❌ Fail to config {} image{}: {:?}", err_ids.len(), plural, err_ids),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::image::repository::Repository;
+    use crate::main::batch::BatchOperation;
+
+    #[test]
+    fn parallel_execution_keeps_image_order() {
+        let images = Repository::server_image_ids()
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>();
+
+        let exec = |id_raw: &String| {
+            let loader = Repository::image_loader_from(id_raw)?;
+
+            if id_raw.starts_with('r') {
+                Err(id_raw.clone())
+            } else {
+                Ok(loader.to_image_id())
+            }
+        };
+
+        let ids = BatchOperation::execute_parallel(&images, exec, 4)
+            .into_iter()
+            .map(|result| result.map_or_else(|id_raw| id_raw, |id| id.to_string()))
+            .collect::<Vec<String>>();
+
+        assert_eq!(images, ids);
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::image::detection::Detection;
+use crate::image::detection::InstallStatus::NotInstalled;
+use crate::tmp;
+
+/// Older temporary directories belong to interrupted operations.
+const STALE_TMP_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Relative to the home, with the detection of the software they belong to.
+fn kept_artifacts() -> Vec<(&'static str, Detection)> {
+    vec![
+        (".local/src/ImageMagick", Detection::binary("magick", &[])),
+    ]
+}
+
+pub struct Leftover {
+    reason: &'static str,
+    path: PathBuf,
+    size: u64,
+}
+
+pub struct Cleanup(Vec<Leftover>);
+
+impl Cleanup {
+    pub fn scan() -> Result<Self, String> {
+        let mut paths = Vec::new();
+
+        if let Some(cache_dir) = tmp::cache_dir().filter(|dir| dir.exists()) {
+            paths.push(("download cache", cache_dir));
+        }
+
+        paths.extend(stale_tmp_dirs()?.into_iter().map(|dir| ("stale temp dir", dir)));
+        paths.extend(orphaned_artifacts()?.into_iter().map(|dir| ("orphaned artifact", dir)));
+
+        let leftovers = paths
+            .into_iter()
+            .map(|(reason, path)| Leftover { reason, size: disk_size(&path), path })
+            .collect();
+
+        Ok(Cleanup(leftovers))
+    }
+
+    pub fn run(&self, dry_run: bool) -> Result<(), String> {
+        if self.0.is_empty() {
+            info!("Nothing to clean.");
+            return Ok(());
+        }
+
+        let mut failed = Vec::new();
+
+        for Leftover { reason, path, size } in &self.0 {
+            let size = format_size(*size);
+
+            if dry_run {
+                info!("Would remove {reason} {} ({size})", path.display());
+                continue;
+            }
+
+            match fs::remove_dir_all(path) {
+                Ok(_) => info!("✅ Remove {reason} {} ({size})", path.display()),
+                Err(error) => {
+                    info!("❌ Fail to remove {reason} {}: {error}", path.display());
+                    failed.push(path.clone());
+                }
+            }
+        }
+
+        let total = format_size(self.0.iter().map(|leftover| leftover.size).sum());
+        let verb = if dry_run { "Would free" } else { "Freed" };
+
+        info!("{verb} {total}.");
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Fail to remove {:?}", failed))
+        }
+    }
+}
+
+fn stale_tmp_dirs() -> Result<Vec<PathBuf>, String> {
+    let now = SystemTime::now();
+    let is_stale = |path: &Path| fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(|modified| now.duration_since(modified).unwrap_or_default() > STALE_TMP_AGE)
+        .unwrap_or(false);
+
+    let dirs = fs::read_dir(std::env::temp_dir())
+        .map_err(|error| error.to_string())?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(tmp::TMP_DIR_PREFIX))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && is_stale(path))
+        .collect();
+
+    Ok(dirs)
+}
+
+fn orphaned_artifacts() -> Result<Vec<PathBuf>, String> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| "Fail to read the user home directory".to_string())?;
+
+    let mut orphans = Vec::new();
+
+    for (rel_path, detection) in kept_artifacts() {
+        let path = home.join(rel_path);
+
+        if path.is_dir() && detection.detect()? == NotInstalled {
+            orphans.push(path);
+        }
+    }
+
+    Ok(orphans)
+}
+
+fn disk_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else { return 0 };
+
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+
+    fs::read_dir(path)
+        .map(|entries| entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| disk_size(&entry.path()))
+            .sum()
+        )
+        .unwrap_or(0)
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::main::clean::{disk_size, format_size};
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!("512 B", format_size(512));
+        assert_eq!("1.5 KiB", format_size(1536));
+        assert_eq!("2.0 GiB", format_size(2 * 1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn sums_nested_file_sizes() {
+        let dir = tempdir().unwrap();
+
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("a.txt"), [0; 100]).unwrap();
+        fs::write(dir.path().join("nested").join("b.txt"), [0; 28]).unwrap();
+
+        assert_eq!(128, disk_size(dir.path()));
+    }
+}
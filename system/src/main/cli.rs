@@ -2,77 +2,675 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
+use crate::main::apply::Plan;
 use crate::main::batch::BatchOperation;
-use crate::main::cli::CliCommand::{Install, Reinstall, Uninstall};
+use crate::main::cli::CliCommand::{Apply, Clean, Doctor, Export, Fetch, History, Info, Install, Interactive, List, Pin, Provision, Reinstall, Schedule, Search, SelfUpdate, Status, Uninstall, Upgrade, Verify};
+use crate::download;
+use crate::download::tls;
+use crate::download::tls::PublicKeyPin;
+use crate::download::{DownloadOptions, Timeouts};
+use crate::image::repository::Repository;
+use crate::main::clean::Cleanup;
+use crate::main::config_edit;
+use crate::main::container;
+use crate::main::container::Target;
+use crate::main::doctor::Diagnosis;
 use crate::main::exec::{OperationContext, OperationExecution};
+use crate::main::exit_code::{check_image_ids, CliError, ExitCode};
+use crate::main::history::OperationHistory;
+use crate::main::info::ImageDetails;
+use crate::main::interactive::ImagePicker;
+use crate::main::list::ImageList;
+use crate::main::lock::{LockSettings, DEFAULT_LOCKFILE_PATH};
+use crate::main::export;
+use crate::main::fetch;
+use crate::main::manifest::{Manifest, ManifestFormat};
+use crate::main::pin;
+use crate::main::profile::{Profiles, DEFAULT_PROFILES_PATH};
+use crate::main::resume;
+use crate::main::resume::{InstallProgress, ProgressStore};
+use crate::main::schedule;
+use crate::main::schedule::Frequency;
+use crate::main::search::ImageSearch;
+use crate::main::self_update::self_update;
+use crate::main::status::StatusReport;
 use crate::main::system::Operation;
-use clap::{Parser, Subcommand};
+use crate::main::verify::VerifyReport;
+use clap::builder::RangedU64ValueParser;
+use crate::output::Verbosity;
+use clap::{ArgAction, ArgGroup, Args, Parser, Subcommand};
 use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::slice;
+use std::time::Duration;
 use CliCommand::Config;
 
+#[derive(Args)]
+pub struct BatchArgs {
+    /// Number of images to operate concurrently.
+    #[arg(long, default_value_t = 1, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    parallel: usize,
+}
+
+#[derive(Subcommand)]
+pub enum ScheduleAction {
+    /// Runs `upgrade --all` periodically with a systemd user timer.
+    #[command(group(ArgGroup::new("frequency").required(true).args(["daily", "weekly"])))]
+    Upgrade {
+        #[arg(long)]
+        daily: bool,
+
+        #[arg(long)]
+        weekly: bool,
+    },
+
+    /// Disables and removes the scheduled upgrade.
+    Remove,
+}
+
 #[derive(Subcommand)]
 pub enum CliCommand {
     Install {
-        #[arg(required = true)]
+        #[arg(required_unless_present_any = ["from_file", "resume"], conflicts_with_all = ["from_file", "resume"])]
         images: Vec<String>,
 
         #[arg(long)]
         config: bool,
+
+        /// Reads the images to install from a JSON manifest like `system.json`.
+        #[arg(long, value_name = "FILE", conflicts_with = "resume")]
+        from_file: Option<PathBuf>,
+
+        /// Retries the images of the last install batch that didn't succeed.
+        #[arg(long, conflicts_with = "config")]
+        resume: bool,
+
+        /// Installs into a container of a base image, like `docker:ubuntu:24.04`.
+        #[arg(long, value_name = "TARGET", conflicts_with_all = ["from_file", "resume"])]
+        target: Option<Target>,
+
+        #[command(flatten)]
+        batch: BatchArgs,
     },
     Uninstall {
         #[arg(required = true)]
         images: Vec<String>,
+
+        /// Also removes the user configuration of the images.
+        #[arg(long)]
+        purge: bool,
+
+        #[command(flatten)]
+        batch: BatchArgs,
     },
     Reinstall {
         #[arg(required = true)]
         images: Vec<String>,
+
+        #[command(flatten)]
+        batch: BatchArgs,
     },
     Config {
+        #[arg(required_unless_present = "edit", conflicts_with = "edit")]
+        images: Vec<String>,
+
+        /// Opens the config file of the image in `$EDITOR`.
+        #[arg(long, value_name = "IMAGE")]
+        edit: Option<String>,
+
+        #[command(flatten)]
+        batch: BatchArgs,
+    },
+
+    /// Reinstalls the images whose installed version differs from the pinned one.
+    Upgrade {
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        images: Vec<String>,
+
+        /// Upgrades every image in the repository that is installed.
+        #[arg(long)]
+        all: bool,
+
+        #[command(flatten)]
+        batch: BatchArgs,
+    },
+
+    /// Installs the images of a named profile, like `desktop-dev`.
+    Provision {
+        profile: String,
+
+        /// Reads the profiles from this JSON file instead of `profiles.json`.
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_PROFILES_PATH)]
+        file: PathBuf,
+
+        #[command(flatten)]
+        batch: BatchArgs,
+    },
+
+    /// Picks the images to install from a terminal UI.
+    Interactive,
+
+    /// Converges the machine to a desired-state manifest.
+    Apply {
+        manifest: PathBuf,
+
+        /// Shows the plan without changing the machine.
+        #[arg(long)]
+        dry_run: bool,
+
+        #[command(flatten)]
+        batch: BatchArgs,
+    },
+
+    /// Lists the images available in the repository by category.
+    List {
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Checks whether the images are present on the machine.
+    Status {
+        #[arg(required = true)]
+        images: Vec<String>,
+    },
+
+    /// Shows the metadata of an image without installing it.
+    Info {
+        image: String,
+    },
+
+    /// Finds images by their IDs, software names, and providers.
+    Search {
+        query: String,
+    },
+
+    /// Checks the preconditions the image installers rely on.
+    Doctor,
+
+    /// Shows the operations recorded in the journal, from the oldest.
+    History {
+        /// Shows only the operations of this image.
+        #[arg(long)]
+        image: Option<String>,
+
+        /// Maximum number of latest operations to show.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Removes the download cache and stale temporary files.
+    Clean {
+        /// Shows what would be removed without removing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Checks the installed files of the images for tampering or corruption.
+    Verify {
+        #[arg(required = true)]
+        images: Vec<String>,
+    },
+
+    /// Holds an image at a version over the one in the repository.
+    Pin {
+        image: String,
+
+        #[arg(required_unless_present = "remove", conflicts_with = "remove")]
+        version: Option<String>,
+
+        /// Integrity hash of the pinned version from the vendor site.
+        #[arg(long)]
+        hash_sha256: Option<String>,
+
+        /// Removes the pin so the image follows the repository version again.
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Writes the installed images into a manifest for `install --from-file`.
+    Export {
+        #[arg(long, value_enum, default_value_t = ManifestFormat::Json)]
+        format: ManifestFormat,
+
+        /// Writes the manifest to this file instead of the standard output.
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Replaces this executable with the latest released one.
+    SelfUpdate,
+
+    /// Downloads and checks the artifacts of the images without installing them.
+    Fetch {
         #[arg(required = true)]
         images: Vec<String>,
+
+        /// Directory to save the artifacts to.
+        #[arg(long, value_name = "DIR")]
+        dest: PathBuf,
+
+        /// Number of artifacts to download concurrently.
+        #[arg(long, default_value_t = 4, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        parallel: usize,
+
+        /// Times to retry an artifact download after a server error or a timeout.
+        #[arg(long, default_value_t = 2)]
+        retries: u32,
+    },
+
+    /// Schedules the upgrade of the installed images.
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
     },
 }
 
 impl Display for CliCommand {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        self.to_operation().fmt(f)
+        let msg = match self {
+            Install { .. } => "install",
+            Uninstall { .. } => "uninstall",
+            Reinstall { .. } => "reinstall",
+            Config { .. } => "config",
+            Upgrade { .. } => "upgrade",
+            Provision { .. } => "provision",
+            Interactive => "interactive",
+            Apply { .. } => "apply",
+            List { .. } => "list",
+            Status { .. } => "status",
+            Info { .. } => "info",
+            Search { .. } => "search",
+            Doctor => "doctor",
+            History { .. } => "history",
+            Clean { .. } => "clean",
+            Verify { .. } => "verify",
+            Pin { .. } => "pin",
+            Export { .. } => "export",
+            SelfUpdate => "self-update",
+            Fetch { .. } => "fetch",
+            Schedule { .. } => "schedule",
+        };
+
+        write!(f, "{}", msg)
     }
 }
 
 impl CliCommand {
-    pub fn to_operation(&self) -> Operation {
+    pub fn to_operation(&self) -> Option<Operation> {
+        match self {
+            Install { .. } => Some(Operation::Install),
+            Uninstall { .. } => Some(Operation::Uninstall),
+            Reinstall { .. } => Some(Operation::Reinstall),
+            Config { edit: Some(_), .. } => None,
+            Config { .. } => Some(Operation::Config),
+            Upgrade { .. } => Some(Operation::Upgrade),
+            Provision { .. } => Some(Operation::Install),
+            Interactive => Some(Operation::Install),
+            Apply { .. } => None,
+            List { .. } => None,
+            Status { .. } => None,
+            Info { .. } => None,
+            Search { .. } => None,
+            Doctor => None,
+            History { .. } => None,
+            Clean { .. } => None,
+            Verify { .. } => None,
+            Pin { .. } => None,
+            Export { .. } => None,
+            SelfUpdate => None,
+            Fetch { .. } => None,
+            Schedule { .. } => None,
+        }
+    }
+
+    pub fn execute(&self, download: &DownloadOptions, lock: Option<&LockSettings>) -> Result<(), CliError> {
+        match (self, self.to_operation()) {
+            (Apply { manifest, dry_run, batch }, _) => Self::execute_apply(manifest, *dry_run, batch, download, lock),
+            (Install { target: Some(target), images, config, .. }, _) => {
+                check_image_ids(images)?;
+
+                container::install(target, images, *config).map_err(CliError::from)
+            }
+            (_, Some(operation)) => self.execute_batch(operation, download, lock),
+            (_, None) => self.execute_query(download, lock),
+        }
+    }
+
+    fn load_ctx(download: &DownloadOptions, lock: Option<&LockSettings>) -> Result<OperationContext, CliError> {
+        OperationContext::load(download.clone(), lock.cloned()).map_err(|error| CliError::new(ExitCode::UnsupportedOs, error))
+    }
+
+    fn execute_apply(path: &Path, dry_run: bool, batch: &BatchArgs, download: &DownloadOptions, lock: Option<&LockSettings>) -> Result<(), CliError> {
+        let ctx = Self::load_ctx(download, lock)?;
+        let manifest = Manifest::load(path)?;
+        let plan = Plan::from(&ctx, &manifest)?;
+
+        println!("Plan to apply {}:", path.display());
+        plan.print();
+
+        if plan.is_converged() {
+            info!("✅ Machine already matches the manifest.");
+            return Ok(());
+        }
+
+        if dry_run {
+            return Ok(());
+        }
+
+        plan.apply(&OperationExecution { ctx }, &manifest, batch.parallel)
+    }
+
+    fn execute_query(&self, download: &DownloadOptions, lock: Option<&LockSettings>) -> Result<(), CliError> {
+        let result = match self {
+            List { json } => ImageList::load().print(*json),
+
+            Status { images } => {
+                check_image_ids(images)?;
+
+                let ctx = Self::load_ctx(download, lock)?;
+
+                StatusReport::load(&ctx, images).print()
+            }
+
+            Info { image } => {
+                check_image_ids(slice::from_ref(image))?;
+
+                let ctx = Self::load_ctx(download, lock)?;
+
+                ImageDetails::load(&ctx, image).map(|details| details.print())
+            }
+
+            Search { query } => {
+                let ctx = Self::load_ctx(download, lock)?;
+
+                ImageSearch::load(&ctx, query).print();
+                Ok(())
+            }
+
+            Doctor => Diagnosis::run().print(),
+
+            History { image, limit, json } =>
+                OperationHistory::load(image, *limit)?.print(*json),
+
+            Clean { dry_run } => Cleanup::scan()?.run(*dry_run),
+
+            Verify { images } => {
+                check_image_ids(images)?;
+
+                let ctx = Self::load_ctx(download, lock)?;
+
+                VerifyReport::load(&ctx, images).print()
+            }
+
+            Pin { image, version, hash_sha256, .. } => {
+                check_image_ids(slice::from_ref(image))?;
+
+                match version {
+                    Some(version) => pin::pin(image, version, hash_sha256),
+                    None => pin::unpin(image),
+                }
+            }
+
+            Export { format, output } => {
+                let ctx = Self::load_ctx(download, lock)?;
+
+                export::export(&ctx, *format, output)
+            }
+
+            SelfUpdate => self_update(download),
+
+            Fetch { images, dest, parallel, retries } => {
+                check_image_ids(images)?;
+
+                let ctx = Self::load_ctx(download, lock)?;
+
+                fetch::fetch(&ctx, images, dest, *parallel, *retries)
+            }
+
+            Config { edit: Some(image), .. } => {
+                check_image_ids(slice::from_ref(image))?;
+
+                let ctx = Self::load_ctx(download, lock)?;
+
+                config_edit::edit(&ctx, image)
+            }
+
+            Schedule { action: ScheduleAction::Upgrade { daily, .. } } => {
+                let frequency = if *daily { Frequency::Daily } else { Frequency::Weekly };
+
+                schedule::schedule_upgrade(frequency)
+            }
+
+            Schedule { action: ScheduleAction::Remove } => schedule::remove_schedule(),
+
+            _ => Err(format!("Command {self} is not a query")),
+        };
+
+        result.map_err(CliError::from)
+    }
+
+    fn batch_args(&self) -> Option<&BatchArgs> {
         match self {
-            Install { .. } => Operation::Install,
-            Uninstall { .. } => Operation::Uninstall,
-            Reinstall { .. } => Operation::Reinstall,
-            Config { .. } => Operation::Config,
+            Install { batch, .. } => Some(batch),
+            Uninstall { batch, .. } => Some(batch),
+            Reinstall { batch, .. } => Some(batch),
+            Config { batch, .. } => Some(batch),
+            Upgrade { batch, .. } => Some(batch),
+            Provision { batch, .. } => Some(batch),
+            _ => None,
         }
     }
 
-    pub fn execute(&self) -> Result<(), String> {
-        let ctx = OperationContext::load()?;
+    fn execute_batch(&self, operation: Operation, download: &DownloadOptions, lock: Option<&LockSettings>) -> Result<(), CliError> {
+        let ctx = Self::load_ctx(download, lock)?;
         let exec = OperationExecution { ctx };
-        let batch = BatchOperation { operation: self.to_operation() };
+        let parallel = self.batch_args().map_or(1, |args| args.parallel);
+        let batch = BatchOperation { operation, parallel };
 
         match self {
-            Install { images, config } =>
-                batch.execute(images, |id_raw| exec.install(id_raw, config)),
+            Install { resume: true, .. } => {
+                let progress = ProgressStore::user()?.load()?;
 
-            Uninstall { images } =>
-                batch.execute(images, |id_raw| exec.uninstall(id_raw)),
+                info!("Resuming the install of {:?}...", progress.remaining());
 
-            Reinstall { images } =>
-                batch.execute(images, |id_raw| exec.reinstall(id_raw)),
+                resume::install(&batch, &exec, progress)
+            }
 
-            Config { images } =>
+            Install { from_file: Some(path), config, .. } => {
+                let manifest = Manifest::load(path)?;
+
+                manifest.warn_version_drift(&exec.ctx);
+
+                let progress = InstallProgress::new(&manifest.image_ids(), |id_raw| {
+                    manifest.config_of(id_raw, *config)
+                });
+
+                resume::install(&batch, &exec, progress)
+            }
+
+            Install { images, config, .. } =>
+                resume::install(&batch, &exec, InstallProgress::new(images, |_| *config)),
+
+            Uninstall { images, purge, .. } =>
+                batch.execute(images, |id_raw| exec.uninstall(id_raw, purge)),
+
+            Reinstall { images, .. } => {
+                exec.prefetch_installs(images);
+
+                batch.execute(images, |id_raw| exec.reinstall(id_raw))
+            }
+
+            Config { images, .. } =>
                 batch.execute(images, |id_raw| exec.config(id_raw)),
+
+            Upgrade { images, all, .. } => {
+                let images = if *all { all_image_ids() } else { images.clone() };
+
+                exec.prefetch_upgrades(&images);
+
+                batch.execute(&images, |id_raw| exec.upgrade(id_raw))
+            }
+
+            Provision { profile, file, .. } => {
+                let profiles = Profiles::load(file)?;
+                let manifest = profiles.profile(profile)?;
+
+                let progress = InstallProgress::new(&manifest.image_ids(), |id_raw| {
+                    manifest.config_of(id_raw, false)
+                });
+
+                resume::install(&batch, &exec, progress)
+            }
+
+            Interactive => {
+                let images = ImagePicker::load(&exec.ctx).pick()?;
+
+                if images.is_empty() {
+                    info!("No images selected.");
+                    return Ok(());
+                }
+
+                resume::install(&batch, &exec, InstallProgress::new(&images, |_| false))
+            }
+
+            _ => Err(format!("Command {self} is not a batch operation").into()),
         }
     }
 }
 
+fn all_image_ids() -> Vec<String> {
+    Repository::desktop_image_ids()
+        .into_iter()
+        .chain(Repository::server_image_ids())
+        .map(|id| id.to_string())
+        .collect()
+}
+
 #[derive(Parser)]
 #[command(name = "system")]
 pub struct SystemCli {
     #[command(subcommand)]
     pub operation: CliCommand,
+
+    /// Prints the output of the commands the images run, or all of it with `-vv`.
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Prints errors and command reports only.
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Reads the downloads from `--artifacts-dir` instead of the network.
+    #[arg(long, global = true, requires = "artifacts_dir")]
+    offline: bool,
+
+    /// Directory with the pre-fetched artifacts, named after their URL.
+    #[arg(long, value_name = "DIR", global = true, requires = "offline")]
+    artifacts_dir: Option<PathBuf>,
+
+    /// Proxy of the downloads, like `http://proxy.example.com:3128`.
+    #[arg(long, value_name = "URL", global = true)]
+    proxy: Option<String>,
+
+    /// Seconds to wait for a connection to a download host.
+    #[arg(long, value_name = "SECS", global = true, default_value_t = download::CONNECT_TIMEOUT_SECS, value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    connect_timeout: u64,
+
+    /// Seconds to wait for the next bytes of a download before failing it.
+    #[arg(long, value_name = "SECS", global = true, default_value_t = download::READ_TIMEOUT_SECS, value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    read_timeout: u64,
+
+    /// Maximum bytes per second of all the downloads, like `5M`.
+    #[arg(long, value_name = "RATE", global = true, value_parser = download::parse_rate)]
+    limit_rate: Option<u64>,
+
+    /// Lockfile to record the installed images in, like `system.lock`.
+    #[arg(long, value_name = "FILE", global = true)]
+    lockfile: Option<PathBuf>,
+
+    /// Fails to install images that differ from the lockfile.
+    #[arg(long, global = true)]
+    locked: bool,
+
+    /// Refuses images that download anything without an integrity check.
+    #[arg(long, global = true)]
+    require_integrity: bool,
+
+    /// Parallel connections to fetch large downloads in ranged chunks over.
+    #[arg(long, value_name = "N", global = true, default_value_t = 1, value_parser = RangedU64ValueParser::<usize>::new().range(1..=16))]
+    connections: usize,
+
+    /// Public key pin of a host, like `sh.rustup.rs=sha256//<base64>`.
+    #[arg(long, value_name = "HOST=PIN", global = true, value_parser = tls::parse_host_pin)]
+    pin_pubkey: Vec<(String, PublicKeyPin)>,
+}
+
+impl SystemCli {
+    pub fn verbosity(&self) -> Verbosity {
+        Verbosity::from_flags(self.verbose, self.quiet)
+    }
+
+    pub fn execute(&self) -> Result<(), CliError> {
+        let lock = self.lockfile_path()
+            .map(|path| LockSettings::new(path, self.locked))
+            .transpose()?;
+
+        let download = self.download_options(lock.as_ref())?;
+
+        download.export_proxy_env();
+        download::set_require_integrity(self.require_integrity);
+
+        self.operation.execute(&download, lock.as_ref())
+    }
+
+    fn download_options(&self, lock: Option<&LockSettings>) -> Result<DownloadOptions, String> {
+        let timeouts = Timeouts::new(
+            Duration::from_secs(self.connect_timeout),
+            Duration::from_secs(self.read_timeout),
+        );
+
+        let mut options = DownloadOptions::default()
+            .with_timeouts(timeouts)
+            .with_connections(self.connections);
+
+        if let Some(artifacts_dir) = self.offline_artifacts_dir()? {
+            options = options.offline(artifacts_dir.clone());
+        }
+
+        if let Some(proxy) = &self.proxy {
+            options = options.with_proxy(download::parse_proxy(proxy)?);
+        }
+
+        if let Some(bytes_per_sec) = self.limit_rate {
+            options = options.with_rate_limit(bytes_per_sec);
+        }
+
+        for (host, pin) in &self.pin_pubkey {
+            options = options.with_pins(host, vec![pin.clone()]);
+        }
+
+        match lock {
+            Some(lock) => lock.download_options(options),
+            None => Ok(options),
+        }
+    }
+
+    fn lockfile_path(&self) -> Option<PathBuf> {
+        match &self.lockfile {
+            Some(path) => Some(path.clone()),
+            None if self.locked => Some(PathBuf::from(DEFAULT_LOCKFILE_PATH)),
+            None => None,
+        }
+    }
+
+    fn offline_artifacts_dir(&self) -> Result<Option<&PathBuf>, String> {
+        match &self.artifacts_dir {
+            Some(dir) if !dir.is_dir() => Err(format!("Artifacts directory {} not found", dir.display())),
+            Some(dir) if self.offline => Ok(Some(dir)),
+            _ => Ok(None),
+        }
+    }
 }
@@ -0,0 +1,73 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+use dialoguer::Confirm;
+
+use crate::image::repository::Repository;
+use crate::main::exec::OperationContext;
+
+/// From `$VISUAL` or `$EDITOR`, which may have arguments, like `code --wait`.
+fn editor() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+fn open_editor(editor: &str, path: &Path) -> Result<(), String> {
+    // The shell splits the editor arguments, while the path goes as a
+    // positional parameter to keep it verbatim
+    let status = Command::new("sh")
+        .args(["-c", &format!("{editor} \"$1\""), "sh"])
+        .arg(path)
+        .status()
+        .map_err(|error| format!("Fail to open editor {editor}.\nCause: {error}"))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Editor {editor} exited with {status}"))
+    }
+}
+
+/// Validates the config after saving until it is valid or the user gives up.
+pub fn edit(ctx: &OperationContext, id_raw: &str) -> Result<(), String> {
+    let id = Repository::image_loader_from(id_raw)?.to_image_id();
+    let path = Repository::config_path(&id);
+
+    if !path.exists() {
+        return Err(format!("Image {id} has no config file at {}", path.display()));
+    }
+
+    let editor = editor();
+
+    loop {
+        open_editor(&editor, &path)?;
+
+        let error = match ctx.load_config(id_raw) {
+            Ok(_) => {
+                info!("✅ Config {} is valid.", path.display());
+                return Ok(());
+            }
+            Err(error) => error,
+        };
+
+        eprintln!("❌ Config {} is invalid.\nCause: {error}", path.display());
+
+        let edit_again = Confirm::new()
+            .with_prompt("Edit it again?")
+            .default(true)
+            .interact()
+            .map_err(|error| error.to_string())?;
+
+        if !edit_again {
+            return Err(format!("Config {} is invalid: {error}", path.display()));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::env;
+use std::process::Command;
+use std::str::FromStr;
+
+use crate::cmd::exec_cmd;
+use crate::output;
+use crate::output::Verbosity;
+
+const CONTAINER_WORKDIR: &str = "/opt/mathswe-ops";
+
+const CONTAINER_EXE: &str = "/usr/local/bin/system";
+
+/// Slim base images may lack `sudo` or the tools the installers use.
+const BOOTSTRAP_SCRIPT: &str = "command -v sudo && command -v curl || \
+    (apt-get update && apt-get install --yes sudo curl ca-certificates gnupg)";
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Target {
+    Docker(String),
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("docker", image)) if !image.is_empty() && !image.starts_with('-') => Ok(Target::Docker(image.to_string())),
+            _ => Err(format!("Invalid target {s}, expected docker:<image>, like docker:ubuntu:24.04")),
+        }
+    }
+}
+
+/// Like `ubuntu:24.04-provisioned` for `ubuntu:24.04`.
+fn provisioned_tag(base_image: &str) -> String {
+    let name_start = base_image.rfind('/').map_or(0, |slash| slash + 1);
+
+    match base_image[name_start..].split_once(':') {
+        Some(_) => format!("{base_image}-provisioned"),
+        None => format!("{base_image}:provisioned"),
+    }
+}
+
+fn install_args(images: &[String], config: bool, verbosity: Verbosity) -> Vec<String> {
+    let verbosity_flag = match verbosity {
+        Verbosity::Quiet => Some("--quiet"),
+        Verbosity::Normal => None,
+        Verbosity::Verbose => Some("-v"),
+        Verbosity::Debug => Some("-vv"),
+    };
+
+    verbosity_flag
+        .into_iter()
+        .chain(["install"])
+        .chain(config.then_some("--config"))
+        .map(String::from)
+        .chain(images.iter().cloned())
+        .collect()
+}
+
+fn docker(args: &[&str]) -> Result<String, String> {
+    exec_cmd("docker", args)
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .map_err(|error| error.to_string())
+}
+
+fn docker_exec(container: &str, cmd: &[&str]) -> Result<(), String> {
+    let status = Command::new("docker")
+        .args(["exec", "--workdir", CONTAINER_WORKDIR, container])
+        .args(cmd)
+        .status()
+        .map_err(|error| format!("Fail to run docker exec.\nCause: {error}"))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Command {} in container {container} exited with {status}", cmd.join(" ")))
+    }
+}
+
+/// Runs the install flow in a container of the base image, then commits it.
+pub fn install(target: &Target, images: &[String], config: bool) -> Result<(), String> {
+    let Target::Docker(base_image) = target;
+
+    exec_cmd("bash", &["-c", "command -v docker"])
+        .map_err(|_| "Docker is required to install into a container but is not installed".to_string())?;
+
+    info!("Starting container of {base_image}...");
+
+    let container = docker(&["run", "--detach", "--entrypoint", "sleep", base_image, "infinity"])?;
+
+    let result = provision(&container, images, config)
+        .and_then(|_| {
+            let tag = provisioned_tag(base_image);
+
+            info!("Committing provisioned image {tag}...");
+
+            docker(&["commit", &container, &tag]).map(|_| tag)
+        });
+
+    info!("Removing container...");
+
+    if let Err(error) = docker(&["rm", "--force", &container]) {
+        info!("⚠️ Fail to remove container {container}.\nCause: {error}");
+    }
+
+    result.map(|tag| info!("✅ Provisioned image {tag} built."))
+}
+
+fn provision(container: &str, images: &[String], config: bool) -> Result<(), String> {
+    let exe = env::current_exe()
+        .map_err(|error| error.to_string())?;
+
+    info!("Copying system executable and image files...");
+
+    docker(&["exec", container, "mkdir", "-p", CONTAINER_WORKDIR])?;
+    docker(&["cp", &exe.to_string_lossy(), &format!("{container}:{CONTAINER_EXE}")])?;
+    docker(&["cp", "image", &format!("{container}:{CONTAINER_WORKDIR}/image")])?;
+
+    info!("Bootstrapping container...");
+
+    docker_exec(container, &["bash", "-c", BOOTSTRAP_SCRIPT])?;
+
+    let args = install_args(images, config, output::verbosity());
+    let args = args.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    info!("Installing {} in container...", images.join(", "));
+
+    docker_exec(container, &[&[CONTAINER_EXE], args.as_slice()].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::main::container::{install_args, provisioned_tag, Target};
+    use crate::output::Verbosity;
+
+    #[test]
+    fn parses_docker_target() {
+        assert_eq!(Ok(Target::Docker("ubuntu:24.04".to_string())), "docker:ubuntu:24.04".parse());
+        assert!("docker:".parse::<Target>().is_err());
+        assert!("podman:ubuntu".parse::<Target>().is_err());
+        assert!("ubuntu".parse::<Target>().is_err());
+    }
+
+    #[test]
+    fn tags_provisioned_image() {
+        assert_eq!("ubuntu:24.04-provisioned", provisioned_tag("ubuntu:24.04"));
+        assert_eq!("ubuntu:provisioned", provisioned_tag("ubuntu"));
+        assert_eq!("localhost:5000/dev:provisioned", provisioned_tag("localhost:5000/dev"));
+    }
+
+    #[test]
+    fn forwards_install_args() {
+        let images = vec!["rust".to_string(), "go".to_string()];
+
+        assert_eq!(vec!["install", "rust", "go"], install_args(&images, false, Verbosity::Normal));
+        assert_eq!(vec!["-v", "install", "--config", "rust", "go"], install_args(&images, true, Verbosity::Verbose));
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::blocking;
+
+use crate::cmd::exec_cmd;
+use crate::main::doctor::Finding::{Fail, Pass, Warn};
+use crate::os;
+
+const KEY_HOSTS: [&str; 5] = [
+    "https://github.com",
+    "https://download.jetbrains.com",
+    "https://dl.google.com",
+    "https://packages.microsoft.com",
+    "https://archive.ubuntu.com",
+];
+
+const REQUIRED_TOOLS: [&str; 4] = ["curl", "gpg", "tar", "apt-get"];
+
+pub enum Finding {
+    Pass(String),
+    Warn(String, String),
+    Fail(String, String),
+}
+
+pub struct Diagnosis(Vec<Finding>);
+
+impl Diagnosis {
+    pub fn run() -> Self {
+        let mut findings = vec![check_os(), check_sudo()];
+
+        findings.extend(check_wsl());
+
+        findings.extend(REQUIRED_TOOLS.iter().map(|tool| check_tool(tool)));
+        findings.extend(KEY_HOSTS.iter().map(|host| check_host(host)));
+        findings.extend(check_home());
+
+        Diagnosis(findings)
+    }
+
+    pub fn print(&self) -> Result<(), String> {
+        for finding in &self.0 {
+            match finding {
+                Pass(msg) => println!("✅ {msg}"),
+                Warn(msg, hint) => println!("⚠️ {msg}\n   Hint: {hint}"),
+                Fail(msg, hint) => println!("❌ {msg}\n   Hint: {hint}"),
+            }
+        }
+
+        let fail_num = self.0
+            .iter()
+            .filter(|finding| matches!(finding, Fail(..)))
+            .count();
+
+        if fail_num == 0 {
+            Ok(())
+        } else {
+            Err(format!("Doctor found {fail_num} problems to fix before operating images"))
+        }
+    }
+}
+
+fn check_os() -> Finding {
+    match os::detect_os() {
+        Ok(Some(os)) => Pass(format!("Detected OS {:?}", os)),
+        Ok(None) => Fail(
+            "OS unsupported".to_string(),
+            "Run the app on Ubuntu, Debian, Fedora, or Arch x64, macOS, or Windows x64, the OSes the images support".to_string(),
+        ),
+        Err(error) => Fail(
+            format!("Fail to detect the OS: {error}"),
+            "Make sure /etc/os-release exists and is readable".to_string(),
+        ),
+    }
+}
+
+/// WSL only runs systemd, which services need, if `/etc/wsl.conf` enables it.
+fn check_wsl() -> Option<Finding> {
+    let is_wsl = os::detect_os().is_ok_and(|os| os.is_some_and(|os| os.is_wsl()));
+
+    if !is_wsl {
+        return None;
+    }
+
+    let finding = if os::has_systemd() {
+        Pass("Running on WSL with systemd".to_string())
+    } else {
+        Warn(
+            "Running on WSL without systemd, so service images won't start".to_string(),
+            "Enable it with `systemd=true` under `[boot]` in /etc/wsl.conf and restart WSL with `wsl --shutdown`".to_string(),
+        )
+    };
+
+    Some(finding)
+}
+
+fn check_sudo() -> Finding {
+    if !is_on_path("sudo") {
+        return Fail(
+            "sudo is not available".to_string(),
+            "Install sudo and add your user to the sudo group".to_string(),
+        );
+    }
+
+    match exec_cmd("sudo", &["--non-interactive", "true"]) {
+        Ok(_) => Pass("sudo is available".to_string()),
+        Err(_) => Warn(
+            "sudo requires a password".to_string(),
+            "Expect the terminal to ask for your password when an image needs it".to_string(),
+        ),
+    }
+}
+
+fn check_tool(tool: &str) -> Finding {
+    if is_on_path(tool) {
+        Pass(format!("{tool} is installed"))
+    } else {
+        Fail(
+            format!("{tool} is not installed"),
+            format!("Install it with `sudo apt-get install {}`", apt_package_of(tool)),
+        )
+    }
+}
+
+fn check_host(host: &str) -> Finding {
+    let response = blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .and_then(|client| client.head(host).send());
+
+    match response {
+        Ok(_) => Pass(format!("{host} is reachable")),
+        Err(error) => Fail(
+            format!("{host} is unreachable: {error}"),
+            "Check your network connection, DNS, and proxy settings".to_string(),
+        ),
+    }
+}
+
+fn check_home() -> Vec<Finding> {
+    let home = match dirs::home_dir() {
+        Some(home) if home.is_dir() => home,
+        _ => return vec![Fail(
+            "HOME directory not found".to_string(),
+            "Set the HOME environment variable to your user directory".to_string(),
+        )],
+    };
+
+    let profile_files = [".profile", ".bashrc"]
+        .iter()
+        .map(|file| home.join(file))
+        .filter(|path| path.exists());
+
+    [home.clone()]
+        .into_iter()
+        .chain(profile_files)
+        .map(|path| check_writable(&path))
+        .collect()
+}
+
+fn check_writable(path: &Path) -> Finding {
+    let readonly = fs::metadata(path)
+        .map(|metadata| metadata.permissions().readonly());
+
+    match readonly {
+        Ok(false) => Pass(format!("{} is writable", path.display())),
+        Ok(true) => Fail(
+            format!("{} is read-only", path.display()),
+            format!("Make it writable with `chmod u+w {}`", path.display()),
+        ),
+        Err(error) => Fail(
+            format!("Fail to read {}: {error}", path.display()),
+            "Check the file ownership of your home directory".to_string(),
+        ),
+    }
+}
+
+fn is_on_path(binary: &str) -> bool {
+    exec_cmd("bash", &["-c", &format!("command -v {binary}")]).is_ok()
+}
+
+fn apt_package_of(tool: &str) -> &str {
+    match tool {
+        "gpg" => "gnupg",
+        "apt-get" => "apt",
+        _ => tool,
+    }
+}
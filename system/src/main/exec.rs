@@ -2,27 +2,40 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
+use crate::download::DownloadOptions;
 use crate::image::repository::Repository;
 use crate::image::{Config, ImageId, ImageOps};
+use crate::main::fetch;
 use crate::main::image_exec::{ConfigExecution, ImageOpsExecution};
+use crate::main::lock::LockSettings;
 use crate::os;
 use crate::os::Os;
 
 #[derive(Clone)]
 pub struct OperationContext {
     os: Os,
+    download: DownloadOptions,
+    lock: Option<LockSettings>,
 }
 
 impl OperationContext {
-    pub fn new(os: Os) -> Self {
-        OperationContext { os }
+    pub fn new(os: Os, download: DownloadOptions, lock: Option<LockSettings>) -> Self {
+        OperationContext { os, download, lock }
     }
 
-    pub fn load() -> Result<Self, String> {
+    pub fn load(download: DownloadOptions, lock: Option<LockSettings>) -> Result<Self, String> {
         os::detect_os()
             .map_err(|io_error| io_error.to_string())?
             .ok_or_else(|| "OS unsupported".to_string())
-            .map(OperationContext::new)
+            .map(|os| OperationContext::new(os, download, lock))
+    }
+
+    pub fn download_options(&self) -> &DownloadOptions {
+        &self.download
+    }
+
+    pub fn lock_settings(&self) -> Option<&LockSettings> {
+        self.lock.as_ref()
     }
 
     fn load_image_ops(
@@ -31,23 +44,27 @@ impl OperationContext {
     ) -> Result<Box<dyn ImageOps>, String> {
         self.load_image(id_raw)
             .map_err(|error| {
-                println!("{}", format!("❌ Fail to load image {}.\nCause: {}", id_raw, error));
+                eprintln!("❌ Fail to load image {}.\nCause: {}", id_raw, error);
                 id_raw.to_string()
             })
     }
 
-    fn load_image(
+    pub fn load_image(
         &self,
         id_raw: &str,
     ) -> Result<Box<dyn ImageOps>, String> {
-        Repository::image_loader_from(id_raw)
+        let mut image = Repository::image_loader_from(id_raw)
             .and_then(|loader| loader
                 .load_image(self.os.clone())
                 .map_err(|error| error.to_string())
-            )
+            )?;
+
+        image.image_mut().package_mut().set_download_options(self.download.clone());
+
+        Ok(image)
     }
 
-    fn load_config(
+    pub fn load_config(
         &self,
         id_raw: &str,
     ) -> Result<Box<dyn Config>, String> {
@@ -81,7 +98,7 @@ impl OperationExecution {
         let image_id = self
             .ctx
             .load_image_ops(id_raw)
-            .map(ImageOpsExecution::new)?
+            .map(|ops| ImageOpsExecution::new(ops, &self.ctx))?
             .install()?;
 
         if *config {
@@ -94,11 +111,12 @@ impl OperationExecution {
     pub fn uninstall(
         &self,
         id_raw: &String,
+        purge: &bool,
     ) -> Result<ImageId, String> {
         self.ctx
             .load_image_ops(id_raw)
-            .map(ImageOpsExecution::new)?
-            .uninstall()
+            .map(|ops| ImageOpsExecution::new(ops, &self.ctx))?
+            .uninstall(*purge)
     }
 
     pub fn reinstall(
@@ -107,7 +125,33 @@ impl OperationExecution {
     ) -> Result<ImageId, String> {
         self.ctx
             .load_image_ops(id_raw)
-            .map(ImageOpsExecution::new)?
+            .map(|ops| ImageOpsExecution::new(ops, &self.ctx))?
             .reinstall()
     }
+
+    pub fn upgrade(
+        &self,
+        id_raw: &str,
+    ) -> Result<ImageId, String> {
+        self.ctx
+            .load_image_ops(id_raw)
+            .map(|ops| ImageOpsExecution::new(ops, &self.ctx))?
+            .upgrade()
+    }
+
+    pub fn prefetch_installs(&self, images: &[String]) {
+        fetch::prefetch(&self.ctx, images, |_| true)
+    }
+
+    pub fn prefetch_upgrades(&self, images: &[String]) {
+        fetch::prefetch(&self.ctx, images, is_outdated)
+    }
+}
+
+fn is_outdated(ops: &dyn ImageOps) -> bool {
+    let version = ops.image().package().software.version;
+
+    ops.detection()
+        .detect()
+        .is_ok_and(|status| status.has_version(&version) == Some(false))
 }
@@ -0,0 +1,84 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fmt::{Display, Formatter};
+use std::process;
+
+use crate::image::repository::Repository;
+
+/// Process exit status, so automation can branch on the class of failure.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ExitCode {
+    Success = 0,
+
+    PartialFailure = 1,
+
+    AllFailed = 2,
+
+    UnsupportedOs = 3,
+
+    /// Nothing was executed.
+    InvalidImageId = 4,
+}
+
+impl From<ExitCode> for process::ExitCode {
+    fn from(code: ExitCode) -> Self {
+        process::ExitCode::from(code as u8)
+    }
+}
+
+#[derive(Debug)]
+pub struct CliError {
+    pub code: ExitCode,
+    pub msg: String,
+}
+
+impl CliError {
+    pub fn new(code: ExitCode, msg: String) -> Self {
+        CliError { code, msg }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl From<String> for CliError {
+    fn from(msg: String) -> Self {
+        CliError::new(ExitCode::PartialFailure, msg)
+    }
+}
+
+pub fn check_image_ids(images: &[String]) -> Result<(), CliError> {
+    let unknown = images
+        .iter()
+        .filter(|id_raw| Repository::image_loader_from(id_raw).is_err())
+        .cloned()
+        .collect::<Vec<String>>();
+
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::new(
+            ExitCode::InvalidImageId,
+            format!("Image IDs {:?} not found in the image repository", unknown),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::main::exit_code::{check_image_ids, ExitCode};
+
+    #[test]
+    fn rejects_unknown_image_ids() {
+        let known = vec!["rust".to_string(), "vscode".to_string()];
+        let unknown = vec!["rust".to_string(), "rustt".to_string()];
+
+        assert!(check_image_ids(&known).is_ok());
+        assert_eq!(ExitCode::InvalidImageId, check_image_ids(&unknown).unwrap_err().code);
+    }
+}
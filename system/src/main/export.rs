@@ -0,0 +1,57 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::image::detection::InstallStatus::NotInstalled;
+use crate::image::repository::Repository;
+use crate::main::exec::OperationContext;
+use crate::main::manifest::{Manifest, ManifestEntry, ManifestFormat};
+
+/// With the version of the images that match the repository one.
+pub fn installed_manifest(ctx: &OperationContext) -> Manifest {
+    let to_entry = |id: String| {
+        let image = ctx.load_image(&id).ok()?;
+        let status = image.detection().detect().ok()?;
+
+        if status == NotInstalled {
+            return None;
+        }
+
+        let repository_version = image.image().package().software.version;
+        let version = status
+            .has_version(&repository_version)
+            .filter(|matches| *matches)
+            .map(|_| repository_version);
+
+        Some(ManifestEntry::Image { id, config: None, version })
+    };
+
+    let entries = Repository::desktop_image_ids()
+        .into_iter()
+        .chain(Repository::server_image_ids())
+        .filter_map(|id| to_entry(id.to_string()))
+        .collect();
+
+    Manifest::new(entries)
+}
+
+pub fn export(
+    ctx: &OperationContext,
+    format: ManifestFormat,
+    output: &Option<PathBuf>,
+) -> Result<(), String> {
+    let manifest = installed_manifest(ctx).serialize(format)?;
+
+    match output {
+        Some(path) => {
+            fs::write(path, manifest).map_err(|error| error.to_string())?;
+            println!("Exported installed images to {}.", path.display());
+        }
+        None => println!("{manifest}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::download::{cache, download_all, DownloadRequest, Downloader, Retry};
+use crate::image::ImageOps;
+use crate::main::exec::OperationContext;
+use crate::tmp::TmpWorkingDir;
+
+const BATCH_DOWNLOADS: usize = 4;
+
+/// `None` if a previous fetch already got it intact.
+fn artifact_downloader(req: DownloadRequest, dest: &Path) -> Result<Option<Downloader>, String> {
+    let filename = req
+        .filename()
+        .ok_or_else(|| format!("URL {} has no filename", req.url()))?;
+
+    let path = dest.join(&filename);
+
+    if path.exists() {
+        if req.check(&path).unwrap_or(false) {
+            info!("Keeping {filename}, already fetched.");
+            return Ok(None);
+        }
+
+        fs::remove_file(&path).map_err(|error| error.to_string())?;
+    }
+
+    Ok(Some(Downloader::new(req, path)))
+}
+
+/// Saves the artifacts named as `--offline` installs read them.
+pub fn fetch(
+    ctx: &OperationContext,
+    images: &[String],
+    dest: &Path,
+    parallel: usize,
+    retries: u32,
+) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|error| error.to_string())?;
+
+    let retry = Retry::new(retries.saturating_add(1), Retry::default().backoff());
+
+    let mut errors = BTreeMap::<String, String>::new();
+    let mut fetches = Vec::<(String, Downloader)>::new();
+
+    for id_raw in images {
+        let artifacts = ctx
+            .load_image(id_raw)
+            .and_then(|image| image.artifacts());
+
+        match artifacts {
+            Ok(artifacts) if artifacts.is_empty() =>
+                info!("Image {id_raw} installs without artifacts to fetch."),
+
+            Ok(artifacts) => for req in artifacts {
+                match artifact_downloader(req.with_retry(retry.clone()), dest) {
+                    Ok(Some(downloader)) => fetches.push((id_raw.clone(), downloader)),
+                    Ok(None) => (),
+                    Err(error) => { errors.insert(id_raw.clone(), error); }
+                }
+            },
+
+            Err(error) => { errors.insert(id_raw.clone(), error); }
+        }
+    }
+
+    info!("Fetching {} artifacts...", fetches.len());
+
+    let (ids, downloaders): (Vec<String>, Vec<Downloader>) = fetches.into_iter().unzip();
+    let paths = downloaders
+        .iter()
+        .map(|downloader| downloader.path.clone())
+        .collect::<Vec<_>>();
+
+    let results = download_all(downloaders, parallel);
+
+    for ((id_raw, path), result) in ids.into_iter().zip(paths).zip(results) {
+        match result {
+            Ok(_) => info!("✅ Fetch {}.", path.display()),
+            Err(error) => {
+                // Don't leave a corrupted artifact for an offline install
+                let _ = fs::remove_file(&path);
+
+                errors.insert(id_raw, error.to_string());
+            }
+        }
+    }
+
+    for (id_raw, error) in &errors {
+        eprintln!("❌ Fail to fetch {id_raw}.\n Cause: {error}");
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Fail to fetch images {:?}", errors.keys().collect::<Vec<_>>()))
+    }
+}
+
+/// Fills the download cache ahead of the installs, which report any failures.
+pub fn prefetch(
+    ctx: &OperationContext,
+    images: &[String],
+    filter: impl Fn(&dyn ImageOps) -> bool,
+) {
+    if images.len() < 2 || ctx.download_options().artifacts_dir().is_some() {
+        return;
+    }
+
+    let Ok(tmp) = TmpWorkingDir::new() else { return };
+
+    let downloaders = images
+        .iter()
+        .filter_map(|id_raw| ctx.load_image(id_raw).ok())
+        .filter(|image| filter(image.as_ref()))
+        .filter_map(|image| image.artifacts().ok())
+        .flatten()
+        .filter(cache::keeps)
+        .filter_map(|req| req.filename().map(|filename| (req, filename)))
+        .enumerate()
+        // Images may download files under the same name
+        .map(|(index, (req, filename))| Downloader::new(req, tmp.join(format!("{index}-{filename}").as_ref())))
+        .collect::<Vec<Downloader>>();
+
+    if downloaders.is_empty() {
+        return;
+    }
+
+    info!("Fetching {} artifacts ahead...", downloaders.len());
+
+    for result in download_all(downloaders, BATCH_DOWNLOADS) {
+        if let Err(error) = result {
+            debug!("Fail to fetch an artifact ahead: {error}");
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use crate::main::journal::{format_timestamp, Journal, JournalEntry};
+
+pub struct OperationHistory(Vec<JournalEntry>);
+
+impl OperationHistory {
+    pub fn load(image: &Option<String>, limit: usize) -> Result<Self, String> {
+        let entries = Journal::user()?.entries()?;
+        let mut matches = entries
+            .into_iter()
+            .filter(|entry| match image {
+                Some(image) => entry.image == *image,
+                None => true,
+            })
+            .collect::<Vec<JournalEntry>>();
+
+        let skip = matches.len().saturating_sub(limit);
+
+        Ok(OperationHistory(matches.split_off(skip)))
+    }
+
+    pub fn print(&self, json: bool) -> Result<(), String> {
+        if json {
+            let entries_json = serde_json::to_string_pretty(&self.0)
+                .map_err(|error| error.to_string())?;
+
+            println!("{entries_json}");
+            return Ok(());
+        }
+
+        if self.0.is_empty() {
+            println!("No operations recorded.");
+        }
+
+        for entry in &self.0 {
+            let icon = if entry.success { "✅" } else { "❌" };
+            let version = entry.version.clone().unwrap_or_else(|| "-".to_string());
+
+            println!(
+                "{icon} {}  {} {} {version}",
+                format_timestamp(entry.timestamp),
+                entry.operation,
+                entry.image,
+            );
+
+            if let Some(error) = &entry.error {
+                println!("   Cause: {}", error.lines().next().unwrap_or_default());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -2,37 +2,71 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
+use crate::download;
+use crate::download::Integrity;
+use crate::image::detection::InstallStatus::NotInstalled;
+use crate::image::purge;
 use crate::image::{Config, ImageId, ImageOps};
+use crate::main::exec::OperationContext;
+use crate::main::journal::{Journal, JournalEntry};
+use crate::main::lock;
+use crate::main::system::Operation;
+use crate::main::verify::SnapshotStore;
 
 pub struct ImageOpsExecution {
     ops: Box<dyn ImageOps>,
+    ctx: OperationContext,
 }
 
 impl ImageOpsExecution {
-    pub fn new(ops: Box<dyn ImageOps>) -> Self {
-        ImageOpsExecution { ops }
+    pub fn new(ops: Box<dyn ImageOps>, ctx: &OperationContext) -> Self {
+        ImageOpsExecution { ops, ctx: ctx.clone() }
     }
 
     pub fn install(&self) -> Result<ImageId, String> {
         let image = self.ops.image();
         let id = image.id();
 
-        println!("Installing {}...", image);
+        self.check_integrity_policy()
+            .and_then(|_| self.check_expected_hash())
+            .and_then(|_| self.check_lock())
+            .map_err(|error| err(id.clone(), format!("❌ Refuse to install {}.\n Cause: {}", id, error)))?;
 
-        self.ops
-            .install()
+        info!("Installing {}...", image);
+
+        let result = self.ops.install();
+
+        record(Operation::Install, &id, Some(image.package().software.version), &result);
+
+        if result.is_ok() {
+            SnapshotStore::record(&id, &self.ops.detection());
+            self.record_lock();
+        }
+
+        result
             .map(|_| ok(id.clone(), format!("✅ Install image {}.", id)))
             .map_err(|error| err(id.clone(), format!("❌ Fail to install {}.\n Cause: {}", id, error)))
     }
 
-    pub fn uninstall(&self) -> Result<ImageId, String> {
+    pub fn uninstall(&self, purge: bool) -> Result<ImageId, String> {
         let image = self.ops.image();
         let id = image.id();
 
-        println!("Uninstalling {}...", image);
+        info!("Uninstalling {}...", image);
 
-        self.ops
+        let result = self.ops
             .uninstall()
+            .and_then(|_| if purge { purge::purge(&self.ops.purge_paths()) } else { Ok(()) });
+
+        record(Operation::Uninstall, &id, Some(image.package().software.version), &result);
+
+        if result.is_ok() {
+            if let Err(error) = SnapshotStore::user().and_then(|store| store.remove(&id)) {
+                eprintln!("Fail to remove the installed files record of {id}: {error}");
+            }
+        }
+
+        result
             .map(|_| ok(id.clone(), format!("✅ Uninstall image {}.", id)))
             .map_err(|error| err(id.clone(), format!("❌ Fail to uninstall {}.\n Cause: {}", id, error)))
     }
@@ -41,13 +75,99 @@ impl ImageOpsExecution {
         let image = self.ops.image();
         let id = image.id();
 
-        println!("Reinstalling {}...", image);
+        self.check_integrity_policy()
+            .and_then(|_| self.check_expected_hash())
+            .and_then(|_| self.check_lock())
+            .map_err(|error| err(id.clone(), format!("❌ Refuse to reinstall {}.\n Cause: {}", id, error)))?;
+
+        info!("Reinstalling {}...", image);
+
+        let result = self.ops.reinstall();
 
-        self.ops
-            .reinstall()
+        record(Operation::Reinstall, &id, Some(image.package().software.version), &result);
+
+        if result.is_ok() {
+            SnapshotStore::record(&id, &self.ops.detection());
+            self.record_lock();
+        }
+
+        result
             .map(|_| ok(id.clone(), format!("✅ Reinstall image {}.", id)))
             .map_err(|error| err(id.clone(), format!("❌ Fail to reinstall {}.\n Cause: {}", id, error)))
     }
+
+    pub fn upgrade(&self) -> Result<ImageId, String> {
+        let image = self.ops.image();
+        let id = image.id();
+        let version = image.package().software.version;
+
+        let status = self.ops
+            .detection()
+            .detect()
+            .map_err(|error| err(id.clone(), format!("❌ Fail to detect {}.\n Cause: {}", id, error)))?;
+
+        if status == NotInstalled {
+            return Ok(ok(id.clone(), format!("➖ Skip image {} since it's not installed.", id)));
+        }
+
+        match status.has_version(&version) {
+            Some(true) => Ok(ok(id.clone(), format!("✅ Image {} is up to date ({}).", id, version))),
+            Some(false) => {
+                info!("Upgrading {} to {}...", id, version);
+                self.reinstall()
+            }
+            None => Ok(ok(
+                id.clone(),
+                format!("➖ Skip image {} since its installed version is unknown; reinstall it to get version {}.", id, version),
+            )),
+        }
+    }
+
+    fn check_lock(&self) -> Result<(), String> {
+        match self.ctx.lock_settings() {
+            Some(settings) => lock::check_image(self.ops.as_ref(), settings),
+            None => Ok(()),
+        }
+    }
+
+    fn record_lock(&self) {
+        let Some(settings) = self.ctx.lock_settings() else { return };
+
+        if let Err(error) = lock::record_image(self.ops.as_ref(), settings, self.ctx.download_options()) {
+            eprintln!("Fail to record {} in the lockfile: {error}", self.ops.image().id());
+        }
+    }
+
+    fn check_integrity_policy(&self) -> Result<(), String> {
+        let unverified = self.ops.unverified_downloads();
+
+        if !download::integrity_required() || unverified.is_empty() {
+            return Ok(());
+        }
+
+        let urls = unverified
+            .iter()
+            .map(|url| url.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+
+        Err(format!("It downloads {urls} without an integrity check, which --require-integrity refuses"))
+    }
+
+    /// An empty hash could never pass the check, so it fails before downloading.
+    fn check_expected_hash(&self) -> Result<(), String> {
+        let image = self.ops.image();
+        let fetch = image.package().fetch;
+
+        match fetch.integrity() {
+            Integrity::Hash(hash) if hash.value().trim().is_empty() => Err(format!(
+                "Its image info has no SHA-256 of {}, so pin the one the vendor publishes with `system pin {} <version> --hash-sha256 <hash>`",
+                fetch.url(),
+                image.id(),
+            )),
+            _ => Ok(()),
+        }
+    }
 }
 
 pub struct ConfigExecution {
@@ -62,10 +182,13 @@ impl ConfigExecution {
     pub fn config(&self) -> Result<ImageId, String> {
         let id = self.ops.image_id();
 
-        println!("Configuring {}...", id);
+        info!("Configuring {}...", id);
 
-        self.ops
-            .config()
+        let result = self.ops.config();
+
+        record(Operation::Config, &id, None, &result);
+
+        result
             .map(|_| ok(id.clone(), format!("✅ Config image {}.", id)))
             .map_err(|error| err(
                 id.clone(),
@@ -74,8 +197,17 @@ impl ConfigExecution {
     }
 }
 
+fn record(
+    operation: Operation,
+    id: &ImageId,
+    version: Option<String>,
+    result: &Result<(), String>,
+) {
+    Journal::record(JournalEntry::new(&operation, id, version, result));
+}
+
 fn ok(id: ImageId, msg: String) -> ImageId {
-    println!("{}", msg);
+    info!("{}", msg);
 
     id
 }
@@ -0,0 +1,44 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::path::PathBuf;
+
+use crate::image::repository::Repository;
+use crate::image::ImageId;
+use crate::main::exec::OperationContext;
+use crate::package::Package;
+
+pub struct ImageDetails {
+    id: ImageId,
+    package: Package,
+    info_path: Option<PathBuf>,
+}
+
+impl ImageDetails {
+    pub fn load(ctx: &OperationContext, id_raw: &str) -> Result<Self, String> {
+        let image = ctx.load_image(id_raw)?.image();
+        let id = image.id();
+        let info_path = Some(Repository::info_path(&id))
+            .filter(|path| path.is_file());
+
+        Ok(ImageDetails { id, package: image.package(), info_path })
+    }
+
+    pub fn print(&self) {
+        let ImageDetails { id, package, info_path } = self;
+        let info_path = info_path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "none (basic image)".to_string());
+
+        println!("Image: {id}");
+        println!("Software: {}", package.software.name);
+        println!("Provider: {}", package.software.provider);
+        println!("Version: {}", package.software.version);
+        println!("Documentation: {}", package.doc);
+        println!("Fetch URL: {}", package.fetch.url());
+        println!("Integrity: {}", package.fetch.integrity());
+        println!("Info file: {info_path}");
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, MultiSelect};
+
+use crate::image::detection::InstallStatus;
+use crate::image::repository::Repository;
+use crate::main::exec::OperationContext;
+
+pub struct PickerItem {
+    id: String,
+    status: Option<InstallStatus>,
+}
+
+impl PickerItem {
+    fn label(&self) -> String {
+        match &self.status {
+            Some(status) => format!("{}  [{status}]", self.id),
+            None => format!("{}  [unknown]", self.id),
+        }
+    }
+}
+
+pub struct ImagePicker(Vec<PickerItem>);
+
+impl ImagePicker {
+    pub fn load(ctx: &OperationContext) -> Self {
+        let to_item = |id: String| {
+            let status = ctx
+                .load_image(&id)
+                .and_then(|image| image.detection().detect())
+                .ok();
+
+            PickerItem { id, status }
+        };
+
+        let items = Repository::desktop_image_ids()
+            .into_iter()
+            .chain(Repository::server_image_ids())
+            .map(|id| to_item(id.to_string()))
+            .collect();
+
+        ImagePicker(items)
+    }
+
+    /// An empty list if the user cancels.
+    pub fn pick(&self) -> Result<Vec<String>, String> {
+        let theme = ColorfulTheme::default();
+        let labels = self.0
+            .iter()
+            .map(PickerItem::label)
+            .collect::<Vec<String>>();
+
+        let selection = MultiSelect::with_theme(&theme)
+            .with_prompt("Select the images to install (space to toggle, enter to confirm)")
+            .items(&labels)
+            .interact_opt()
+            .map_err(|error| error.to_string())?
+            .unwrap_or_default();
+
+        let ids = selection
+            .into_iter()
+            .map(|index| self.0[index].id.clone())
+            .collect::<Vec<String>>();
+
+        if ids.is_empty() {
+            return Ok(ids);
+        }
+
+        let confirmed = Confirm::with_theme(&theme)
+            .with_prompt(format!("Install {}?", ids.join(", ")))
+            .default(true)
+            .interact()
+            .map_err(|error| error.to_string())?;
+
+        Ok(if confirmed { ids } else { Vec::new() })
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::image::ImageId;
+use crate::main::system::Operation;
+use crate::tmp;
+
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: u64,
+    pub operation: String,
+    pub image: String,
+    pub version: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn new(
+        operation: &Operation,
+        id: &ImageId,
+        version: Option<String>,
+        result: &Result<(), String>,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        JournalEntry {
+            timestamp,
+            operation: operation.to_string(),
+            image: id.to_string(),
+            version,
+            success: result.is_ok(),
+            error: result.clone().err(),
+        }
+    }
+}
+
+/// JSON-lines log at `~/.local/share/mathswe-ops/journal.jsonl`.
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    pub fn user() -> Result<Self, String> {
+        tmp::data_dir()
+            .map(|dir| Journal { path: dir.join("journal.jsonl") })
+            .ok_or_else(|| "Fail to find the user data directory".to_string())
+    }
+
+    /// Only warns if the journal is unavailable since the operation already ran.
+    pub fn record(entry: JournalEntry) {
+        if let Err(error) = Self::user().and_then(|journal| journal.append(&entry)) {
+            eprintln!("Fail to record {} {} in the journal: {}", entry.operation, entry.image, error);
+        }
+    }
+
+    pub fn append(&self, entry: &JournalEntry) -> Result<(), String> {
+        let _guard = JOURNAL_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+
+        let line = serde_json::to_string(entry).map_err(|error| error.to_string())?;
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{line}"))
+            .map_err(|error| error.to_string())
+    }
+
+    pub fn entries(&self) -> Result<Vec<JournalEntry>, String> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let journal = fs::read_to_string(&self.path).map_err(|error| error.to_string())?;
+
+        let entries = journal
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        Ok(entries)
+    }
+}
+
+/// Like `2024-07-20 13:45:10 UTC`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let secs = timestamp % 86_400;
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    )
+}
+
+/// Proleptic Gregorian date of the days since the Unix epoch.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use crate::main::journal::{format_timestamp, Journal, JournalEntry};
+
+    #[test]
+    fn formats_utc_timestamps() {
+        assert_eq!("1970-01-01 00:00:00 UTC", format_timestamp(0));
+        assert_eq!("2024-02-29 13:45:10 UTC", format_timestamp(1_709_214_310));
+    }
+
+    #[test]
+    fn appends_and_reads_entries() {
+        let dir = tempdir().unwrap();
+        let journal = Journal { path: dir.path().join("journal.jsonl") };
+        let entry = JournalEntry {
+            timestamp: 1_709_214_310,
+            operation: "install".to_string(),
+            image: "go".to_string(),
+            version: Some("1.22.5".to_string()),
+            success: false,
+            error: Some("Fail to download".to_string()),
+        };
+
+        assert_eq!(Vec::<JournalEntry>::new(), journal.entries().unwrap());
+
+        journal.append(&entry).unwrap();
+        journal.append(&entry).unwrap();
+
+        assert_eq!(vec![entry.clone(), entry], journal.entries().unwrap());
+    }
+}
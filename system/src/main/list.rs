@@ -0,0 +1,51 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use serde::Serialize;
+
+use crate::image::repository::Repository;
+use crate::image::ImageId;
+
+#[derive(Serialize)]
+pub struct ImageList {
+    desktop: Vec<String>,
+    server: Vec<String>,
+}
+
+impl ImageList {
+    pub fn load() -> Self {
+        let to_strings = |ids: Vec<ImageId>| ids
+            .iter()
+            .map(ImageId::to_string)
+            .collect();
+
+        ImageList {
+            desktop: to_strings(Repository::desktop_image_ids()),
+            server: to_strings(Repository::server_image_ids()),
+        }
+    }
+
+    pub fn print(&self, json: bool) -> Result<(), String> {
+        if json {
+            let list_json = serde_json::to_string_pretty(self)
+                .map_err(|error| error.to_string())?;
+
+            println!("{list_json}");
+        } else {
+            print_category("Desktop Images", &self.desktop);
+            println!();
+            print_category("Server Images", &self.server);
+        }
+
+        Ok(())
+    }
+}
+
+fn print_category(title: &str, ids: &[String]) {
+    println!("{title} ({})", ids.len());
+
+    for id in ids {
+        println!("  {id}");
+    }
+}
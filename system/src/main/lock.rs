@@ -0,0 +1,210 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::download::DownloadOptions;
+use crate::image::ImageOps;
+
+pub const DEFAULT_LOCKFILE_PATH: &str = "system.lock";
+
+/// Installing with `--locked` reproduces the machine from it or fails.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Lockfile {
+    images: BTreeMap<String, LockedImage>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockedImage {
+    pub version: String,
+    pub artifacts: Vec<LockedArtifact>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockedArtifact {
+    pub url: String,
+    pub sha256: String,
+}
+
+impl Lockfile {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Lockfile::default());
+        }
+
+        let lockfile = fs::read_to_string(path)
+            .map_err(|error| format!("Fail to read lockfile at {:?}.\nCause: {}", path, error))?;
+
+        serde_json::from_str(&lockfile)
+            .map_err(|error| format!("Fail to parse lockfile at {:?}.\nCause: {}", path, error))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let lockfile = serde_json::to_string_pretty(self)
+            .map_err(|error| error.to_string())?;
+
+        fs::write(path, lockfile + "\n")
+            .map_err(|error| format!("Fail to write lockfile at {:?}.\nCause: {}", path, error))
+    }
+
+    pub fn set(&mut self, id_raw: &str, image: LockedImage) {
+        self.images.insert(id_raw.to_string(), image);
+    }
+
+    pub fn check(&self, id_raw: &str, version: &str, urls: &[Url]) -> Result<&LockedImage, String> {
+        let locked = self.images
+            .get(id_raw)
+            .ok_or_else(|| format!("Image {id_raw} is not in the lockfile"))?;
+
+        if locked.version != version {
+            return Err(format!("Image {id_raw} would install version {version}, but the lockfile has {}", locked.version));
+        }
+
+        let locked_urls = locked.artifacts
+            .iter()
+            .map(|artifact| artifact.url.as_str())
+            .collect::<Vec<&str>>();
+
+        let image_urls = urls
+            .iter()
+            .map(Url::as_str)
+            .collect::<Vec<&str>>();
+
+        if locked_urls != image_urls {
+            return Err(format!("Image {id_raw} would download {:?}, but the lockfile has {:?}", image_urls, locked_urls));
+        }
+
+        Ok(locked)
+    }
+
+    pub fn digests(&self) -> Result<HashMap<Url, String>, String> {
+        self.images
+            .values()
+            .flat_map(|image| &image.artifacts)
+            .map(|artifact| Url::parse(&artifact.url)
+                .map(|url| (url, artifact.sha256.clone()))
+                .map_err(|error| format!("Invalid locked URL {}: {error}", artifact.url)))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LockSettings {
+    path: PathBuf,
+    locked: bool,
+}
+
+impl LockSettings {
+    pub fn new(path: PathBuf, locked: bool) -> Result<Self, String> {
+        if locked && !path.is_file() {
+            return Err(format!("Lockfile {} not found, install without --locked to create it", path.display()));
+        }
+
+        Ok(LockSettings { path, locked })
+    }
+
+    pub fn download_options(&self, options: DownloadOptions) -> Result<DownloadOptions, String> {
+        if self.locked {
+            Ok(options.with_locked_digests(Lockfile::load(&self.path)?.digests()?))
+        } else {
+            Ok(options.recording_digests())
+        }
+    }
+}
+
+static WRITE: Mutex<()> = Mutex::new(());
+
+pub fn check_image(ops: &dyn ImageOps, settings: &LockSettings) -> Result<(), String> {
+    let LockSettings { path, locked: true } = settings else { return Ok(()) };
+
+    let image = ops.image();
+    let urls = artifact_urls(ops)?;
+    let lockfile = Lockfile::load(path)?;
+
+    lockfile
+        .check(&image.id().to_string(), &image.package().software.version, &urls)
+        .map(|_| ())
+}
+
+pub fn record_image(ops: &dyn ImageOps, settings: &LockSettings, download: &DownloadOptions) -> Result<(), String> {
+    let LockSettings { path, locked: false } = settings else { return Ok(()) };
+
+    let image = ops.image();
+    let artifacts = artifact_urls(ops)?
+        .into_iter()
+        .map(|url| download.digest_of(&url)
+            .map(|sha256| LockedArtifact { url: url.to_string(), sha256 })
+            .ok_or_else(|| format!("No SHA-256 of {url} to lock")))
+        .collect::<Result<Vec<LockedArtifact>, String>>()?;
+
+    let locked = LockedImage { version: image.package().software.version, artifacts };
+    let _write = WRITE.lock().map_err(|error| error.to_string())?;
+    let mut lockfile = Lockfile::load(path)?;
+
+    lockfile.set(&image.id().to_string(), locked);
+    lockfile.save(path)
+}
+
+fn artifact_urls(ops: &dyn ImageOps) -> Result<Vec<Url>, String> {
+    ops.artifacts()
+        .map(|artifacts| artifacts.iter().map(|req| req.url()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Url;
+
+    use crate::main::lock::{LockedArtifact, LockedImage, Lockfile};
+
+    fn go_lockfile() -> Lockfile {
+        let mut lockfile = Lockfile::default();
+
+        lockfile.set("go", LockedImage {
+            version: "1.22.5".to_string(),
+            artifacts: vec![LockedArtifact {
+                url: "https://go.dev/dl/go1.22.5.linux-amd64.tar.gz".to_string(),
+                sha256: "904b924d435eaea086515bc63235b192ea441bd8c9b198c507e85009e6e4c7f0".to_string(),
+            }],
+        });
+
+        lockfile
+    }
+
+    #[test]
+    fn checks_locked_image() {
+        let lockfile = go_lockfile();
+        let urls = [Url::parse("https://go.dev/dl/go1.22.5.linux-amd64.tar.gz").unwrap()];
+        let other_urls = [Url::parse("https://go.dev/dl/go1.23.0.linux-amd64.tar.gz").unwrap()];
+
+        assert!(lockfile.check("go", "1.22.5", &urls).is_ok());
+        assert!(lockfile.check("go", "1.23.0", &urls).is_err());
+        assert!(lockfile.check("go", "1.22.5", &other_urls).is_err());
+        assert!(lockfile.check("go", "1.22.5", &[]).is_err());
+        assert!(lockfile.check("zig", "0.13.0", &[]).is_err());
+    }
+
+    #[test]
+    fn reads_locked_digests() {
+        let digests = go_lockfile().digests().unwrap();
+        let url = Url::parse("https://go.dev/dl/go1.22.5.linux-amd64.tar.gz").unwrap();
+
+        assert_eq!(1, digests.len());
+        assert_eq!(Some("904b924d435eaea086515bc63235b192ea441bd8c9b198c507e85009e6e4c7f0"), digests.get(&url).map(String::as_str));
+    }
+
+    #[test]
+    fn serializes_lockfile() {
+        let lockfile = go_lockfile();
+        let lockfile_json = serde_json::to_string(&lockfile).unwrap();
+
+        assert_eq!(lockfile, serde_json::from_str(&lockfile_json).unwrap());
+        assert!(lockfile_json.starts_with(r#"{"images":{"go":{"version":"1.22.5","artifacts":[{"url":"#));
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::main::exec::OperationContext;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ManifestFormat {
+    Json,
+    Toml,
+}
+
+impl ManifestFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => ManifestFormat::Toml,
+            _ => ManifestFormat::Json,
+        }
+    }
+}
+
+/// Declarative list of images to install, like a `system.json` file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    images: Vec<ManifestEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ManifestEntry {
+    Id(String),
+    Image {
+        id: String,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        config: Option<bool>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<String>,
+    },
+}
+
+impl ManifestEntry {
+    pub fn id(&self) -> &str {
+        match self {
+            ManifestEntry::Id(id) => id,
+            ManifestEntry::Image { id, .. } => id,
+        }
+    }
+
+    pub fn config(&self, default: bool) -> bool {
+        match self {
+            ManifestEntry::Image { config: Some(config), .. } => *config,
+            _ => default,
+        }
+    }
+}
+
+impl Manifest {
+    pub fn new(images: Vec<ManifestEntry>) -> Self {
+        Manifest { images }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let manifest = fs::read_to_string(path)
+            .map_err(|error| format!("Fail to read manifest at {:?}.\nCause: {}", path, error))?;
+
+        Self::parse(&manifest, ManifestFormat::from_path(path))
+            .map_err(|error| format!("Fail to parse manifest at {:?}.\nCause: {}", path, error))
+    }
+
+    pub fn parse(manifest: &str, format: ManifestFormat) -> Result<Self, String> {
+        match format {
+            ManifestFormat::Json => serde_json::from_str(manifest).map_err(|error| error.to_string()),
+            ManifestFormat::Toml => toml::from_str(manifest).map_err(|error| error.to_string()),
+        }
+    }
+
+    pub fn serialize(&self, format: ManifestFormat) -> Result<String, String> {
+        match format {
+            ManifestFormat::Json => serde_json::to_string_pretty(self).map_err(|error| error.to_string()),
+            ManifestFormat::Toml => toml::to_string_pretty(self).map_err(|error| error.to_string()),
+        }
+    }
+
+    /// Drifted versions won't reproduce the exported machine.
+    pub fn warn_version_drift(&self, ctx: &OperationContext) {
+        for entry in &self.images {
+            let ManifestEntry::Image { id, version: Some(version), .. } = entry else { continue };
+
+            let Ok(image) = ctx.load_image(id) else { continue };
+            let repository_version = image.image().package().software.version;
+
+            if repository_version != *version {
+                info!(
+                    "⚠️ Manifest has {id} {version}, but the repository will install {repository_version}. Run `system pin {id} {version}` to hold it back."
+                );
+            }
+        }
+    }
+
+    pub fn image_ids(&self) -> Vec<String> {
+        self.images
+            .iter()
+            .map(|entry| entry.id().to_string())
+            .collect()
+    }
+
+    /// The first entry of the ID wins.
+    pub fn config_of(&self, id_raw: &str, default: bool) -> bool {
+        self.images
+            .iter()
+            .find(|entry| entry.id() == id_raw)
+            .map_or(default, |entry| entry.config(default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::main::manifest::{Manifest, ManifestEntry, ManifestFormat};
+
+    #[test]
+    fn round_trips_toml() {
+        let manifest = Manifest::new(vec![
+            ManifestEntry::Image { id: "go".to_string(), config: None, version: Some("1.22.5".to_string()) },
+            ManifestEntry::Image { id: "git".to_string(), config: Some(true), version: None },
+        ]);
+
+        let manifest_toml = manifest.serialize(ManifestFormat::Toml).unwrap();
+        let parsed = Manifest::parse(&manifest_toml, ManifestFormat::Toml).unwrap();
+
+        assert!(manifest_toml.contains("[[images]]"));
+        assert_eq!(vec!["go", "git"], parsed.image_ids());
+        assert!(parsed.config_of("git", false));
+    }
+
+    #[test]
+    fn reads_ids_and_overrides() {
+        let manifest: Manifest = serde_json::from_str(r#"{
+            "images": [
+                "rust",
+                { "id": "miniconda", "config": true },
+                { "id": "git" }
+            ]
+        }"#).unwrap();
+
+        assert_eq!(vec!["rust", "miniconda", "git"], manifest.image_ids());
+        assert!(!manifest.config_of("rust", false));
+        assert!(manifest.config_of("miniconda", false));
+        assert!(manifest.config_of("git", true));
+    }
+}
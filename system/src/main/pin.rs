@@ -0,0 +1,52 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+
+use serde_json::Value;
+
+use crate::image::pin::{Pin, Pins};
+use crate::image::repository::Repository;
+
+pub fn pin(id_raw: &str, version: &str, hash_sha256: &Option<String>) -> Result<(), String> {
+    let id = Repository::image_loader_from(id_raw)?.to_image_id();
+    let info_path = Repository::info_path(&id);
+    let mut info: Value = fs::read_to_string(&info_path)
+        .map_err(|error| format!("Image {id} has no information file to pin.\nCause: {error}"))
+        .and_then(|info_json| serde_json::from_str(&info_json).map_err(|error| error.to_string()))?;
+
+    let has_hash = info.get("hash_sha256").is_some();
+    let pin = Pin { version: version.to_string(), hash_sha256: hash_sha256.clone() };
+
+    pin.apply(&mut info)
+        .map_err(|error| format!("Fail to pin image {id}: {error}"))?;
+
+    let mut pins = Pins::load()?;
+
+    pins.set(&id, pin);
+    pins.save()?;
+
+    info!("📌 Pin image {id} to version {version}.");
+
+    if has_hash && hash_sha256.is_none() {
+        info!("Set --hash-sha256 with the vendor hash of version {version}, or its download will fail the integrity check.");
+    }
+
+    Ok(())
+}
+
+pub fn unpin(id_raw: &str) -> Result<(), String> {
+    let id = Repository::image_loader_from(id_raw)?.to_image_id();
+    let mut pins = Pins::load()?;
+
+    match pins.remove(&id) {
+        Some(Pin { version, .. }) => {
+            pins.save()?;
+            info!("Unpin image {id} from version {version}.");
+        }
+        None => info!("Image {id} is not pinned."),
+    }
+
+    Ok(())
+}
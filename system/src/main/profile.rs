@@ -0,0 +1,60 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::main::manifest::Manifest;
+
+pub const DEFAULT_PROFILES_PATH: &str = "profiles.json";
+
+/// Named provisioning profiles, like `desktop-dev` or `ci-server`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Profiles(BTreeMap<String, Manifest>);
+
+impl Profiles {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = File::open(path)
+            .map_err(|error| format!("Fail to read profiles at {:?}.\nCause: {}", path, error))?;
+
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|error| format!("Fail to parse profiles at {:?}.\nCause: {}", path, error))
+    }
+
+    pub fn profile(&self, name: &str) -> Result<&Manifest, String> {
+        self.0
+            .get(name)
+            .ok_or_else(|| format!(
+                "Profile {} not found. Available profiles: {:?}",
+                name,
+                self.0.keys().collect::<Vec<&String>>(),
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::image::repository::Repository;
+    use crate::main::profile::{Profiles, DEFAULT_PROFILES_PATH};
+
+    #[test]
+    fn default_profiles_use_repository_images() {
+        let profiles = Profiles::load(&PathBuf::from(DEFAULT_PROFILES_PATH)).unwrap();
+
+        for manifest in profiles.0.values() {
+            for id_raw in manifest.image_ids() {
+                assert!(Repository::image_loader_from(&id_raw).is_ok(), "Unknown image {id_raw}");
+            }
+        }
+
+        assert!(profiles.profile("ci-server").is_ok());
+        assert!(profiles.profile("unknown").is_err());
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::main::batch::BatchOperation;
+use crate::main::exec::OperationExecution;
+use crate::main::exit_code::{check_image_ids, CliError};
+use crate::tmp;
+
+/// Lets `install --resume` retry only the rest of the batch after an interruption.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InstallProgress {
+    pub images: Vec<String>,
+    pub config: Vec<String>,
+
+    pub done: Vec<String>,
+}
+
+impl InstallProgress {
+    pub fn new(images: &[String], config_of: impl Fn(&str) -> bool) -> Self {
+        InstallProgress {
+            images: images.to_vec(),
+            config: images
+                .iter()
+                .filter(|id_raw| config_of(id_raw))
+                .cloned()
+                .collect(),
+            done: Vec::new(),
+        }
+    }
+
+    pub fn remaining(&self) -> Vec<String> {
+        self.images
+            .iter()
+            .filter(|id_raw| !self.done.contains(id_raw))
+            .cloned()
+            .collect()
+    }
+
+    pub fn config_of(&self, id_raw: &str) -> bool {
+        self.config.iter().any(|id| id == id_raw)
+    }
+
+    pub fn mark_done(&mut self, id_raw: &str) {
+        if !self.done.iter().any(|id| id == id_raw) {
+            self.done.push(id_raw.to_string());
+        }
+    }
+}
+
+/// Stored at `~/.local/share/mathswe-ops/install-progress.json`.
+pub struct ProgressStore {
+    path: PathBuf,
+}
+
+impl ProgressStore {
+    pub fn user() -> Result<Self, String> {
+        tmp::data_dir()
+            .map(|dir| ProgressStore { path: dir.join("install-progress.json") })
+            .ok_or_else(|| "Fail to find the user data directory".to_string())
+    }
+
+    pub fn save(&self, progress: &InstallProgress) -> Result<(), String> {
+        let progress_json = serde_json::to_string_pretty(progress)
+            .map_err(|error| error.to_string())?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+
+        fs::write(&self.path, progress_json).map_err(|error| error.to_string())
+    }
+
+    pub fn load(&self) -> Result<InstallProgress, String> {
+        if !self.path.exists() {
+            return Err("No interrupted install to resume".to_string());
+        }
+
+        let progress_json = fs::read_to_string(&self.path).map_err(|error| error.to_string())?;
+
+        serde_json::from_str(&progress_json).map_err(|error| error.to_string())
+    }
+
+    pub fn clear(&self) -> Result<(), String> {
+        if self.path.exists() {
+            fs::remove_file(&self.path).map_err(|error| error.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn install(
+    batch: &BatchOperation,
+    exec: &OperationExecution,
+    progress: InstallProgress,
+) -> Result<(), CliError> {
+    let store = ProgressStore::user()?;
+    let images = progress.remaining();
+
+    check_image_ids(&images)?;
+    store.save(&progress)?;
+    exec.prefetch_installs(&images);
+
+    let progress = Mutex::new(progress);
+
+    let result = batch.execute(&images, |id_raw| {
+        let config = progress
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .config_of(id_raw);
+
+        let result = exec.install(id_raw, &config);
+
+        if result.is_ok() {
+            let mut progress = progress
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            progress.mark_done(id_raw);
+
+            if let Err(error) = store.save(&progress) {
+                eprintln!("Fail to save the install progress of {id_raw}: {error}");
+            }
+        }
+
+        result
+    });
+
+    if result.is_ok() {
+        store.clear()?;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::main::resume::InstallProgress;
+
+    #[test]
+    fn resumes_remaining_images() {
+        let images = ["git", "rust", "go"].map(String::from);
+        let mut progress = InstallProgress::new(&images, |id_raw| id_raw == "git");
+
+        progress.mark_done("git");
+        progress.mark_done("git");
+
+        assert_eq!(vec!["rust".to_string(), "go".to_string()], progress.remaining());
+        assert_eq!(vec!["git".to_string()], progress.done);
+        assert!(progress.config_of("git"));
+        assert!(!progress.config_of("rust"));
+    }
+}
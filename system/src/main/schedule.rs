@@ -0,0 +1,144 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cmd::{exec_cmd, print_output};
+use crate::os;
+
+const UNIT_NAME: &str = "mathswe-ops-upgrade";
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+}
+
+/// It's the systemd `OnCalendar` value of the frequency.
+impl Display for Frequency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+fn user_unit_dir() -> Result<PathBuf, String> {
+    dirs::config_dir()
+        .map(|dir| dir.join("systemd").join("user"))
+        .ok_or_else(|| "Fail to read the user config directory".to_string())
+}
+
+fn service_unit(exe: &Path) -> String {
+    format!(
+        "[Unit]
+Description=Upgrade the MathSwe System images
+
+[Service]
+Type=oneshot
+ExecStart={} --quiet upgrade --all
+",
+        exe.display(),
+    )
+}
+
+/// The timer is persistent, so it runs at the next boot if the machine was off.
+fn timer_unit(frequency: Frequency) -> String {
+    format!(
+        "[Unit]
+Description=Schedule the MathSwe System images upgrade
+
+[Timer]
+OnCalendar={frequency}
+Persistent=true
+
+[Install]
+WantedBy=timers.target
+"
+    )
+}
+
+fn exec_systemctl_user(args: &[&str]) -> Result<(), String> {
+    let args = ["--user"]
+        .into_iter()
+        .chain(args.iter().copied())
+        .collect::<Vec<&str>>();
+
+    let output = exec_cmd("systemctl", &args)
+        .map_err(|error| error.to_string())?;
+
+    print_output(output);
+
+    Ok(())
+}
+
+pub fn schedule_upgrade(frequency: Frequency) -> Result<(), String> {
+    let exe = env::current_exe().map_err(|error| error.to_string())?;
+    let unit_dir = user_unit_dir()?;
+
+    fs::create_dir_all(&unit_dir).map_err(|error| error.to_string())?;
+
+    info!("Writing units {UNIT_NAME}.service and {UNIT_NAME}.timer...");
+
+    fs::write(unit_dir.join(format!("{UNIT_NAME}.service")), service_unit(&exe))
+        .and_then(|_| fs::write(unit_dir.join(format!("{UNIT_NAME}.timer")), timer_unit(frequency)))
+        .map_err(|error| error.to_string())?;
+
+    os::warn_without_systemd();
+
+    exec_systemctl_user(&["daemon-reload"])?;
+    exec_systemctl_user(&["enable", "--now", &format!("{UNIT_NAME}.timer")])?;
+
+    info!("✅ Schedule {frequency} upgrade of the installed images.");
+
+    Ok(())
+}
+
+pub fn remove_schedule() -> Result<(), String> {
+    let unit_dir = user_unit_dir()?;
+    let timer_path = unit_dir.join(format!("{UNIT_NAME}.timer"));
+    let service_path = unit_dir.join(format!("{UNIT_NAME}.service"));
+
+    if !timer_path.exists() && !service_path.exists() {
+        info!("No upgrade is scheduled.");
+        return Ok(());
+    }
+
+    exec_systemctl_user(&["disable", "--now", &format!("{UNIT_NAME}.timer")])?;
+
+    [timer_path, service_path]
+        .iter()
+        .filter(|path| path.exists())
+        .try_for_each(fs::remove_file)
+        .map_err(|error| error.to_string())?;
+
+    exec_systemctl_user(&["daemon-reload"])?;
+
+    info!("✅ Remove the scheduled upgrade.");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::main::schedule::{service_unit, timer_unit, Frequency};
+
+    #[test]
+    fn renders_upgrade_units() {
+        let service = service_unit(Path::new("/home/user/.local/bin/system"));
+        let timer = timer_unit(Frequency::Weekly);
+
+        assert!(service.contains("ExecStart=/home/user/.local/bin/system --quiet upgrade --all\n"));
+        assert!(timer.contains("OnCalendar=weekly\n"));
+        assert!(timer.contains("WantedBy=timers.target\n"));
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::cmp::Reverse;
+
+use crate::image::repository::Repository;
+use crate::main::exec::OperationContext;
+use crate::package::Software;
+
+pub struct SearchMatch {
+    id: String,
+    software: Option<Software>,
+    score: u32,
+}
+
+pub struct ImageSearch(Vec<SearchMatch>);
+
+impl ImageSearch {
+    pub fn load(ctx: &OperationContext, query: &str) -> Self {
+        let to_match = |id: String| {
+            let software = ctx
+                .load_image(&id)
+                .ok()
+                .map(|image| image.image().package().software);
+
+            let fields = software
+                .iter()
+                .flat_map(|software| [software.name.clone(), software.provider.clone()])
+                .chain([id.clone()]);
+
+            fields
+                .filter_map(|field| fuzzy_score(query, &field))
+                .max()
+                .map(|score| SearchMatch { id, software, score })
+        };
+
+        let mut matches = Repository::desktop_image_ids()
+            .into_iter()
+            .chain(Repository::server_image_ids())
+            .map(|id| id.to_string())
+            .filter_map(to_match)
+            .collect::<Vec<SearchMatch>>();
+
+        matches.sort_by_key(|m| Reverse(m.score));
+
+        ImageSearch(matches)
+    }
+
+    pub fn print(&self) {
+        if self.0.is_empty() {
+            println!("No images found.");
+            return;
+        }
+
+        for SearchMatch { id, software, .. } in &self.0 {
+            match software {
+                Some(Software { name, provider, .. }) if !provider.is_empty() =>
+                    println!("{id}  {name} ({provider})"),
+
+                Some(Software { name, .. }) => println!("{id}  {name}"),
+                None => println!("{id}"),
+            }
+        }
+    }
+}
+
+/// Substring matches score over scattered ones, and earlier, tighter ones higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query = query.to_lowercase();
+    let text = text.to_lowercase();
+
+    if query.is_empty() {
+        return None;
+    }
+
+    if let Some(position) = text.find(&query) {
+        let penalty = position + text.len() - query.len();
+
+        return Some(2000u32.saturating_sub(penalty as u32));
+    }
+
+    let mut text_chars = text.chars().enumerate();
+    let mut gaps = 0;
+    let mut last_index = None;
+
+    for query_char in query.chars() {
+        let (index, _) = text_chars.find(|(_, text_char)| *text_char == query_char)?;
+
+        if let Some(last) = last_index {
+            gaps += index - last - 1;
+        }
+
+        last_index = Some(index);
+    }
+
+    Some(1000u32.saturating_sub(gaps as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::main::search::fuzzy_score;
+
+    #[test]
+    fn scores_substring_over_subsequence() {
+        let substring = fuzzy_score("idea", "intellij-idea").unwrap();
+        let subsequence = fuzzy_score("itid", "intellij-idea").unwrap();
+
+        assert!(substring > subsequence);
+        assert!(fuzzy_score("IDEA", "IntelliJ IDEA").is_some());
+        assert!(fuzzy_score("jvs", "rust").is_none());
+        assert!(fuzzy_score("", "rust").is_none());
+    }
+
+    #[test]
+    fn scores_tighter_matches_higher() {
+        let exact = fuzzy_score("go", "go").unwrap();
+        let prefix = fuzzy_score("go", "goland").unwrap();
+        let tight = fuzzy_score("vsc", "vscode").unwrap();
+        let loose = fuzzy_score("vce", "vscode").unwrap();
+
+        assert!(exact > prefix);
+        assert!(tight > loose);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use reqwest::{blocking, Url};
+use serde::Deserialize;
+
+use crate::download::{DownloadOptions, DownloadRequest, Downloader, Integrity};
+use crate::tmp::TmpWorkingDir;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/mathswe-ops/mathswe-ops---mvp/releases";
+
+/// Releases of this monorepo app are tagged like `system/v0.2.0`.
+const TAG_PREFIX: &str = "system/v";
+
+const BINARY_ASSET: &str = "system-linux-x64";
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    draft: bool,
+    prerelease: bool,
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn version(&self) -> Option<&str> {
+        self.tag_name.strip_prefix(TAG_PREFIX)
+    }
+
+    fn asset_url(&self, name: &str) -> Result<String, String> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .map(|asset| asset.browser_download_url.clone())
+            .ok_or_else(|| format!("Release {} has no asset {}", self.tag_name, name))
+    }
+}
+
+fn client() -> Result<blocking::Client, String> {
+    blocking::Client::builder()
+        .user_agent(format!("system/{}", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|error| error.to_string())
+}
+
+fn fetch_latest_release() -> Result<Release, String> {
+    let releases_json = client()?
+        .get(RELEASES_URL)
+        .send()
+        .and_then(|res| res.error_for_status())
+        .and_then(|res| res.text())
+        .map_err(|error| format!("Fail to fetch the releases.\nCause: {error}"))?;
+
+    let releases: Vec<Release> = serde_json::from_str(&releases_json)
+        .map_err(|error| error.to_string())?;
+
+    latest_release(releases).ok_or_else(|| "No system release found".to_string())
+}
+
+/// GitHub lists the releases from the newest.
+fn latest_release(releases: Vec<Release>) -> Option<Release> {
+    releases
+        .into_iter()
+        .find(|release| !release.draft && !release.prerelease && release.version().is_some())
+}
+
+/// The final rename within the same file system replaces the executable atomically.
+fn replace_executable(new_binary: &Path, current_exe: &Path) -> Result<(), String> {
+    let staged = current_exe.with_extension("new");
+
+    fs::copy(new_binary, &staged)
+        .and_then(|_| fs::set_permissions(&staged, fs::Permissions::from_mode(0o755)))
+        .and_then(|_| fs::rename(&staged, current_exe))
+        .map_err(|error| {
+            let _ = fs::remove_file(&staged);
+
+            format!(
+                "Fail to replace {}.\nCause: {error}\nIf the app is installed system-wide, update it with its .deb package instead.",
+                current_exe.display(),
+            )
+        })
+}
+
+pub fn self_update(download: &DownloadOptions) -> Result<(), String> {
+    let current_version = env!("CARGO_PKG_VERSION");
+    let release = fetch_latest_release()?;
+    let latest_version = release.version().unwrap_or_default().to_string();
+
+    if latest_version == current_version {
+        info!("✅ System {current_version} is up to date.");
+        return Ok(());
+    }
+
+    info!("Updating System {current_version} to {latest_version}...");
+
+    let binary_url = release.asset_url(BINARY_ASSET)?;
+    let checksum_url = release.asset_url(&format!("{BINARY_ASSET}.sha256"))?;
+    let integrity = Integrity::RemoteSha256 {
+        url: Url::parse(&checksum_url).map_err(|error| error.to_string())?,
+    };
+    let req = DownloadRequest::new(&binary_url, integrity)
+        .map_err(|error| error.to_string())?
+        .with_options(download.clone());
+
+    let tmp = TmpWorkingDir::new().map_err(|error| error.to_string())?;
+    let downloader = Downloader::from(req, &tmp);
+
+    info!("Downloading {binary_url}...");
+
+    downloader
+        .download_blocking()
+        .map_err(|error| error.to_string())?;
+
+    let current_exe = env::current_exe().map_err(|error| error.to_string())?;
+
+    replace_executable(&downloader.path, &current_exe)?;
+
+    info!("✅ Update System to {latest_version}.");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::main::self_update::{latest_release, Release};
+
+    fn release(tag_name: &str, prerelease: bool) -> Release {
+        Release { tag_name: tag_name.to_string(), draft: false, prerelease, assets: Vec::new() }
+    }
+
+    #[test]
+    fn finds_latest_stable_system_release() {
+        let releases = vec![
+            release("system/v0.3.0-rc.1", true),
+            release("ops/v1.0.0", false),
+            release("system/v0.2.0", false),
+            release("system/v0.1.0", false),
+        ];
+
+        let latest = latest_release(releases).unwrap();
+
+        assert_eq!(Some("0.2.0"), latest.version());
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use crate::image::detection::InstallStatus;
+use crate::image::detection::InstallStatus::Installed;
+use crate::main::exec::OperationContext;
+
+pub struct ImageStatus {
+    id_raw: String,
+    status: Result<InstallStatus, String>,
+}
+
+pub struct StatusReport(Vec<ImageStatus>);
+
+impl StatusReport {
+    pub fn load(ctx: &OperationContext, images: &[String]) -> Self {
+        let detect = |id_raw: &String| ImageStatus {
+            id_raw: id_raw.clone(),
+            status: ctx
+                .load_image(id_raw)
+                .and_then(|image| image.detection().detect()),
+        };
+
+        StatusReport(images.iter().map(detect).collect())
+    }
+
+    pub fn print(&self) -> Result<(), String> {
+        let mut failed = Vec::new();
+
+        for ImageStatus { id_raw, status } in &self.0 {
+            match status {
+                Ok(status @ Installed(_)) => println!("✅ {id_raw}: {status}"),
+                Ok(status) => println!("➖ {id_raw}: {status}"),
+                Err(error) => {
+                    println!("❌ Fail to check image {id_raw}.\nCause: {error}");
+                    failed.push(id_raw.clone());
+                }
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Fail to check the status of images {:?}", failed))
+        }
+    }
+}
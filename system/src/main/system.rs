@@ -4,13 +4,14 @@
 
 use std::fmt::{Display, Formatter};
 use Operation::Config;
-use crate::main::system::Operation::{Install, Reinstall, Uninstall};
+use crate::main::system::Operation::{Install, Reinstall, Uninstall, Upgrade};
 
 #[derive(Clone)]
 pub enum Operation {
     Install,
     Uninstall,
     Reinstall,
+    Upgrade,
     Config,
 }
 
@@ -20,6 +21,7 @@ impl Display for Operation {
             Install => "install",
             Uninstall => "uninstall",
             Reinstall => "reinstall",
+            Upgrade => "upgrade",
             Config => "config",
         };
 
@@ -0,0 +1,340 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::exec_cmd;
+use crate::download::hashing::calculate_sha256;
+use crate::image::detection::Detection;
+use crate::image::detection::InstallStatus::NotInstalled;
+use crate::image::ImageId;
+use crate::main::exec::OperationContext;
+use crate::tmp;
+
+/// SHA-256 of the installed files, so later checks find tampered or corrupted ones.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verification {
+    Intact(usize),
+    Tampered(Vec<String>),
+    NoBaseline,
+}
+
+impl Snapshot {
+    /// `None` for packages their package manager checks instead.
+    pub fn take(detection: &Detection) -> Result<Option<Self>, String> {
+        let root = match detection {
+            Detection::Binary(name, _) => resolve_binary(name)?,
+            Detection::HomeDir(rel_path) => dirs::home_dir()
+                .ok_or_else(|| "Fail to read the user home directory".to_string())?
+                .join(rel_path),
+            Detection::DpkgPackage(_)
+            | Detection::RpmPackage(_)
+            | Detection::PacmanPackage(_)
+            | Detection::BrewPackage(_)
+            | Detection::WingetPackage(_)
+            | Detection::FlatpakPackage(_) => return Ok(None),
+        };
+
+        let mut files = BTreeMap::new();
+
+        hash_files(&root, &mut files)?;
+
+        Ok(Some(Snapshot { files }))
+    }
+
+    pub fn diff(&self, current: &Snapshot) -> Vec<String> {
+        let changed = self.files
+            .iter()
+            .filter_map(|(path, hash)| match current.files.get(path) {
+                Some(current_hash) if current_hash == hash => None,
+                Some(_) => Some(format!("modified {}", path.display())),
+                None => Some(format!("missing {}", path.display())),
+            });
+
+        let added = current.files
+            .keys()
+            .filter(|path| !self.files.contains_key(*path))
+            .map(|path| format!("added {}", path.display()));
+
+        changed.chain(added).collect()
+    }
+}
+
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    pub fn user() -> Result<Self, String> {
+        tmp::data_dir()
+            .map(|dir| SnapshotStore { dir: dir.join("snapshots") })
+            .ok_or_else(|| "Fail to find the user data directory".to_string())
+    }
+
+    fn path(&self, id: &ImageId) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    pub fn save(&self, id: &ImageId, snapshot: &Snapshot) -> Result<(), String> {
+        let snapshot_json = serde_json::to_string_pretty(snapshot)
+            .map_err(|error| error.to_string())?;
+
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(self.path(id), snapshot_json))
+            .map_err(|error| error.to_string())
+    }
+
+    pub fn load(&self, id: &ImageId) -> Result<Option<Snapshot>, String> {
+        let path = self.path(id);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let snapshot_json = fs::read_to_string(path).map_err(|error| error.to_string())?;
+
+        serde_json::from_str(&snapshot_json)
+            .map(Some)
+            .map_err(|error| error.to_string())
+    }
+
+    pub fn remove(&self, id: &ImageId) -> Result<(), String> {
+        let path = self.path(id);
+
+        if path.exists() {
+            fs::remove_file(path).map_err(|error| error.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Only warns on failure since the installation itself already succeeded.
+    pub fn record(id: &ImageId, detection: &Detection) {
+        let result = Snapshot::take(detection).and_then(|snapshot| match snapshot {
+            Some(snapshot) => Self::user()?.save(id, &snapshot),
+            None => Ok(()),
+        });
+
+        if let Err(error) = result {
+            eprintln!("Fail to record the installed files of {id} to verify them later: {error}");
+        }
+    }
+}
+
+pub fn verify(id: &ImageId, detection: &Detection) -> Result<Verification, String> {
+    if detection.detect()? == NotInstalled {
+        return Err(format!("Image {id} is not installed"));
+    }
+
+    if let Detection::DpkgPackage(name) = detection {
+        return verify_os_package("dpkg", name);
+    }
+
+    if let Detection::RpmPackage(name) = detection {
+        return verify_os_package("rpm", name);
+    }
+
+    if let Detection::PacmanPackage(name) = detection {
+        return verify_pacman_package(name);
+    }
+
+    // Homebrew, winget, and Flatpak keep no checksums of the installed files
+    // to check against
+    if let Detection::BrewPackage(_) | Detection::WingetPackage(_) | Detection::FlatpakPackage(_) = detection {
+        return Ok(Verification::NoBaseline);
+    }
+
+    let Some(baseline) = SnapshotStore::user()?.load(id)? else {
+        return Ok(Verification::NoBaseline);
+    };
+
+    let current = Snapshot::take(detection)?
+        .ok_or_else(|| format!("Fail to read the installed files of {id}"))?;
+
+    let diff = baseline.diff(&current);
+
+    if diff.is_empty() {
+        Ok(Verification::Intact(current.files.len()))
+    } else {
+        Ok(Verification::Tampered(diff))
+    }
+}
+
+fn verify_os_package(pkg_manager: &str, name: &str) -> Result<Verification, String> {
+    let output = exec_cmd("bash", &["-c", &format!("{pkg_manager} --verify {name} || true")])
+        .map_err(|error| error.to_string())?;
+
+    let failures = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| format!("modified {}", line.split_whitespace().last().unwrap_or(line)))
+        .collect::<Vec<String>>();
+
+    if failures.is_empty() {
+        Ok(Verification::Intact(0))
+    } else {
+        Ok(Verification::Tampered(failures))
+    }
+}
+
+fn verify_pacman_package(name: &str) -> Result<Verification, String> {
+    let output = exec_cmd("bash", &["-c", &format!("pacman -Qkk {name} 2>&1 || true")])
+        .map_err(|error| error.to_string())?;
+
+    let failures = pacman_failures(&String::from_utf8_lossy(&output.stdout), name);
+
+    if failures.is_empty() {
+        Ok(Verification::Intact(0))
+    } else {
+        Ok(Verification::Tampered(failures))
+    }
+}
+
+fn pacman_failures(report: &str, name: &str) -> Vec<String> {
+    let prefix = format!("warning: {name}: ");
+
+    report
+        .lines()
+        .filter_map(|line| line.strip_prefix(&prefix))
+        .map(|failure| {
+            let path = failure.split(" (").next().unwrap_or(failure);
+
+            if failure.contains("No such file") {
+                format!("missing {path}")
+            } else {
+                format!("modified {path}")
+            }
+        })
+        .collect()
+}
+
+fn resolve_binary(name: &str) -> Result<PathBuf, String> {
+    let output = exec_cmd("bash", &["-c", &format!("command -v {name}")])
+        .map_err(|_| format!("Binary {name} not found in PATH"))?;
+
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+
+    fs::canonicalize(&path).map_err(|error| error.to_string())
+}
+
+fn hash_files(path: &Path, files: &mut BTreeMap<PathBuf, String>) -> Result<(), String> {
+    let metadata = fs::symlink_metadata(path).map_err(|error| error.to_string())?;
+
+    if metadata.is_dir() {
+        let entries = fs::read_dir(path).map_err(|error| error.to_string())?;
+
+        for entry in entries {
+            let entry = entry.map_err(|error| error.to_string())?;
+
+            hash_files(&entry.path(), files)?;
+        }
+    } else if metadata.is_file() {
+        let hash = calculate_sha256(path).map_err(|error| error.to_string())?;
+
+        files.insert(path.to_path_buf(), hash);
+    }
+
+    Ok(())
+}
+
+pub struct VerifyReport(Vec<(String, Result<Verification, String>)>);
+
+impl VerifyReport {
+    pub fn load(ctx: &OperationContext, images: &[String]) -> Self {
+        let check = |id_raw: &String| {
+            let verification = ctx
+                .load_image(id_raw)
+                .and_then(|image| verify(&image.image().id(), &image.detection()));
+
+            (id_raw.clone(), verification)
+        };
+
+        VerifyReport(images.iter().map(check).collect())
+    }
+
+    pub fn print(&self) -> Result<(), String> {
+        let mut failed = Vec::new();
+
+        for (id_raw, verification) in &self.0 {
+            match verification {
+                Ok(Verification::Intact(0)) => println!("✅ {id_raw}: intact"),
+                Ok(Verification::Intact(num)) => println!("✅ {id_raw}: {num} files intact"),
+                Ok(Verification::NoBaseline) => println!(
+                    "➖ {id_raw}: no installed files recorded; reinstall it to record them"
+                ),
+                Ok(Verification::Tampered(changes)) => {
+                    println!("❌ {id_raw}: {} files changed since installed", changes.len());
+
+                    for change in changes {
+                        println!("   {change}");
+                    }
+
+                    failed.push(id_raw.clone());
+                }
+                Err(error) => {
+                    println!("❌ Fail to verify image {id_raw}.\nCause: {error}");
+                    failed.push(id_raw.clone());
+                }
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Fail to verify images {:?}", failed))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use crate::main::verify::{pacman_failures, Snapshot};
+
+    fn snapshot(files: &[(&str, &str)]) -> Snapshot {
+        let files = files
+            .iter()
+            .map(|(path, hash)| (PathBuf::from(path), hash.to_string()))
+            .collect::<BTreeMap<PathBuf, String>>();
+
+        Snapshot { files }
+    }
+
+    #[test]
+    fn diffs_modified_missing_and_added_files() {
+        let baseline = snapshot(&[("/opt/a", "1"), ("/opt/b", "2"), ("/opt/c", "3")]);
+        let current = snapshot(&[("/opt/a", "1"), ("/opt/b", "9"), ("/opt/d", "4")]);
+
+        assert_eq!(
+            vec!["modified /opt/b", "missing /opt/c", "added /opt/d"],
+            baseline.diff(&current),
+        );
+
+        assert!(baseline.diff(&baseline).is_empty());
+    }
+
+    #[test]
+    fn reads_pacman_failures() {
+        let report = "warning: zoom: /opt/zoom/zoom (SHA256 checksum mismatch)\n\
+            warning: zoom: /opt/zoom/libQt.so (No such file or directory)\n\
+            zoom: 1482 total files, 2 altered files\n";
+
+        assert_eq!(
+            vec!["modified /opt/zoom/zoom".to_string(), "missing /opt/zoom/libQt.so".to_string()],
+            pacman_failures(report, "zoom"),
+        );
+        assert!(pacman_failures("zoom: 1482 total files, 0 altered files\n", "zoom").is_empty());
+    }
+}
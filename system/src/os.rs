@@ -2,53 +2,199 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 // This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
 
-use crate::cmd::{exec_cmd};
-use crate::os::Os::Linux;
+use crate::cmd::{exec_cmd, exec_powershell};
+use crate::os::Os::{Linux, MacOs, Windows};
 use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::{Once, OnceLock};
 use std::time::{Duration, Instant};
-use std::{io, thread};
-use LinuxType::Ubuntu;
-use OsArch::X64;
-use PkgType::Deb;
+use std::{fs, io, thread};
+use serde::{Deserialize, Serialize};
+use LinuxType::{Arch, Debian, Fedora, Ubuntu};
+use OsArch::{Arm64, Armhf, X64};
+use FlatpakScope::{System, User};
+use PkgType::{Brew, BrewCask, Deb, Flatpak, Pacman, Rpm, Winget};
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum OsArch {
-    X64
+    X64,
+    Arm64,
+    Armhf,
 }
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum LinuxType {
-    Ubuntu
+    Ubuntu,
+    Debian,
+    Fedora,
+    Arch,
 }
 
+impl LinuxType {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Ubuntu => "ubuntu",
+            Debian => "debian",
+            Fedora => "fedora",
+            Arch => "arch",
+        }
+    }
+
+    pub fn pkg_type(&self) -> PkgType {
+        match self {
+            Ubuntu | Debian => Deb,
+            Fedora => Rpm,
+            Arch => Pacman,
+        }
+    }
+}
+
+// `MacOs` ends with the enum name like the OS name does
+#[allow(clippy::enum_variant_names)]
 #[derive(PartialEq, Clone, Debug)]
 pub enum Os {
-    Linux(OsArch, LinuxType)
+    Linux(OsArch, LinuxType),
+    MacOs(OsArch),
+    Windows(OsArch),
 }
 
+impl Os {
+    pub fn distro_id(&self) -> &'static str {
+        match self {
+            Linux(_, linux_type) => linux_type.id(),
+            MacOs(_) => "macos",
+            Windows(_) => "windows",
+        }
+    }
+
+    pub fn pkg_type(&self) -> PkgType {
+        match self {
+            Linux(_, linux_type) => linux_type.pkg_type(),
+            MacOs(_) => Brew,
+            Windows(_) => Winget,
+        }
+    }
+
+    pub fn app_pkg_type(&self) -> PkgType {
+        match self {
+            Linux(_, linux_type) => linux_type.pkg_type(),
+            MacOs(_) => BrewCask,
+            Windows(_) => Winget,
+        }
+    }
+
+    /// WSL has no desktop session, and only runs systemd if enabled.
+    pub fn is_wsl(&self) -> bool {
+        matches!(self, Linux(_, _)) && WSL.get().copied().unwrap_or(false)
+    }
+
+    pub fn login_profile(&self) -> Result<&'static str, String> {
+        match self {
+            Linux(_, _) => Ok(".profile"),
+            MacOs(_) => Ok(".zprofile"),
+            Windows(_) => Err(format!("No login profile on {}", self.distro_id())),
+        }
+    }
+
+    pub fn shell_rc_files(&self) -> Result<&'static [&'static str], String> {
+        match self {
+            Linux(_, _) => Ok(&[".bashrc"]),
+            MacOs(_) => Ok(&[".zshrc", ".bash_profile", ".bashrc"]),
+            Windows(_) => Err(format!("No shell init files on {}", self.distro_id())),
+        }
+    }
+}
+
+static WSL: OnceLock<bool> = OnceLock::new();
+
+// `detect_os` combines the Linux architecture with the distro, so only tests
+// name these hosts
+#[cfg(test)]
 pub const UBUNTU_X64: Os = Linux(X64, Ubuntu);
 
+#[cfg(test)]
+pub const DEBIAN_ARM64: Os = Linux(Arm64, Debian);
+
+#[cfg(test)]
+pub const DEBIAN_ARMHF: Os = Linux(Armhf, Debian);
+
+#[cfg(test)]
+pub const FEDORA_X64: Os = Linux(X64, Fedora);
+
+#[cfg(test)]
+pub const ARCH_X64: Os = Linux(X64, Arch);
+
+pub const MACOS_X64: Os = MacOs(X64);
+
+pub const MACOS_ARM64: Os = MacOs(Arm64);
+
+pub const WINDOWS_X64: Os = Windows(X64);
+
+#[derive(PartialEq, Clone, Debug)]
 pub enum PkgType {
-    Deb
+    Deb,
+    Rpm,
+    Pacman,
+    Brew,
+    BrewCask,
+    Winget,
+    Flatpak(FlatpakScope),
 }
 
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlatpakScope {
+    User,
+    System,
+}
+
+impl FlatpakScope {
+    fn flag(&self) -> &'static str {
+        match self {
+            User => "--user",
+            System => "--system",
+        }
+    }
+}
+
+const FLATHUB_REPO: &str = "https://dl.flathub.org/repo/flathub.flatpakrepo";
+
 pub struct OsPkg {
     pub pkg_type: PkgType,
     pub name: String,
 }
 
 impl OsPkg {
+    /// Homebrew and winget install by name instead, like `install_managed`.
     pub fn install(&self, installer_path: &PathBuf) -> Result<(), String> {
-        match self.pkg_type {
-            Deb => Self::install_deb(installer_path)
+        match &self.pkg_type {
+            Deb => Self::install_deb(installer_path),
+            Rpm => Self::install_rpm(installer_path),
+            Pacman => Self::install_pacman(installer_path),
+            Brew | BrewCask | Winget | Flatpak(_) => self.install_managed(),
+        }
+    }
+
+    pub fn install_managed(&self) -> Result<(), String> {
+        match &self.pkg_type {
+            Brew => Self::install_brew(&self.name, false),
+            BrewCask => Self::install_brew(&self.name, true),
+            Winget => Self::install_winget(&self.name),
+            Flatpak(scope) => Self::install_flatpak(&self.name, scope),
+            Deb | Rpm | Pacman => Err(format!("Package {} installs from a downloaded file", self.name)),
         }
     }
 
     pub fn uninstall(&self) -> Result<(), String> {
-        match self.pkg_type {
-            Deb => Self::uninstall_deb(&self.name)
+        match &self.pkg_type {
+            Deb => Self::uninstall_deb(&self.name),
+            Rpm => Self::uninstall_rpm(&self.name),
+            Pacman => Self::uninstall_pacman(&self.name),
+            Brew => Self::uninstall_brew(&self.name, false),
+            BrewCask => Self::uninstall_brew(&self.name, true),
+            Winget => Self::uninstall_winget(&self.name),
+            Flatpak(scope) => Self::uninstall_flatpak(&self.name, scope),
         }
     }
 
@@ -59,13 +205,13 @@ impl OsPkg {
         ).map_err(|error| error.to_string())?;
         let stdout = String::from_utf8_lossy(&output.stdout);
 
-        println!("{}", stdout);
+        verbose!("{}", stdout);
 
         Ok(())
     }
 
     fn uninstall_deb(name: &str) -> Result<(), String> {
-        println!("{}", format!("Removing package {}...", name));
+        info!("{}", format!("Removing package {}...", name));
 
         let output = exec_cmd(
             "sudo",
@@ -73,9 +219,9 @@ impl OsPkg {
         ).map_err(|error| error.to_string())?;
         let stdout = String::from_utf8_lossy(&output.stdout);
 
-        println!("{}", stdout);
+        verbose!("{}", stdout);
 
-        println!("Cleaning up no longer required packages...");
+        info!("Cleaning up no longer required packages...");
 
         let output = exec_cmd(
             "sudo",
@@ -83,32 +229,237 @@ impl OsPkg {
         ).map_err(|error| error.to_string())?;
         let stdout = String::from_utf8_lossy(&output.stdout);
 
-        println!("{}", stdout);
+        verbose!("{}", stdout);
+
+        Ok(())
+    }
+
+    fn install_rpm(installer: &Path) -> Result<(), String> {
+        let output = exec_cmd(
+            "sudo",
+            &["dnf", "--assumeyes", "install", installer.to_str().unwrap()],
+        ).map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        verbose!("{}", stdout);
+
+        Ok(())
+    }
+
+    fn uninstall_rpm(name: &str) -> Result<(), String> {
+        info!("{}", format!("Removing package {}...", name));
+
+        let output = exec_cmd(
+            "sudo",
+            &["dnf", "--assumeyes", "remove", name],
+        ).map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        verbose!("{}", stdout);
+
+        Ok(())
+    }
+
+    fn install_pacman(installer: &Path) -> Result<(), String> {
+        let output = exec_cmd(
+            "sudo",
+            &["pacman", "--noconfirm", "--upgrade", installer.to_str().unwrap()],
+        ).map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        verbose!("{}", stdout);
+
+        Ok(())
+    }
+
+    fn uninstall_pacman(name: &str) -> Result<(), String> {
+        info!("{}", format!("Removing package {}...", name));
+
+        let output = exec_cmd(
+            "sudo",
+            &["pacman", "--noconfirm", "--remove", "--recursive", name],
+        ).map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        verbose!("{}", stdout);
+
+        Ok(())
+    }
+
+    fn install_brew(name: &str, cask: bool) -> Result<(), String> {
+        let output = exec_cmd("brew", &brew_args("install", name, cask))
+            .map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        verbose!("{}", stdout);
+
+        Ok(())
+    }
+
+    fn uninstall_brew(name: &str, cask: bool) -> Result<(), String> {
+        info!("{}", format!("Removing package {}...", name));
+
+        let output = exec_cmd("brew", &brew_args("uninstall", name, cask))
+            .map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        verbose!("{}", stdout);
+
+        Ok(())
+    }
+
+    fn install_winget(id: &str) -> Result<(), String> {
+        let output = exec_powershell(&format!(
+            "winget install --id {id} --exact --silent --accept-package-agreements --accept-source-agreements"
+        )).map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        verbose!("{}", stdout);
+
+        Ok(())
+    }
+
+    fn uninstall_winget(id: &str) -> Result<(), String> {
+        info!("{}", format!("Removing package {}...", id));
+
+        let output = exec_powershell(&format!("winget uninstall --id {id} --exact --silent"))
+            .map_err(|error| error.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        verbose!("{}", stdout);
 
         Ok(())
     }
+
+    fn install_flatpak(id: &str, scope: &FlatpakScope) -> Result<(), String> {
+        exec_cmd("bash", &["-c", "command -v flatpak"])
+            .map_err(|_| "Flatpak is not installed. Install it with the OS package manager, e.g., `sudo apt-get install flatpak`".to_string())?;
+
+        info!("Adding Flathub remote...");
+
+        exec_flatpak(scope, &flatpak_args(scope, "remote-add", &["--if-not-exists", "flathub", FLATHUB_REPO]))?;
+
+        exec_flatpak(scope, &flatpak_args(scope, "install", &["--noninteractive", "flathub", id]))
+    }
+
+    fn uninstall_flatpak(id: &str, scope: &FlatpakScope) -> Result<(), String> {
+        info!("{}", format!("Removing package {}...", id));
+
+        exec_flatpak(scope, &flatpak_args(scope, "uninstall", &["--noninteractive", id]))
+    }
 }
 
-pub fn detect_os() -> io::Result<Option<Os>> {
-    if cfg!(target_os = "linux") && cfg!(target_arch = "x86_64") {
-        let os_release = std::fs::read_to_string("/etc/os-release")?;
+fn flatpak_args<'a>(scope: &FlatpakScope, subcommand: &'a str, args: &[&'a str]) -> Vec<&'a str> {
+    [subcommand, scope.flag()]
+        .into_iter()
+        .chain(args.iter().copied())
+        .collect()
+}
 
-        if os_release.contains("Ubuntu") {
-            Ok(Some(UBUNTU_X64))
-        } else {
-            Ok(None)
-        }
+fn exec_flatpak(scope: &FlatpakScope, args: &[&str]) -> Result<(), String> {
+    let output = match scope {
+        User => exec_cmd("flatpak", args),
+        System => exec_cmd("sudo", &[&["flatpak"], args].concat()),
+    }.map_err(|error| error.to_string())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    verbose!("{}", stdout);
+
+    Ok(())
+}
+
+fn brew_args<'a>(subcommand: &'a str, name: &'a str, cask: bool) -> Vec<&'a str> {
+    if cask {
+        vec![subcommand, "--cask", name]
+    } else {
+        vec![subcommand, name]
+    }
+}
+
+pub fn detect_os() -> io::Result<Option<Os>> {
+    if cfg!(target_os = "linux") {
+        let Some(arch) = linux_arch() else { return Ok(None) };
+        let os_release = fs::read_to_string("/etc/os-release")?;
+
+        WSL.get_or_init(|| fs::read_to_string("/proc/version")
+            .is_ok_and(|proc_version| is_wsl_kernel(&proc_version))
+        );
+
+        Ok(linux_type_of(&os_release).map(|linux_type| Linux(arch, linux_type)))
+    } else if cfg!(target_os = "macos") && cfg!(target_arch = "x86_64") {
+        Ok(Some(MACOS_X64))
+    } else if cfg!(target_os = "macos") && cfg!(target_arch = "aarch64") {
+        Ok(Some(MACOS_ARM64))
+    } else if cfg!(target_os = "windows") && cfg!(target_arch = "x86_64") {
+        Ok(Some(WINDOWS_X64))
     } else {
         Ok(None)
     }
 }
 
+/// `arm` builds target the `armhf` Raspberry Pi OS.
+fn linux_arch() -> Option<OsArch> {
+    if cfg!(target_arch = "x86_64") {
+        Some(X64)
+    } else if cfg!(target_arch = "aarch64") {
+        Some(Arm64)
+    } else if cfg!(target_arch = "arm") {
+        Some(Armhf)
+    } else {
+        None
+    }
+}
+
+/// Derivatives list their base in `ID_LIKE`, so they go before Debian, which
+/// they list too, and 32-bit Raspberry Pi OS has `ID=raspbian`.
+fn linux_type_of(os_release: &str) -> Option<LinuxType> {
+    let field = |name: &str| os_release
+        .lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix('='))
+        .map(|value| value.trim_matches('"'))
+        .unwrap_or_default();
+
+    let id = field("ID");
+    let is_like = |distro: &str| id == distro || field("ID_LIKE").split_whitespace().any(|like| like == distro);
+
+    if is_like("ubuntu") {
+        Some(Ubuntu)
+    } else if id == "debian" || id == "raspbian" {
+        Some(Debian)
+    } else if is_like("fedora") {
+        Some(Fedora)
+    } else if is_like("arch") {
+        Some(Arch)
+    } else {
+        None
+    }
+}
+
+fn is_wsl_kernel(proc_version: &str) -> bool {
+    let proc_version = proc_version.to_lowercase();
+
+    proc_version.contains("microsoft") || proc_version.contains("wsl")
+}
+
+pub fn has_systemd() -> bool {
+    Path::new("/run/systemd/system").is_dir()
+}
+
+pub fn warn_without_systemd() {
+    static WARNED: Once = Once::new();
+
+    if !has_systemd() {
+        WARNED.call_once(|| info!("⚠️ systemd isn't running, so services won't start. On WSL, enable it with `systemd=true` under `[boot]` in /etc/wsl.conf and restart WSL with `wsl --shutdown`."));
+    }
+}
+
 /// Notice: It may return a list of truncated process names, so check for
 /// prefixes when trying to find a process name. For example, it may return
 /// "jetbrains-toolb" instead of "jetbrains-toolbox."
 pub fn get_running_processes(os: Os) -> Result<Vec<String>, String> {
     match os {
-        Linux(X64, Ubuntu) => get_running_processes_ubuntu()
+        Linux(_, _) | MacOs(_) => get_running_processes_ubuntu(),
+        Windows(_) => get_running_processes_windows(),
     }
 }
 
@@ -127,7 +478,8 @@ fn get_running_processes_ubuntu() -> Result<Vec<String>, String> {
 
 pub fn kill_process(os: Os, process_name: &str) -> Result<(), String> {
     match os {
-        Linux(X64, Ubuntu) => kill_process_ubuntu(process_name)
+        Linux(_, _) | MacOs(_) => kill_process_ubuntu(process_name),
+        Windows(_) => kill_process_windows(process_name),
     }
 }
 
@@ -138,6 +490,26 @@ fn kill_process_ubuntu(process_name: &str) -> Result<(), String> {
     Ok(())
 }
 
+fn get_running_processes_windows() -> Result<Vec<String>, String> {
+    let output = exec_powershell("Get-Process | ForEach-Object ProcessName")
+        .map_err(|error| error.to_string())?;
+
+    let processes = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<String>>();
+
+    Ok(processes)
+}
+
+fn kill_process_windows(process_name: &str) -> Result<(), String> {
+    exec_powershell(&format!("Stop-Process -Name '{process_name}' -Force"))
+        .map_err(|error| error.to_string())?;
+
+    Ok(())
+}
+
 /// Notice: Similar to `get_running_processes`, the `process_name_prefix`
 /// argument must be a prefix of the actual process name since the low-level
 /// commands will probably truncate the name.
@@ -198,7 +570,14 @@ pub fn kill_process_and_wait(
     Ok(())
 }
 
+pub mod apt_repo;
+
 pub mod linux {
+    use std::fs;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::path::Path;
+
     pub fn expand_home_path(path: &str) -> String {
         if path.starts_with("~") {
             dirs::home_dir()
@@ -209,9 +588,83 @@ pub mod linux {
         }
     }
 
+    pub fn append_lines(path: &Path, lines: &[&str]) -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|error| format!("Fail to open {:?}: {}", path, error))?;
+
+        let contents = lines
+            .iter()
+            .fold(String::new(), |acc, line| format!("{acc}{line}\n"));
+
+        writeln!(file, "{contents}")
+            .map_err(|error| format!("Fail to write to {:?}: {}", path, error))
+    }
+
+    pub fn has_line(path: &Path, line: &str) -> bool {
+        fs::read_to_string(path)
+            .is_ok_and(|contents| contents.lines().any(|current| current.trim() == line.trim()))
+    }
+
+    pub fn remove_lines(path: &Path, lines: &[&str]) -> Result<(), String> {
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Fail to read {:?}: {}", path, error))?;
+
+        let is_removed = |line: &str| lines
+            .iter()
+            .any(|removed| removed.trim() == line.trim() && !line.trim().is_empty());
+
+        let cleaned = contents
+            .lines()
+            .filter(|line| !is_removed(line))
+            .fold(String::new(), |acc, line| format!("{acc}{line}\n"));
+
+        fs::write(path, cleaned)
+            .map_err(|error| format!("Fail to write {:?}: {}", path, error))
+    }
+
+    pub type BlockMarkers<'a> = (&'a str, &'a str);
+
+    pub fn replace_block(contents: &str, (start, end): BlockMarkers, block: Option<&[String]>) -> String {
+        let mut in_block = false;
+        let mut lines = contents
+            .lines()
+            .filter(|line| {
+                let is_block = in_block || *line == start;
+
+                in_block = is_block && *line != end;
+
+                !is_block
+            })
+            .map(String::from)
+            .collect::<Vec<String>>();
+
+        if let Some(block) = block {
+            lines.push(start.to_string());
+            lines.extend(block.iter().cloned());
+            lines.push(end.to_string());
+        }
+
+        lines.iter().map(|line| format!("{line}\n")).collect()
+    }
+
+    pub fn write_block(path: &Path, markers: BlockMarkers, block: Option<&[String]>) -> Result<(), String> {
+        let contents = fs::read_to_string(path).unwrap_or_default();
+
+        fs::write(path, replace_block(&contents, markers, block))
+            .map_err(|error| format!("Fail to write {:?}: {}", path, error))
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
+        use crate::tmp::TmpWorkingDir;
         use dirs::home_dir;
 
         #[test]
@@ -263,5 +716,128 @@ pub mod linux {
 
             assert_eq!(expand_home_path(path_with_trailing_slash), expected_path);
         }
+
+        #[test]
+        fn appends_and_removes_lines() {
+            let tmp = TmpWorkingDir::new().unwrap();
+            let rc = tmp.join(Path::new(".bashrc"));
+            let lines = ["# Tool", r#"export PATH="$PATH:$HOME/.tool/bin""#];
+
+            fs::write(&rc, "alias ll='ls -l'\n").unwrap();
+
+            append_lines(&rc, &lines).unwrap();
+
+            let contents = fs::read_to_string(&rc).unwrap();
+
+            assert!(contents.contains(r#"export PATH="$PATH:$HOME/.tool/bin""#));
+            assert!(contents.starts_with("alias ll='ls -l'\n# Tool\n"));
+            assert!(has_line(&rc, "# Tool"));
+
+            remove_lines(&rc, &lines).unwrap();
+
+            let contents = fs::read_to_string(&rc).unwrap();
+
+            assert!(!contents.contains("# Tool"));
+            assert!(!contents.contains(".tool/bin"));
+            assert!(contents.contains("alias ll='ls -l'"));
+            assert!(!has_line(&rc, "# Tool"));
+        }
+
+        #[test]
+        fn replaces_block() {
+            let markers = ("# >>> tool >>>", "# <<< tool <<<");
+            let block = vec!["alias ll='ls -alF'".to_string()];
+            let contents = "export A=1\n# >>> tool >>>\nalias l='ls'\n# <<< tool <<<\nexport B=2\n";
+            let replaced = replace_block(contents, markers, Some(&block));
+
+            assert_eq!(
+                "export A=1\nexport B=2\n# >>> tool >>>\nalias ll='ls -alF'\n# <<< tool <<<\n",
+                replaced,
+            );
+            assert_eq!(replaced, replace_block(&replaced, markers, Some(&block)));
+            assert_eq!("export A=1\nexport B=2\n", replace_block(&replaced, markers, None));
+        }
+
+        #[test]
+        fn remove_lines_ignores_missing_file() {
+            let tmp = TmpWorkingDir::new().unwrap();
+            let rc = tmp.join(Path::new(".zshrc"));
+
+            assert!(remove_lines(&rc, &["# Tool"]).is_ok());
+            assert!(!rc.exists());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::os::{brew_args, flatpak_args, is_wsl_kernel, linux_type_of, ARCH_X64, MACOS_ARM64, UBUNTU_X64, WINDOWS_X64};
+    use crate::os::LinuxType::{Arch, Debian, Fedora, Ubuntu};
+    use crate::os::FlatpakScope::{System, User};
+    use crate::os::PkgType::{Brew, BrewCask, Pacman, Winget};
+
+    #[test]
+    fn detects_linux_type() {
+        let ubuntu = "PRETTY_NAME=\"Ubuntu 24.04 LTS\"\nNAME=\"Ubuntu\"\nID=ubuntu\nID_LIKE=debian\nVERSION_CODENAME=noble\n";
+        let debian = "PRETTY_NAME=\"Debian GNU/Linux 12 (bookworm)\"\nNAME=\"Debian GNU/Linux\"\nID=debian\nVERSION_CODENAME=bookworm\n";
+        let fedora = "NAME=\"Fedora Linux\"\nID=fedora\nVERSION_ID=40\n";
+        let rocky = "NAME=\"Rocky Linux\"\nID=\"rocky\"\nID_LIKE=\"rhel centos fedora\"\n";
+        let arch = "NAME=\"Arch Linux\"\nID=arch\n";
+        let manjaro = "NAME=\"Manjaro Linux\"\nID=manjaro\nID_LIKE=arch\n";
+        let mint = "NAME=\"Linux Mint\"\nID=linuxmint\nID_LIKE=\"ubuntu debian\"\nVERSION_CODENAME=virginia\nUBUNTU_CODENAME=jammy\n";
+        let pop_os = "NAME=\"Pop!_OS\"\nID=pop\nID_LIKE=\"ubuntu debian\"\nVERSION_CODENAME=jammy\nUBUNTU_CODENAME=jammy\n";
+        let elementary = "NAME=\"elementary OS\"\nID=elementary\nID_LIKE=ubuntu\nVERSION_CODENAME=horus\nUBUNTU_CODENAME=jammy\n";
+        let raspbian = "PRETTY_NAME=\"Raspbian GNU/Linux 12 (bookworm)\"\nID=raspbian\nID_LIKE=debian\nVERSION_CODENAME=bookworm\n";
+        let alpine = "NAME=\"Alpine Linux\"\nID=alpine\n";
+
+        assert_eq!(Some(Ubuntu), linux_type_of(ubuntu));
+        assert_eq!(Some(Ubuntu), linux_type_of(mint));
+        assert_eq!(Some(Ubuntu), linux_type_of(pop_os));
+        assert_eq!(Some(Ubuntu), linux_type_of(elementary));
+        assert_eq!(Some(Debian), linux_type_of(debian));
+        assert_eq!(Some(Debian), linux_type_of(raspbian));
+        assert_eq!(Some(Fedora), linux_type_of(fedora));
+        assert_eq!(Some(Fedora), linux_type_of(rocky));
+        assert_eq!(Some(Arch), linux_type_of(arch));
+        assert_eq!(Some(Arch), linux_type_of(manjaro));
+        assert_eq!(None, linux_type_of(alpine));
+    }
+
+    #[test]
+    fn detects_wsl_kernel() {
+        assert!(is_wsl_kernel("Linux version 5.15.153.1-microsoft-standard-WSL2 (root@941d701f84f1) (gcc (GCC) 11.2.0) #1 SMP"));
+        assert!(is_wsl_kernel("Linux version 4.4.0-19041-Microsoft (Microsoft@Microsoft.com) (gcc version 5.4.0) #1237-Microsoft"));
+        assert!(!is_wsl_kernel("Linux version 6.8.0-40-generic (buildd@lcy02-amd64-078) (x86_64-linux-gnu-gcc-13 (Ubuntu 13.2.0-23ubuntu4) 13.2.0) #40-Ubuntu SMP"));
+    }
+
+    #[test]
+    fn reads_os_conventions() {
+        assert_eq!("ubuntu", UBUNTU_X64.distro_id());
+        assert_eq!("macos", MACOS_ARM64.distro_id());
+        assert_eq!(Pacman, ARCH_X64.pkg_type());
+        assert_eq!(Brew, MACOS_ARM64.pkg_type());
+        assert_eq!(BrewCask, MACOS_ARM64.app_pkg_type());
+        assert_eq!(Ok(".profile"), UBUNTU_X64.login_profile());
+        assert_eq!(Ok(".zprofile"), MACOS_ARM64.login_profile());
+        assert!(MACOS_ARM64.shell_rc_files().unwrap().contains(&".zshrc"));
+        assert_eq!(Winget, WINDOWS_X64.app_pkg_type());
+    }
+
+    #[test]
+    fn builds_brew_args() {
+        assert_eq!(vec!["install", "jq"], brew_args("install", "jq", false));
+        assert_eq!(vec!["uninstall", "--cask", "zoom"], brew_args("uninstall", "zoom", true));
+    }
+
+    #[test]
+    fn builds_flatpak_args() {
+        assert_eq!(
+            vec!["install", "--user", "--noninteractive", "flathub", "org.gimp.GIMP"],
+            flatpak_args(&User, "install", &["--noninteractive", "flathub", "org.gimp.GIMP"]),
+        );
+        assert_eq!(
+            vec!["uninstall", "--system", "--noninteractive", "org.gimp.GIMP"],
+            flatpak_args(&System, "uninstall", &["--noninteractive", "org.gimp.GIMP"]),
+        );
     }
 }
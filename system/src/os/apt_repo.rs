@@ -0,0 +1,144 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::path::Path;
+
+use crate::cmd::{exec_cmd, print_output};
+use crate::download::gpg::GpgKey;
+use crate::tmp::TmpWorkingDir;
+
+const KEYRINGS_DIR: &str = "/etc/apt/keyrings";
+
+/// Derivatives, like Linux Mint, prefer the Ubuntu codename they're based on.
+pub const CODENAME: &str = "$(. /etc/os-release && echo ${UBUNTU_CODENAME:-$VERSION_CODENAME})";
+
+/// Linux Mint and elementary OS prefer the Ubuntu release they're based on.
+pub const RELEASE: &str = "$(if [ -f /etc/upstream-release/lsb-release ]; \
+    then . /etc/upstream-release/lsb-release && echo $DISTRIB_RELEASE; \
+    else . /etc/os-release && echo $VERSION_ID; fi)";
+
+fn keyring_path(name: &str) -> String {
+    format!("{KEYRINGS_DIR}/{name}.gpg")
+}
+
+fn source_list_path(name: &str) -> String {
+    format!("/etc/apt/sources.list.d/{name}.list")
+}
+
+fn source_entry(keyring: &str, source: &str) -> String {
+    format!("deb [arch=$(dpkg --print-architecture) signed-by={keyring}] {source}")
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.' | '_'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid APT repository name {name}"))
+    }
+}
+
+fn exec_bash(bash_cmd: &str) -> Result<(), String> {
+    let output = exec_cmd("bash", &["-c", bash_cmd])
+        .map_err(|error| error.to_string())?;
+
+    print_output(output);
+
+    Ok(())
+}
+
+/// Bash evaluates the `source`, so it can read the OS release, like `CODENAME`.
+pub fn add(name: &str, key: &GpgKey, source: &str) -> Result<(), String> {
+    check_name(name)?;
+
+    let tmp = TmpWorkingDir::new()
+        .map_err(|error| error.to_string())?;
+
+    let key_file = tmp.join(Path::new(&format!("{name}.asc")));
+    let key_file = key_file.to_str().unwrap();
+    let keyring = keyring_path(name);
+
+    info!("Fetching {name} repository key...");
+
+    exec_bash(&format!("curl --proto '=https' --tlsv1.2 -fsSL {} -o {key_file}", key.url()))?;
+
+    key.check_key_file(Path::new(key_file))?;
+
+    info!("Adding {name} repository...");
+
+    // Some vendors publish the key already dearmored (binary keyring)
+    exec_bash(&format!(
+        "sudo install -m 0755 -d {KEYRINGS_DIR} && \
+        if grep -q 'BEGIN PGP PUBLIC KEY BLOCK' {key_file}; \
+        then sudo gpg --dearmor --yes -o {keyring} {key_file}; \
+        else sudo cp {key_file} {keyring}; fi && \
+        sudo chmod a+r {keyring}"
+    ))?;
+
+    exec_bash(&format!(
+        r#"echo "{}" | sudo tee {}"#,
+        source_entry(&keyring, source),
+        source_list_path(name),
+    ))?;
+
+    update()
+}
+
+pub fn remove(name: &str) -> Result<(), String> {
+    check_name(name)?;
+
+    info!("Removing {name} repository...");
+
+    let output = exec_cmd(
+        "sudo",
+        &["rm", "-f", &source_list_path(name), &keyring_path(name)],
+    ).map_err(|error| error.to_string())?;
+
+    print_output(output);
+
+    update()
+}
+
+pub fn update() -> Result<(), String> {
+    let output = exec_cmd("sudo", &["apt-get", "update"])
+        .map_err(|error| error.to_string())?;
+
+    print_output(output);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::os::apt_repo::{check_name, keyring_path, source_entry, source_list_path};
+
+    #[test]
+    fn places_repository_files() {
+        assert_eq!("/etc/apt/keyrings/github-cli.gpg", keyring_path("github-cli"));
+        assert_eq!("/etc/apt/sources.list.d/github-cli.list", source_list_path("github-cli"));
+    }
+
+    #[test]
+    fn formats_source_entry() {
+        assert_eq!(
+            "deb [arch=$(dpkg --print-architecture) signed-by=/etc/apt/keyrings/github-cli.gpg] https://cli.github.com/packages stable main",
+            source_entry(&keyring_path("github-cli"), "https://cli.github.com/packages stable main"),
+        );
+    }
+
+    #[test]
+    fn checks_repository_name() {
+        assert!(check_name("github-cli").is_ok());
+        assert!(check_name("pgdg").is_ok());
+        assert!(check_name("").is_err());
+        assert!(check_name("../sources").is_err());
+        assert!(check_name("gh; rm -rf /").is_err());
+        assert!(check_name("Docker").is_err());
+    }
+}
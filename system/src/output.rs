@@ -0,0 +1,88 @@
+// Copyright (c) 2024 Tobias Briones. All rights reserved.
+// SPDX-License-Identifier: GPL-3.0-or-later
+// This file is part of https://github.com/mathswe-ops/mathswe-ops---mvp
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Errors and command reports, like `list`, always print.
+#[derive(PartialEq, PartialOrd, Clone, Copy, Debug)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+    Debug,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+impl Verbosity {
+    /// `--quiet` takes precedence over `-v`.
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Debug,
+        }
+    }
+
+    fn from_u8(level: u8) -> Self {
+        match level {
+            0 => Verbosity::Quiet,
+            1 => Verbosity::Normal,
+            2 => Verbosity::Verbose,
+            _ => Verbosity::Debug,
+        }
+    }
+}
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    Verbosity::from_u8(VERBOSITY.load(Ordering::Relaxed))
+}
+
+pub fn enabled(level: Verbosity) -> bool {
+    verbosity() >= level
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::output::enabled($crate::output::Verbosity::Normal) {
+            println!($($arg)*);
+        }
+    };
+}
+
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::output::enabled($crate::output::Verbosity::Verbose) {
+            println!($($arg)*);
+        }
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::output::enabled($crate::output::Verbosity::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::Verbosity;
+
+    #[test]
+    fn reads_verbosity_flags() {
+        assert_eq!(Verbosity::Normal, Verbosity::from_flags(0, false));
+        assert_eq!(Verbosity::Verbose, Verbosity::from_flags(1, false));
+        assert_eq!(Verbosity::Debug, Verbosity::from_flags(2, false));
+        assert_eq!(Verbosity::Debug, Verbosity::from_flags(3, false));
+        assert_eq!(Verbosity::Quiet, Verbosity::from_flags(0, true));
+        assert!(Verbosity::Quiet < Verbosity::Normal);
+    }
+}
@@ -10,7 +10,9 @@ use de::Visitor;
 use reqwest::Url;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use VersionError::DigitIntError;
-use crate::download::{DownloadRequest, Integrity};
+use crate::download::auth::Auth;
+use crate::download::tls::PublicKeyPin;
+use crate::download::{DownloadOptions, DownloadRequest, Integrity};
 use crate::os::{Os, OsPkg, PkgType};
 use crate::package::VersionError::InvalidDigit;
 
@@ -195,6 +197,61 @@ impl<'de> Deserialize<'de> for SemVerVendor {
     }
 }
 
+/// A release channel version like `8.0`, as in `dotnet-sdk-8.0`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct MajorMinor(pub u8, pub u8);
+
+impl Display for MajorMinor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.0, self.1)
+    }
+}
+
+impl FromStr for MajorMinor {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_to_version_error = |parse_error: ParseIntError| DigitIntError(parse_error);
+
+        let parts: Vec<&str> = s.split('.').collect();
+
+        if parts.len() != 2 {
+            return Err(InvalidDigit(format!("String {} must have 2 digits but has {}", s, parts.len())));
+        }
+
+        let major = parts[0].parse::<u8>().map_err(parse_to_version_error)?;
+        let minor = parts[1].parse::<u8>().map_err(parse_to_version_error)?;
+
+        Ok(MajorMinor(major, minor))
+    }
+}
+
+impl Serialize for MajorMinor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+struct MajorMinorVisitor;
+
+impl<'de> Visitor<'de> for MajorMinorVisitor {
+    type Value = MajorMinor;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a version string in the format x.y")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        MajorMinor::from_str(v).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for MajorMinor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(MajorMinorVisitor)
+    }
+}
+
 #[derive(PartialEq, Clone, Debug)]
 pub struct YearSemVer(pub u16, pub u8, pub u8, pub u8);
 
@@ -303,6 +360,11 @@ pub struct Package {
     pub os: Os,
     pub doc: Url,
     pub fetch: DownloadRequest,
+    managed: bool,
+    mirror_dirs: Vec<Url>,
+    auth: Option<Auth>,
+    pins: Vec<PublicKeyPin>,
+    download_options: DownloadOptions,
 }
 
 impl Package {
@@ -312,7 +374,18 @@ impl Package {
         doc: Url,
         fetch: DownloadRequest,
     ) -> Self {
-        Package { name: name.to_string(), os, software, doc, fetch }
+        Package {
+            name: name.to_string(),
+            os,
+            software,
+            doc,
+            fetch,
+            managed: false,
+            mirror_dirs: vec![],
+            auth: None,
+            pins: vec![],
+            download_options: DownloadOptions::default(),
+        }
     }
 
     /// Creates a managed `Package` that doesn't have a download URL because a
@@ -327,7 +400,56 @@ impl Package {
     ) -> Self {
         let download_req = DownloadRequest::new(doc.as_str(), Integrity::None).unwrap();
 
-        Self::new(name, os, software, doc, download_req)
+        Package { managed: true, ..Self::new(name, os, software, doc, download_req) }
+    }
+
+    pub fn is_managed(&self) -> bool {
+        self.managed
+    }
+
+    /// The URL downloaded without an integrity check, like a piped vendor script.
+    pub fn unverified_download(&self) -> Option<Url> {
+        let unverified = !self.managed && self.fetch.integrity() == Integrity::None;
+
+        unverified.then(|| self.fetch.url())
+    }
+
+    pub fn set_mirror_dirs(&mut self, mirror_dirs: Vec<Url>) {
+        self.fetch = self.fetch.clone().with_mirror_dirs(&mirror_dirs);
+        self.mirror_dirs = mirror_dirs;
+    }
+
+    pub fn set_auth(&mut self, auth: Auth) {
+        self.fetch = self.fetch.clone().with_auth(auth.clone());
+        self.auth = Some(auth);
+    }
+
+    pub fn set_pins(&mut self, pins: Vec<PublicKeyPin>) {
+        self.fetch = self.fetch.clone().with_pins(pins.clone());
+        self.pins = pins;
+    }
+
+    pub fn set_download_options(&mut self, download_options: DownloadOptions) {
+        self.fetch = self.fetch.clone().with_options(download_options.clone());
+        self.download_options = download_options;
+    }
+
+    /// Credentials and pins only apply to downloads from the package host.
+    pub fn download_request(&self, req: DownloadRequest) -> DownloadRequest {
+        let req = req
+            .with_mirror_dirs(&self.mirror_dirs)
+            .with_options(self.download_options.clone());
+
+        if req.url().host_str() != self.fetch.url().host_str() {
+            return req;
+        }
+
+        let req = req.with_pins(self.pins.clone());
+
+        match &self.auth {
+            Some(auth) => req.with_auth(auth.clone()),
+            None => req,
+        }
     }
 
     pub fn to_os_pkg(&self, pkg_type: PkgType) -> OsPkg {
@@ -345,10 +467,11 @@ impl Display for Package {
 mod tests {
     use std::str::FromStr;
     use reqwest::Url;
+    use crate::download::auth::Auth;
     use crate::download::{DownloadRequest, Integrity};
     use crate::download::gpg::GpgKey;
     use crate::os::UBUNTU_X64;
-    use crate::package::{Package, SemVer, SemVerRev, SemVerVendor, Software, YearSemVer};
+    use crate::package::{MajorMinor, Package, SemVer, SemVerRev, SemVerVendor, Software, YearSemVer};
 
     #[test]
     fn semver_to_string() {
@@ -429,6 +552,23 @@ mod tests {
         assert_eq!(format!("\"{}\"", ver.to_string()), ser);
     }
 
+    #[test]
+    fn major_minor_from_str() {
+        let ver = MajorMinor::from_str("8.0").unwrap();
+
+        assert_eq!(MajorMinor(8, 0), ver);
+        assert_eq!("8.0", ver.to_string());
+        assert!(MajorMinor::from_str("8.0.1").is_err());
+    }
+
+    #[test]
+    fn major_minor_deserialize() {
+        let ver: MajorMinor = serde_json::from_str("\"8.3\"")
+            .expect("Fail to deserialize MajorMinor");
+
+        assert_eq!(MajorMinor(8, 3), ver);
+    }
+
     #[test]
     fn year_semver_from_str() {
         let sem_ver_str = "2024.2.3.1";
@@ -493,5 +633,45 @@ mod tests {
 
         assert_eq!("zoom", package.name);
         assert_eq!(UBUNTU_X64, package.os);
+        assert_eq!(None, package.unverified_download());
+    }
+
+    #[test]
+    fn finds_unverified_download() {
+        let software = Software::new("Rust Team", "Rust", "latest");
+        let doc = Url::parse("https://www.rust-lang.org/tools/install").unwrap();
+        let script = Package::new(
+            "rust",
+            UBUNTU_X64,
+            software.clone(),
+            doc.clone(),
+            DownloadRequest::new("https://sh.rustup.rs", Integrity::None).unwrap(),
+        );
+        let managed = Package::new_managed("rust", UBUNTU_X64, software, doc);
+
+        assert_eq!(Some(Url::parse("https://sh.rustup.rs").unwrap()), script.unverified_download());
+        assert_eq!(None, managed.unverified_download());
+    }
+
+    #[test]
+    fn scopes_auth_to_package_host() {
+        let software = Software::new("JFrog", "Artifactory", "7.90.7");
+        let doc = Url::parse("https://jfrog.com/help").unwrap();
+        let fetch = DownloadRequest::new("https://releases.jfrog.io/pkg.tar.gz", Integrity::None).unwrap();
+        let auth = Auth::Bearer { token_env: "JFROG_TOKEN".to_string() };
+        let mut package = Package::new("artifactory", UBUNTU_X64, software, doc, fetch);
+
+        package.set_auth(auth.clone());
+
+        let same_host = package.download_request(
+            DownloadRequest::new("https://releases.jfrog.io/key.asc", Integrity::None).unwrap()
+        );
+        let other_host = package.download_request(
+            DownloadRequest::new("https://keys.example.com/key.asc", Integrity::None).unwrap()
+        );
+
+        assert_eq!(Some(auth.clone()), package.fetch.auth());
+        assert_eq!(Some(auth), same_host.auth());
+        assert_eq!(None, other_host.auth());
     }
 }
@@ -7,13 +7,31 @@ use std::path::{Path, PathBuf};
 
 use tempfile::TempDir;
 
+/// Lets the stale directories of interrupted operations be found.
+pub const TMP_DIR_PREFIX: &str = "mathswe-ops_";
+
+/// `~/.cache/mathswe-ops`
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("mathswe-ops"))
+}
+
+/// `~/.local/share/mathswe-ops`
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("mathswe-ops"))
+}
+
+/// `~/.config/mathswe-ops`
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("mathswe-ops"))
+}
+
 pub struct TmpWorkingDir {
     dir: TempDir,
 }
 
 impl TmpWorkingDir {
     pub fn new() -> io::Result<Self> {
-        let temp_dir = TempDir::with_prefix("mathswe-ops_")?;
+        let temp_dir = TempDir::with_prefix(TMP_DIR_PREFIX)?;
 
         Ok(TmpWorkingDir { dir: temp_dir })
     }